@@ -0,0 +1,184 @@
+use crate::project::config::{AgentConfig, Permission, PROJECT_CONFIG_FILE};
+use serde::Deserialize;
+use std::ffi::OsStr;
+use std::path::Path;
+
+#[derive(Deserialize, Debug)]
+pub struct Step {
+    pub reason: Option<String>,
+    #[serde(flatten)]
+    pub action: Action,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum Action {
+    ReadFile { path: String },
+    WriteFile { path: String, content: String },
+    Run { command: String },
+    Done { summary: String },
+}
+
+impl Action {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::ReadFile { .. } => "read_file",
+            Self::WriteFile { .. } => "write_file",
+            Self::Run { .. } => "run",
+            Self::Done { .. } => "done",
+        }
+    }
+
+    pub fn describe(&self) -> String {
+        match self {
+            Self::ReadFile { path } => format!("read {}", path),
+            Self::WriteFile { path, content } => {
+                format!("write {} ({} lines)", path, content.lines().count())
+            }
+            Self::Run { command } => format!("run `{}`", command),
+            Self::Done { .. } => "done".to_string(),
+        }
+    }
+
+    pub fn path(&self) -> Option<&str> {
+        match self {
+            Self::ReadFile { path } | Self::WriteFile { path, .. } => Some(path),
+            Self::Run { .. } | Self::Done { .. } => None,
+        }
+    }
+
+    // Writing .termai.toml would let the agent widen its own [agent] and [sandbox] rules,
+    // a symlink to it counts as well.
+    pub fn writes_project_config(&self, directory: &Path) -> bool {
+        let Self::WriteFile { path, .. } = self else {
+            return false;
+        };
+        let target = directory.join(path);
+        let resolved = target.canonicalize().unwrap_or_else(|_| target.clone());
+        [target, resolved]
+            .iter()
+            .any(|path| path.file_name() == Some(OsStr::new(PROJECT_CONFIG_FILE)))
+    }
+
+    pub fn permission(&self, config: &AgentConfig) -> Permission {
+        match self {
+            Self::ReadFile { .. } => config.read_file,
+            Self::WriteFile { .. } => config.write_file,
+            Self::Run { .. } => config.run,
+            Self::Done { .. } => Permission::Auto,
+        }
+    }
+}
+
+// Takes the outermost JSON object, models tend to wrap it in a code fence.
+pub fn parse_step(answer: &str) -> Result<Step, String> {
+    let start = answer.find('{').ok_or("there is no JSON object")?;
+    let end = answer.rfind('}').ok_or("the JSON object is not closed")?;
+    if end < start {
+        return Err("the JSON object is not closed".to_string());
+    }
+    serde_json::from_str(&answer[start..=end]).map_err(|err| err.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn parses_an_action_inside_a_code_fence() {
+        let answer = "Here you go:\n```json\n{\"reason\": \"look\", \"action\": \"read_file\", \"path\": \"src/main.rs\"}\n```";
+        let step = parse_step(answer).unwrap();
+        assert_eq!(step.reason.as_deref(), Some("look"));
+        assert!(matches!(step.action, Action::ReadFile { ref path } if path == "src/main.rs"));
+    }
+
+    #[test]
+    fn parses_each_action() {
+        let write =
+            parse_step(r#"{"action": "write_file", "path": "a", "content": "{}"}"#).unwrap();
+        assert!(matches!(write.action, Action::WriteFile { ref content, .. } if content == "{}"));
+        let run = parse_step(r#"{"action": "run", "command": "cargo test"}"#).unwrap();
+        assert_eq!(run.action.name(), "run");
+        let done = parse_step(r#"{"action": "done", "summary": "ok"}"#).unwrap();
+        assert!(done.reason.is_none());
+        assert_eq!(done.action.name(), "done");
+    }
+
+    #[test]
+    fn rejects_answers_that_are_not_one_action() {
+        assert!(parse_step("no json here").is_err());
+        assert!(parse_step("} backwards {").is_err());
+        assert!(parse_step(r#"{"action": "delete_file", "path": "a"}"#).is_err());
+        assert!(parse_step(r#"{"action": "read_file"}"#).is_err());
+        assert!(parse_step(
+            r#"{"action": "run", "command": "ls"} {"action": "done", "summary": ""}"#
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn only_file_actions_have_a_path() {
+        let read = parse_step(r#"{"action": "read_file", "path": "../x"}"#).unwrap();
+        assert_eq!(read.action.path(), Some("../x"));
+        let run = parse_step(r#"{"action": "run", "command": "cat ../x"}"#).unwrap();
+        assert_eq!(run.action.path(), None);
+    }
+
+    #[test]
+    fn permissions_follow_the_config_and_done_is_always_allowed() {
+        let config = AgentConfig {
+            read_file: Permission::Deny,
+            write_file: Permission::Auto,
+            run: Permission::Ask,
+        };
+        let permission = |answer: &str| parse_step(answer).unwrap().action.permission(&config);
+        assert_eq!(
+            permission(r#"{"action": "read_file", "path": "a"}"#),
+            Permission::Deny
+        );
+        assert_eq!(
+            permission(r#"{"action": "write_file", "path": "a", "content": ""}"#),
+            Permission::Auto
+        );
+        assert_eq!(
+            permission(r#"{"action": "run", "command": "ls"}"#),
+            Permission::Ask
+        );
+        assert_eq!(
+            permission(r#"{"action": "done", "summary": ""}"#),
+            Permission::Auto
+        );
+    }
+
+    #[test]
+    fn the_project_config_cannot_be_written() {
+        let directory = TempDir::new().unwrap();
+        let write = |path: &str| {
+            parse_step(&format!(
+                r#"{{"action": "write_file", "path": "{}", "content": ""}}"#,
+                path
+            ))
+            .unwrap()
+            .action
+        };
+        assert!(write(".termai.toml").writes_project_config(directory.path()));
+        assert!(write("./sub/.termai.toml").writes_project_config(directory.path()));
+        assert!(!write("src/main.rs").writes_project_config(directory.path()));
+        let read = parse_step(r#"{"action": "read_file", "path": ".termai.toml"}"#).unwrap();
+        assert!(!read.action.writes_project_config(directory.path()));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn a_symlink_to_the_project_config_cannot_be_written() {
+        use std::os::unix::fs::symlink;
+        let directory = TempDir::new().unwrap();
+        fs::write(directory.path().join(".termai.toml"), "").unwrap();
+        symlink(".termai.toml", directory.path().join("link")).unwrap();
+        let write =
+            parse_step(r#"{"action": "write_file", "path": "link", "content": ""}"#).unwrap();
+        assert!(write.action.writes_project_config(directory.path()));
+    }
+}
@@ -0,0 +1,312 @@
+use crate::agent::model::{parse_step, Action};
+use crate::changes::model::ChangeOrigin;
+use crate::changes::service::changes_service::{record_change, snapshot_files};
+use crate::common::terminal::{ask, is_interactive};
+use crate::config::repository::ConfigRepository;
+use crate::git::repository::repo_root;
+use crate::openai::model::role::Role;
+use crate::openai::service::prompt::follow_up;
+use crate::output::diff::render_diff;
+use crate::output::markdown::truncate;
+use crate::output::progress::status;
+use crate::project::config::{load_project_config, Permission, ProjectConfig, PROJECT_CONFIG_FILE};
+use crate::sandbox::model::policy::escapes_directory;
+use crate::sandbox::repository::AuditRepository;
+use crate::sandbox::service::sandbox_service::run_sandboxed;
+use crate::session::model::session::Session;
+use crate::session::repository::{AttachmentRepository, MessageRepository, SessionRepository};
+use crate::session::service::sessions_service::{session, session_add_messages};
+use crate::usage::model::watchdog::Watchdog;
+use crate::usage::repository::UsageRepository;
+use crate::usage::service::usage_service::session_usage;
+use anyhow::{anyhow, Result};
+use chrono::Local;
+use colored::*;
+use similar::TextDiff;
+use std::fs;
+use std::path::Path;
+
+const MAX_OUTPUT_CHARS: usize = 6000;
+const MAX_FILE_CHARS: usize = 40000;
+const SOURCE_TASK_WIDTH: usize = 40;
+
+const AGENT_PROMPT: &str = "
+You are an agent working on a task in a project directory, supervised by a developer in the terminal.
+Work in small steps. Answer every time with exactly one JSON object and nothing else, one of:
+{\"reason\": \"why\", \"action\": \"read_file\", \"path\": \"src/main.rs\"}
+{\"reason\": \"why\", \"action\": \"write_file\", \"path\": \"src/main.rs\", \"content\": \"the complete new content of the file\"}
+{\"reason\": \"why\", \"action\": \"run\", \"command\": \"cargo test\"}
+{\"reason\": \"why\", \"action\": \"done\", \"summary\": \"what was done and what is left\"}
+Paths are relative to the project directory. The result of each action comes back in the next message.
+The developer may refuse an action, then find another way or finish with done.";
+
+pub struct AgentInput<'a> {
+    pub task: &'a str,
+    pub session: Option<&'a str>,
+    pub max_steps: u32,
+    pub max_tokens: u32,
+    pub max_minutes: u64,
+    pub directory: &'a Path,
+}
+
+#[allow(clippy::too_many_arguments)]
+pub async fn run_agent<
+    R: ConfigRepository,
+    UR: UsageRepository,
+    AUR: AuditRepository,
+    SR: SessionRepository,
+    MR: MessageRepository,
+    AR: AttachmentRepository,
+>(
+    repo: &R,
+    usage_repo: &UR,
+    audit_repo: &AUR,
+    session_repo: &SR,
+    message_repo: &MR,
+    attachment_repo: &AR,
+    input: &AgentInput<'_>,
+) -> Result<()> {
+    // every write is recorded in the repository so `termai undo` can take it back
+    repo_root(input.directory)?;
+    let name = input
+        .session
+        .map(str::to_string)
+        .unwrap_or_else(|| format!("agent-{}", Local::now().format("%Y%m%d-%H%M%S")));
+    let mut session = session(session_repo, message_repo, attachment_repo, &name)?;
+    if !session
+        .messages
+        .iter()
+        .any(|message| message.role == Role::System && message.content == AGENT_PROMPT)
+    {
+        session.add_raw_message(AGENT_PROMPT.to_string(), Role::System);
+    }
+
+    let result = steps(repo, usage_repo, audit_repo, &mut session, input).await;
+    session_add_messages(session_repo, message_repo, attachment_repo, &session)?;
+    status(&format!(
+        "the steps are logged in session '{}'",
+        session.name
+    ));
+    result
+}
+
+async fn steps<R: ConfigRepository, UR: UsageRepository, AUR: AuditRepository>(
+    repo: &R,
+    usage_repo: &UR,
+    audit_repo: &AUR,
+    session: &mut Session,
+    input: &AgentInput<'_>,
+) -> Result<()> {
+    let config = load_project_config(input.directory);
+    let watchdog = Watchdog::new(input.max_tokens, input.max_minutes, None);
+    let earlier_usage = session_usage(usage_repo, &session.id).len();
+    let source = format!("agent: {}", truncate(input.task, SOURCE_TASK_WIDTH));
+    let mut message = format!("Task: {}", input.task);
+    for number in 1..=input.max_steps {
+        let usage = session_usage(usage_repo, &session.id);
+        let usage = &usage[earlier_usage.min(usage.len())..];
+        if let Some(reason) = watchdog.exceeded(usage) {
+            return Err(anyhow!("stopped after {} step(s), {}", number - 1, reason));
+        }
+        let answer = follow_up(
+            repo,
+            usage_repo,
+            "agent",
+            input.directory,
+            session,
+            &message,
+        )
+        .await?;
+        let step = match parse_step(&answer) {
+            Ok(step) => step,
+            Err(err) => {
+                status("the answer was not an action, asking again");
+                message = format!(
+                    "That was not a single action object ({}). Answer with exactly one JSON object.",
+                    err
+                );
+                continue;
+            }
+        };
+
+        println!(
+            "{} {}",
+            format!("step {}:", number).bold(),
+            step.action.describe()
+        );
+        if let Some(reason) = &step.reason {
+            println!("  {}", reason.dimmed());
+        }
+        if let Some(path) = step
+            .action
+            .path()
+            .filter(|path| escapes_directory(input.directory, path))
+        {
+            println!("  {}", "outside the project directory".yellow());
+            message = format!(
+                "{} is outside the project directory, only use paths under it.",
+                path
+            );
+            continue;
+        }
+        if step.action.writes_project_config(input.directory) {
+            println!(
+                "  {}",
+                "the agent may not change its own permissions".yellow()
+            );
+            message = format!(
+                "{} holds the agent's permissions and cannot be written. Find another way or finish with done.",
+                PROJECT_CONFIG_FILE
+            );
+            continue;
+        }
+        if let Action::Done { summary } = &step.action {
+            println!();
+            println!("{}", summary);
+            let usage = session_usage(usage_repo, &session.id);
+            let usage = &usage[earlier_usage.min(usage.len())..];
+            status(&format!("spent {}", watchdog.summary(usage)));
+            return Ok(());
+        }
+        let refusal = match step.action.permission(&config.agent) {
+            Permission::Auto => None,
+            Permission::Deny => Some("denied by [agent] in .termai.toml"),
+            Permission::Ask => match confirm(&step.action, input.directory)? {
+                true => None,
+                false => Some("refused"),
+            },
+        };
+        message = match refusal {
+            None => execute(
+                audit_repo,
+                &config,
+                &step.action,
+                input.directory,
+                &source,
+                &session.name,
+            ),
+            Some(refusal) => {
+                println!("  {}", refusal.yellow());
+                format!(
+                    "The developer refused this {} action. Find another way or finish with done.",
+                    step.action.name()
+                )
+            }
+        };
+    }
+    Err(anyhow!(
+        "stopped after {} steps without finishing, continue with `termai agent --session {} \"...\"`",
+        input.max_steps,
+        session.name
+    ))
+}
+
+fn confirm(action: &Action, directory: &Path) -> Result<bool> {
+    if !is_interactive() {
+        status(&format!(
+            "not running in a terminal, refusing the {} action (see [agent] in .termai.toml)",
+            action.name()
+        ));
+        return Ok(false);
+    }
+    if let Action::WriteFile { path, content } = action {
+        let before = fs::read_to_string(directory.join(path)).unwrap_or_default();
+        let diff = file_diff(path, &before, content);
+        for line in render_diff(&diff.lines().collect::<Vec<&str>>(), false) {
+            println!("  {}", line);
+        }
+    }
+    ask("  allow?", false)
+}
+
+fn execute<AUR: AuditRepository>(
+    audit_repo: &AUR,
+    config: &ProjectConfig,
+    action: &Action,
+    directory: &Path,
+    source: &str,
+    session_name: &str,
+) -> String {
+    match action {
+        Action::ReadFile { path } => match fs::read_to_string(directory.join(path)) {
+            Ok(content) if content.chars().count() > MAX_FILE_CHARS => format!(
+                "{} (only the first {} characters):\n```\n{}\n```",
+                path,
+                MAX_FILE_CHARS,
+                content.chars().take(MAX_FILE_CHARS).collect::<String>()
+            ),
+            Ok(content) => format!("{}:\n```\n{}\n```", path, content),
+            Err(err) => format!("Could not read {}: {}", path, err),
+        },
+        Action::WriteFile { path, content } => {
+            match write_file(directory, path, content, source, session_name) {
+                Ok(()) => format!("Wrote {}.", path),
+                Err(err) => format!("Could not write {}: {}", path, err),
+            }
+        }
+        Action::Run { command } => {
+            match run_sandboxed(audit_repo, &config.sandbox, command, directory, source) {
+                Ok(output) => {
+                    let code = output
+                        .status
+                        .code()
+                        .map(|code| code.to_string())
+                        .unwrap_or_else(|| "none".to_string());
+                    let line = format!("  exit code {}", code);
+                    match output.status.success() {
+                        true => println!("{}", line.green()),
+                        false => println!("{}", line.red()),
+                    }
+                    format!(
+                        "Exit code {}\n```\n{}{}\n```",
+                        code,
+                        tail(&String::from_utf8_lossy(&output.stdout)),
+                        tail(&String::from_utf8_lossy(&output.stderr))
+                    )
+                }
+                Err(err) => {
+                    println!("  {}", err.to_string().yellow());
+                    format!("The command did not run: {}", err)
+                }
+            }
+        }
+        Action::Done { .. } => String::new(),
+    }
+}
+
+fn write_file(
+    directory: &Path,
+    path: &str,
+    content: &str,
+    source: &str,
+    session_name: &str,
+) -> Result<()> {
+    let target = directory.join(path);
+    let before = fs::read_to_string(&target).unwrap_or_default();
+    let diff = file_diff(path, &before, content);
+    let snapshots = snapshot_files(directory, &diff)?;
+    if let Some(parent) = target.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(&target, content)?;
+    let origin = ChangeOrigin {
+        source: source.to_string(),
+        session: Some(session_name.to_string()),
+        message_id: None,
+    };
+    record_change(directory, &diff, origin, snapshots);
+    Ok(())
+}
+
+fn file_diff(path: &str, before: &str, after: &str) -> String {
+    TextDiff::from_lines(before, after)
+        .unified_diff()
+        .context_radius(3)
+        .header(&format!("a/{}", path), &format!("b/{}", path))
+        .to_string()
+}
+
+fn tail(output: &str) -> String {
+    let skip = output.chars().count().saturating_sub(MAX_OUTPUT_CHARS);
+    output.chars().skip(skip).collect()
+}
@@ -1,7 +1,9 @@
-use clap::Parser;
+use crate::openai::model::sampling::Sampling;
+use clap::{Parser, Subcommand};
+use clap_complete::Shell;
 
 #[derive(Parser, Debug)]
-#[command(version, about, long_about = None)]
+#[command(version, about, long_about = None, args_conflicts_with_subcommands = true)]
 pub struct Args {
     #[arg(short, long)]
     pub chat_gpt_api_key: Option<String>,
@@ -19,10 +21,602 @@ pub struct Args {
     pub sessions_all: bool,
     #[arg(long)]
     pub session: Option<String>,
+    #[arg(long)]
+    pub ignore_budget: bool,
+    #[arg(long)]
+    pub no_summarize: bool,
+    /// add the files of a named context set from [context.sets.<name>] in .termai.toml
+    #[arg(long)]
+    pub context_set: Option<String>,
+    /// focus the answer on a region open in an editor, e.g. src/main.rs:10-24
+    #[arg(long, value_name = "FILE:LINE[-LINE]")]
+    pub from_editor: Option<String>,
+    /// model to answer with instead of the configured one, see `termai models list`
+    #[arg(long)]
+    pub model: Option<String>,
+    /// sampling temperature from 0 to 2, lower gives more predictable answers
+    #[arg(long)]
+    pub temperature: Option<f32>,
+    /// only sample from the most likely tokens within this probability mass, 0 to 1
+    #[arg(long)]
+    pub top_p: Option<f32>,
+    /// upper limit on the tokens of the answer
+    #[arg(long)]
+    pub max_output_tokens: Option<u32>,
+    /// stop the answer when the model writes this text, can be given up to four times
+    #[arg(long, value_name = "TEXT")]
+    pub stop: Vec<String>,
+    /// answer with code only: prose around the code blocks is rejected and asked again,
+    /// and only the code is printed
+    #[arg(long)]
+    pub code_only: bool,
+    /// wait for the whole answer and print it formatted instead of as it arrives
+    #[arg(long)]
+    pub no_stream: bool,
+    /// ask the model even when the response cache holds an answer to this question
+    #[arg(long)]
+    pub no_cache: bool,
+    /// send piped input above paste_limit_tokens without asking
+    #[arg(long)]
+    pub send_large: bool,
+    /// language of the answer: `auto` follows the question, `off` leaves it to the model,
+    /// or a language name such as German
+    #[arg(long, value_name = "LANGUAGE")]
+    pub answer_language: Option<String>,
+    /// keep everything in memory, nothing is written to disk (after the subcommand when using one)
+    #[arg(long, global = true)]
+    pub read_only: bool,
+    /// treat the directory as trusted for this run (after the subcommand when using one)
+    #[arg(long, global = true)]
+    pub trust: bool,
+    /// hide progress and status messages (after the subcommand when using one)
+    #[arg(long, short = 'q', global = true)]
+    pub quiet: bool,
     pub data: Option<String>,
     pub(crate) directory: Option<String>,
     #[arg(short, long, value_delimiter = ',')]
     pub(crate) exclude: Vec<String>,
+    #[command(subcommand)]
+    pub command: Option<Commands>,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum Commands {
+    /// Save and reuse code blocks from AI answers
+    Snippet {
+        #[command(subcommand)]
+        action: SnippetAction,
+    },
+    /// Change stored configuration values
+    Config {
+        #[command(subcommand)]
+        action: ConfigAction,
+    },
+    /// Show a word-level diff between two answers of a session
+    DiffAnswers {
+        session: String,
+        /// answer number (1 = first answer) or message id
+        first: String,
+        /// answer number (1 = first answer) or message id
+        second: String,
+    },
+    /// Apply a diff from an answer to the working tree with `git apply`
+    ApplyDiff {
+        session: String,
+        /// answer number (1 = first answer) or message id
+        answer: String,
+        /// which diff block of the answer to apply (1 = first)
+        #[arg(long, default_value_t = 1)]
+        block: usize,
+        /// only check that the diff applies cleanly
+        #[arg(long)]
+        check: bool,
+        #[arg(long, default_value = ".")]
+        directory: String,
+    },
+    /// Let the model change the code until a command passes, feeding failures back
+    Iterate {
+        /// what to do, `@file` references are sent along
+        task: String,
+        /// verification command, run with `sh -c` after each change
+        #[arg(long)]
+        run: String,
+        #[arg(long, default_value_t = 5)]
+        max_iterations: u32,
+        /// stop once the answers used this many tokens in total
+        #[arg(long, default_value_t = 200_000)]
+        max_tokens: u32,
+        /// stop once this many minutes have passed
+        #[arg(long, default_value_t = 20)]
+        max_minutes: u64,
+        /// ask whether to go on after every N iterations
+        #[arg(long, value_name = "N")]
+        confirm_every: Option<u32>,
+        /// also write the summary to a file, JSON for .json and Markdown otherwise
+        #[arg(long, value_name = "FILE")]
+        report: Option<String>,
+        #[arg(long, default_value = ".")]
+        directory: String,
+    },
+    /// Experimental: let the model work on a task by reading and writing files and running commands,
+    /// each action is allowed, asked about or denied as set in [agent] in .termai.toml
+    Agent {
+        /// what to do
+        task: String,
+        /// session to log the steps in and to continue from, a new one by default
+        #[arg(long)]
+        session: Option<String>,
+        #[arg(long, default_value_t = 20)]
+        max_steps: u32,
+        /// stop once the answers used this many tokens in total
+        #[arg(long, default_value_t = 200_000)]
+        max_tokens: u32,
+        /// stop once this many minutes have passed
+        #[arg(long, default_value_t = 20)]
+        max_minutes: u64,
+        #[arg(long, default_value = ".")]
+        directory: String,
+    },
+    /// Make the same change to many files, several at a time, resuming where an interrupted run stopped
+    Bulk {
+        /// the change to make to every file
+        task: String,
+        /// glob of the files to change, e.g. "src/**/*.rs"
+        #[arg(long)]
+        files: String,
+        /// number of files changed at the same time
+        #[arg(long, default_value_t = 4)]
+        jobs: usize,
+        /// start over instead of skipping the files done by an earlier run
+        #[arg(long)]
+        restart: bool,
+        /// also write the summary to a file, JSON for .json and Markdown otherwise
+        #[arg(long, value_name = "FILE")]
+        report: Option<String>,
+    },
+    /// List or revert the file edits made by apply-diff and iterate
+    Changes {
+        #[command(subcommand)]
+        action: ChangesAction,
+    },
+    /// Restore the files from before the most recent AI edits
+    Undo {
+        /// number of recorded edits to undo, newest first
+        #[arg(long, default_value_t = 1)]
+        steps: usize,
+        /// restore even files that were changed after the edit
+        #[arg(long)]
+        force: bool,
+    },
+    /// Inspect stored sessions
+    Session {
+        #[command(subcommand)]
+        action: SessionAction,
+    },
+    /// Generate and read AI descriptions of commits stored in git notes
+    Notes {
+        #[command(subcommand)]
+        action: NotesAction,
+    },
+    /// Suggest the most valuable tests to add from an LCOV or Cobertura report
+    CoverageGaps {
+        report: String,
+        /// number of files with the largest gaps to send to the model
+        #[arg(long, default_value_t = 5)]
+        top: usize,
+        /// also write the suggested tests
+        #[arg(long)]
+        generate: bool,
+    },
+    /// Suggest the next semantic version from the commits since the last tag
+    Tag {
+        #[command(subcommand)]
+        action: TagAction,
+    },
+    /// Explain benchmark changes with the code that changed
+    Perf {
+        #[command(subcommand)]
+        action: PerfAction,
+    },
+    /// Ask for SQL against a SQLite file or postgres:// database, using its schema as context
+    Sql {
+        #[arg(long)]
+        db: String,
+        question: String,
+        /// run the generated query if it is read-only and preview the results
+        #[arg(long)]
+        execute: bool,
+        #[arg(long, default_value_t = 20)]
+        limit: usize,
+    },
+    /// Explain or diagnose a Kubernetes resource from kubectl output
+    K8s {
+        #[command(subcommand)]
+        action: K8sAction,
+    },
+    /// Summarize a log file as an incident report, `-` reads stdin
+    Logs {
+        source: String,
+        /// large logs are sampled down to about this many tokens
+        #[arg(long, default_value_t = 8000)]
+        max_tokens: u32,
+    },
+    /// Build a regex from a description, checked against sample lines ("+ " must match, "- " must not)
+    Regex {
+        description: String,
+        #[arg(long)]
+        test_file: String,
+    },
+    /// Build a jq filter from a description, checked against a JSON sample
+    Jq {
+        description: String,
+        #[arg(long)]
+        test_file: String,
+        /// file with the output the filter should produce
+        #[arg(long)]
+        expect: Option<String>,
+    },
+    /// Run a prompt on a schedule through cron
+    Schedule {
+        #[command(subcommand)]
+        action: ScheduleAction,
+    },
+    /// Bundle recent crash reports into one file to attach to a bug report
+    ReportBug,
+    /// Walk through the API key, shell completion and git hook setup
+    Setup,
+    /// Print the shell completion script
+    Completions { shell: Shell },
+    /// Install shell completion and the man page where your shell and `man` find them
+    InstallExtras {
+        /// shell to install completion for, detected from $SHELL by default
+        #[arg(long)]
+        shell: Option<Shell>,
+    },
+    /// Manage the cache of answers to identical one-shot questions, see `config set response_cache on`
+    Cache {
+        #[command(subcommand)]
+        action: CacheAction,
+    },
+    /// Manage large PDF attachments uploaded to OpenAI, see `config set file_uploads on`
+    Uploads {
+        #[command(subcommand)]
+        action: UploadsAction,
+    },
+    /// Turn the occasional feature hints on or off, or show them again with `reset`
+    Hints {
+        #[arg(value_parser = ["on", "off", "reset"])]
+        action: String,
+    },
+    /// Print copy-pasteable recipes for a command, e.g. `termai examples sql`
+    Examples {
+        /// command to show recipes for, `ask` for plain questions
+        command: Option<String>,
+    },
+    /// List recent prompts, or run one again with `history rerun <n>`
+    History {
+        #[command(subcommand)]
+        action: Option<HistoryAction>,
+        #[arg(long, default_value_t = 20)]
+        limit: u32,
+    },
+    /// Manage the directories termai may read context from and run commands in
+    Trust {
+        #[command(subcommand)]
+        action: TrustAction,
+    },
+    /// List the commands termai ran for you, including the ones the sandbox blocked
+    Audit {
+        #[arg(long, default_value_t = 20)]
+        limit: u32,
+    },
+    /// Suggest a better system prompt from the answers you rated down
+    Tune {
+        /// number of recent low-rated answers to learn from
+        #[arg(long, default_value_t = 10)]
+        limit: u32,
+    },
+    /// Show the models of the configured provider
+    Models {
+        #[command(subcommand)]
+        action: ModelsAction,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum HistoryAction {
+    /// Run a prompt from the history again with the same context flags
+    Rerun {
+        /// prompt number from `termai history` (1 = most recent)
+        number: usize,
+        /// change the prompt in $EDITOR before running it
+        #[arg(long)]
+        edit: bool,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum ModelsAction {
+    /// List the available chat models with context window, modality and price
+    List {
+        /// fetch the list again instead of using the cached one
+        #[arg(long)]
+        refresh: bool,
+        /// include embedding, audio and moderation models
+        #[arg(long)]
+        all: bool,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum NotesAction {
+    /// Describe a commit and store it under refs/notes/termai
+    Generate {
+        #[arg(default_value = "HEAD")]
+        sha: String,
+        /// include the full diffs of changed submodules
+        #[arg(long)]
+        include_submodules: bool,
+        /// include diffs of lock files and generated files
+        #[arg(long)]
+        include_generated: bool,
+    },
+    /// Print the stored description of a commit
+    Show { sha: String },
+    /// Install a post-commit hook that runs `notes generate` for each commit
+    InstallHook,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum ChangesAction {
+    /// Show the recorded edits, newest first
+    List {
+        #[arg(long, default_value_t = 20)]
+        limit: usize,
+    },
+    /// Undo a recorded edit with `git apply --reverse`
+    Revert {
+        /// change id or a prefix of it, see `changes list`
+        id: String,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum TagAction {
+    /// Classify the changes since the last tag as major, minor or patch
+    Suggest {
+        /// create the suggested version as an annotated tag
+        #[arg(long)]
+        create: bool,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum PerfAction {
+    /// Compare two hyperfine or criterion result files, or time two commands
+    Explain {
+        /// before and after result files (hyperfine --export-json or criterion estimates.json)
+        files: Vec<String>,
+        /// command to time before the change
+        #[arg(long)]
+        before: Option<String>,
+        /// command to time after the change
+        #[arg(long)]
+        after: Option<String>,
+        #[arg(long, default_value_t = 10)]
+        runs: u32,
+        /// revision the working tree is diffed against
+        #[arg(long, default_value = "HEAD")]
+        base: String,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum K8sAction {
+    /// Explain what a resource does, e.g. `k8s explain deployment/api`
+    Explain {
+        resource: String,
+        #[arg(short, long)]
+        namespace: Option<String>,
+        #[arg(long)]
+        context: Option<String>,
+        /// run the kubectl commands without asking
+        #[arg(short, long)]
+        yes: bool,
+    },
+    /// Find out why a resource is failing, including its logs
+    Diagnose {
+        resource: String,
+        #[arg(short, long)]
+        namespace: Option<String>,
+        #[arg(long)]
+        context: Option<String>,
+        /// run the kubectl commands without asking
+        #[arg(short, long)]
+        yes: bool,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum ScheduleAction {
+    /// Add a crontab entry that runs a prompt, e.g. `schedule add weekly-review --cron "0 9 * * MON" --prompt "..."`
+    Add {
+        name: String,
+        #[arg(long)]
+        cron: String,
+        #[arg(long)]
+        prompt: String,
+        #[arg(long, default_value = ".")]
+        directory: String,
+        /// session the answers are appended to, defaults to the schedule name
+        #[arg(long)]
+        session: Option<String>,
+        /// print the crontab entry instead of installing it
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// List scheduled runs
+    List,
+    /// Remove a scheduled run
+    Remove { name: String },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum ConfigAction {
+    /// Set a configuration value, e.g. `config set budget_monthly 20`
+    Set { key: String, value: String },
+    /// Choose where answers come from: OpenAI, or a local Ollama or llama.cpp server
+    SetProvider {
+        #[arg(value_parser = ["openai", "ollama"])]
+        provider: String,
+    },
+    /// Open ~/.config/termai/config.toml in $EDITOR and validate it on save
+    Edit,
+    /// Check ~/.config/termai/config.toml and the project's .termai.toml for mistakes
+    Validate {
+        #[arg(default_value = ".")]
+        directory: String,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum SessionAction {
+    /// List stored sessions, most recently used first
+    List {
+        #[arg(long, default_value = "recent", value_parser = ["recent", "name", "messages"])]
+        sort: String,
+        #[arg(long, default_value_t = 20)]
+        limit: u32,
+        #[arg(long, default_value_t = 1)]
+        page: u32,
+        #[arg(long, value_parser = ["temporary", "named"])]
+        filter: Option<String>,
+    },
+    /// Print the messages of a session
+    Show {
+        name: String,
+        #[arg(long)]
+        stats: bool,
+        #[arg(long)]
+        show_context: bool,
+    },
+    /// Copy a session into a new one to try another direction without touching the original
+    Branch {
+        name: String,
+        /// name of the new session
+        branch: String,
+        /// keep the messages up to this answer: its number in `session show` or an id prefix
+        #[arg(long)]
+        from_message: Option<String>,
+    },
+    /// Show when a session was active: messages per hour of each day, tokens, cost and gaps
+    Timeline {
+        name: String,
+        /// first day to show, YYYY-MM-DD
+        #[arg(long)]
+        since: Option<String>,
+        /// last day to show, YYYY-MM-DD
+        #[arg(long)]
+        until: Option<String>,
+    },
+    /// Export a session as a notebook, code blocks become runnable cells
+    Export {
+        name: String,
+        #[arg(long, default_value = "jupyter", value_parser = ["jupyter", "quarto"])]
+        format: String,
+        /// file to write to, prints to stdout when omitted
+        #[arg(short, long)]
+        output: Option<String>,
+    },
+    /// Summarize decisions, code changes and open questions, shown by `session show`
+    /// and used instead of the older messages when the session continues
+    Summarize {
+        name: String,
+        #[arg(long, default_value = "brief", value_parser = ["brief", "detailed"])]
+        depth: String,
+    },
+    /// Turn a session into a reference document of its final decisions and code,
+    /// with the discarded alternatives in an appendix
+    Distill {
+        name: String,
+        /// file to write to, prints to stdout when omitted
+        #[arg(short, long)]
+        output: Option<String>,
+    },
+    /// Show which context files changed on disk since they were sent
+    Stale { name: String },
+    /// Rate an answer and keep a private note on it, prints the note without a rating or text
+    Note {
+        name: String,
+        /// answer number (1 = first answer) or message id
+        answer: String,
+        text: Option<String>,
+        #[arg(long, conflicts_with = "down")]
+        up: bool,
+        #[arg(long)]
+        down: bool,
+    },
+    /// Show which models give the best-rated answers
+    Ratings,
+    /// Pin a session to an exact model version, the model of the last answer by default
+    Pin { name: String, model: Option<String> },
+    /// Let a session follow the configured model again
+    Unpin { name: String },
+    /// Move a pinned session to another model and note the switch in the transcript
+    Migrate { name: String, model: String },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum CacheAction {
+    /// Remove every cached answer
+    Purge,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum UploadsAction {
+    /// List the uploaded files with their size and the attachment they hold
+    List,
+    /// Delete an uploaded file from OpenAI, or all of them with --all
+    Delete {
+        #[arg(required_unless_present = "all")]
+        file_id: Option<String>,
+        #[arg(long, conflicts_with = "file_id")]
+        all: bool,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum TrustAction {
+    /// List the trusted and refused directories
+    List,
+    /// Trust a directory, the whole repository when it is inside one
+    Add {
+        #[arg(default_value = ".")]
+        directory: String,
+    },
+    /// Forget the decision for a directory so termai asks again
+    Remove {
+        #[arg(default_value = ".")]
+        directory: String,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum SnippetAction {
+    /// Save a code block from the last answer of a session
+    Save {
+        name: String,
+        #[arg(long)]
+        session: Option<String>,
+        #[arg(long, default_value_t = 1)]
+        block: usize,
+    },
+    /// List all saved snippets
+    List,
+    /// Search snippets by name, language or content
+    Search { query: String },
+    /// Copy a snippet to the clipboard
+    Copy { name: String },
 }
 
 impl Args {
@@ -38,7 +632,77 @@ impl Args {
         self.sessions_all
     }
 
+    pub fn sampling(&self) -> Sampling {
+        Sampling {
+            temperature: self.temperature,
+            top_p: self.top_p,
+            max_output_tokens: self.max_output_tokens,
+            stop: self.stop.clone(),
+        }
+    }
+
     pub fn is_session(&self) -> bool {
         self.session.is_some()
     }
+
+    pub fn is_write_command(&self) -> bool {
+        if self.is_chat_gpt_api_key() || self.redact_add.is_some() || self.redact_remove.is_some() {
+            return true;
+        }
+        matches!(
+            &self.command,
+            Some(Commands::Snippet {
+                action: SnippetAction::Save { .. }
+            }) | Some(Commands::Config {
+                action: ConfigAction::Set { .. }
+                    | ConfigAction::SetProvider { .. }
+                    | ConfigAction::Edit
+            }) | Some(Commands::Notes {
+                action: NotesAction::Generate { .. } | NotesAction::InstallHook
+            }) | Some(Commands::Changes {
+                action: ChangesAction::Revert { .. }
+            }) | Some(Commands::Undo { .. })
+                | Some(Commands::Tag {
+                    action: TagAction::Suggest { create: true }
+                })
+                | Some(Commands::Schedule {
+                    action: ScheduleAction::Add { .. } | ScheduleAction::Remove { .. }
+                })
+                | Some(Commands::Session {
+                    action: SessionAction::Export {
+                        output: Some(_),
+                        ..
+                    } | SessionAction::Distill {
+                        output: Some(_),
+                        ..
+                    }
+                })
+                | Some(Commands::Session {
+                    action: SessionAction::Summarize { .. }
+                        | SessionAction::Note { text: Some(_), .. }
+                        | SessionAction::Note { up: true, .. }
+                        | SessionAction::Note { down: true, .. }
+                        | SessionAction::Pin { .. }
+                        | SessionAction::Unpin { .. }
+                        | SessionAction::Migrate { .. }
+                        | SessionAction::Branch { .. }
+                })
+                | Some(Commands::ReportBug)
+                | Some(Commands::Setup)
+                | Some(Commands::InstallExtras { .. })
+                | Some(Commands::Hints { .. })
+                | Some(Commands::Cache { .. })
+                | Some(Commands::Uploads {
+                    action: UploadsAction::Delete { .. }
+                })
+                | Some(Commands::Tune { .. })
+                | Some(Commands::ApplyDiff { check: false, .. })
+                | Some(Commands::Iterate { .. })
+                | Some(Commands::Agent { .. })
+                | Some(Commands::Bulk { .. })
+                | Some(Commands::Trust {
+                    action: TrustAction::Add { .. } | TrustAction::Remove { .. }
+                })
+        )
+    }
 }
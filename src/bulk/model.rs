@@ -0,0 +1,69 @@
+use crate::snippet::model::code_block::extract_code_blocks;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+
+pub const NO_CHANGE: &str = "NO CHANGE";
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum Outcome {
+    Changed,
+    Unchanged,
+    Failed,
+}
+
+impl Outcome {
+    pub fn to_key(self) -> &'static str {
+        match self {
+            Self::Changed => "changed",
+            Self::Unchanged => "unchanged",
+            Self::Failed => "failed",
+        }
+    }
+
+    pub fn is_done(self) -> bool {
+        self != Self::Failed
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct FileResult {
+    pub outcome: Outcome,
+    pub detail: String,
+    #[serde(default)]
+    pub lines_changed: u32,
+}
+
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct BulkState {
+    pub task: String,
+    pub files: BTreeMap<String, FileResult>,
+}
+
+pub enum Rewrite {
+    Content(String),
+    Unchanged,
+}
+
+pub fn state_id(task: &str, files: &[String]) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(task.as_bytes());
+    for file in files {
+        hasher.update(b"\0");
+        hasher.update(file.as_bytes());
+    }
+    let hash = format!("{:x}", hasher.finalize());
+    hash[..12].to_string()
+}
+
+pub fn parse_rewrite(answer: &str) -> Option<Rewrite> {
+    if answer.trim().trim_matches('.') == NO_CHANGE {
+        return Some(Rewrite::Unchanged);
+    }
+    let mut blocks = extract_code_blocks(answer);
+    if blocks.len() != 1 {
+        return None;
+    }
+    Some(Rewrite::Content(blocks.remove(0).content))
+}
@@ -0,0 +1,238 @@
+use crate::bulk::model::{parse_rewrite, state_id, BulkState, FileResult, Outcome, Rewrite};
+use crate::changes::model::{changed_files, ChangeOrigin};
+use crate::changes::service::changes_service::{record_change, snapshot_files};
+use crate::config::repository::ConfigRepository;
+use crate::git::repository::repo_root;
+use crate::openai::service::prompt::prompt;
+use crate::output::markdown::truncate;
+use crate::output::progress::{is_quiet, set_quiet, status, Progress};
+use crate::output::report::{Report, ReportFile};
+use crate::usage::model::token_usage::{total_cost, total_tokens, TokenUsage};
+use crate::usage::repository::UsageRepository;
+use anyhow::{anyhow, Result};
+use chrono::Utc;
+use futures::stream::{self, StreamExt};
+use similar::TextDiff;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+const BULK_DIR: &str = ".termai/bulk";
+const SOURCE_TASK_WIDTH: usize = 40;
+
+const BULK_PROMPT: &str = "
+You apply one change to a single file, the same change is made to many files of a code base.
+Reply with the complete new content of the file in one fenced code block and keep everything the change does not touch exactly as it is.
+If the file needs no change, reply with NO CHANGE alone.";
+
+pub struct BulkInput<'a> {
+    pub task: &'a str,
+    pub files: &'a str,
+    pub jobs: usize,
+    pub restart: bool,
+    pub report: Option<&'a str>,
+}
+
+pub async fn bulk<R: ConfigRepository, UR: UsageRepository>(
+    repo: &R,
+    usage_repo: &UR,
+    input: &BulkInput<'_>,
+) -> Result<()> {
+    let root = repo_root(Path::new("."))?;
+    let files = matching_files(input.files)?;
+    if files.is_empty() {
+        return Err(anyhow!("no files match '{}'", input.files));
+    }
+    let state_path = root
+        .join(BULK_DIR)
+        .join(format!("{}.json", state_id(input.task, &files)));
+    let mut state = match input.restart {
+        true => BulkState::default(),
+        false => load_state(&state_path).unwrap_or_default(),
+    };
+    state.task = input.task.to_string();
+    let pending = files
+        .iter()
+        .filter(|file| {
+            !state
+                .files
+                .get(*file)
+                .is_some_and(|result| result.outcome.is_done())
+        })
+        .collect::<Vec<&String>>();
+    if pending.len() < files.len() {
+        status(&format!(
+            "resuming, {} of {} files were done in an earlier run",
+            files.len() - pending.len(),
+            files.len()
+        ));
+    }
+
+    let system_prompt = format!("{}\n\nThe change: {}", BULK_PROMPT, input.task);
+    let source = format!("bulk: {}", truncate(input.task, SOURCE_TASK_WIDTH));
+    let started_at = Utc::now().naive_utc();
+    let started = Instant::now();
+    let progress = Progress::steps(pending.len(), "bulk");
+    let quiet = is_quiet();
+    set_quiet(true);
+    let mut results = stream::iter(pending)
+        .map(|file| {
+            let system_prompt = &system_prompt;
+            let source = &source;
+            async move {
+                let result = rewrite_file(repo, usage_repo, system_prompt, source, file).await;
+                (file, result)
+            }
+        })
+        .buffer_unordered(input.jobs.max(1));
+    while let Some((file, result)) = results.next().await {
+        let result = result.unwrap_or_else(|err| FileResult {
+            outcome: Outcome::Failed,
+            detail: err.to_string(),
+            lines_changed: 0,
+        });
+        progress.message(file);
+        progress.advance();
+        state.files.insert(file.clone(), result);
+        if let Err(err) = save_state(&state_path, &state) {
+            status(&format!("could not save the progress: {:?}", err));
+        }
+    }
+    set_quiet(quiet);
+    progress.finish();
+
+    let usage = usage_repo
+        .fetch_usage_since(started_at)
+        .unwrap_or_default()
+        .iter()
+        .map(TokenUsage::from)
+        .collect::<Vec<TokenUsage>>();
+    let rows = files
+        .iter()
+        .filter_map(|file| {
+            let result = state.files.get(file)?;
+            Some(ReportFile {
+                path: file.clone(),
+                status: result.outcome.to_key().to_string(),
+                detail: result.detail.clone(),
+                lines_changed: result.lines_changed,
+            })
+        })
+        .collect::<Vec<ReportFile>>();
+    let failed = rows.iter().filter(|row| row.status == "failed").count();
+    let report = Report::new(
+        format!("bulk: {}", input.task),
+        rows,
+        total_tokens(&usage),
+        total_cost(&usage),
+        started.elapsed(),
+    );
+    println!();
+    report.print();
+    if let Some(path) = input.report {
+        report.write(path)?;
+    }
+    if failed > 0 {
+        println!("run the same command again to retry the failed files");
+        return Err(anyhow!("{} file(s) failed", failed));
+    }
+    let _ = fs::remove_file(&state_path);
+    Ok(())
+}
+
+async fn rewrite_file<R: ConfigRepository, UR: UsageRepository>(
+    repo: &R,
+    usage_repo: &UR,
+    system_prompt: &str,
+    source: &str,
+    path: &str,
+) -> Result<FileResult> {
+    let before =
+        fs::read_to_string(path).map_err(|err| anyhow!("could not read {}: {:?}", path, err))?;
+    let input = format!("{}\n```\n{}\n```", path, before);
+    let answer = prompt(
+        repo,
+        usage_repo,
+        "bulk",
+        Path::new("."),
+        system_prompt,
+        &input,
+    )
+    .await?;
+    let after = match parse_rewrite(&answer) {
+        Some(Rewrite::Content(after)) if after != before => after,
+        Some(_) => {
+            return Ok(FileResult {
+                outcome: Outcome::Unchanged,
+                detail: String::new(),
+                lines_changed: 0,
+            })
+        }
+        None => {
+            return Ok(FileResult {
+                outcome: Outcome::Failed,
+                detail: "the answer was not a single code block".to_string(),
+                lines_changed: 0,
+            })
+        }
+    };
+
+    let diff = TextDiff::from_lines(&before, &after)
+        .unified_diff()
+        .context_radius(3)
+        .header(&format!("a/{}", path), &format!("b/{}", path))
+        .to_string();
+    let directory = Path::new(".");
+    let snapshots = snapshot_files(directory, &diff)?;
+    fs::write(path, &after).map_err(|err| anyhow!("could not write {}: {:?}", path, err))?;
+    let origin = ChangeOrigin {
+        source: source.to_string(),
+        session: None,
+        message_id: None,
+    };
+    record_change(directory, &diff, origin, snapshots);
+    let (added, removed) = changed_files(&diff)
+        .iter()
+        .fold((0, 0), |(added, removed), file| {
+            (added + file.added, removed + file.removed)
+        });
+    Ok(FileResult {
+        outcome: Outcome::Changed,
+        detail: format!("+{} -{}", added, removed),
+        lines_changed: added + removed,
+    })
+}
+
+fn matching_files(pattern: &str) -> Result<Vec<String>> {
+    let paths =
+        glob::glob(pattern).map_err(|err| anyhow!("invalid pattern '{}': {}", pattern, err))?;
+    let mut files = paths
+        .flatten()
+        .filter(|path| path.is_file())
+        .filter(|path| {
+            !path
+                .components()
+                .any(|component| matches!(component.as_os_str().to_str(), Some(".git" | ".termai")))
+        })
+        .map(|path| path.to_string_lossy().to_string())
+        .collect::<Vec<String>>();
+    files.sort();
+    Ok(files)
+}
+
+fn load_state(path: &PathBuf) -> Option<BulkState> {
+    let content = fs::read_to_string(path).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+fn save_state(path: &PathBuf, state: &BulkState) -> Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+        let ignore = dir.parent().unwrap_or(dir).join(".gitignore");
+        if !ignore.exists() {
+            fs::write(&ignore, "*\n")?;
+        }
+    }
+    fs::write(path, serde_json::to_string_pretty(state)?)?;
+    Ok(())
+}
@@ -0,0 +1,27 @@
+use chrono::NaiveDateTime;
+
+pub struct CachedAnswerEntity {
+    pub key: String,
+    pub provider: String,
+    pub model: String,
+    pub answer: String,
+    pub created_at: NaiveDateTime,
+}
+
+impl CachedAnswerEntity {
+    pub fn new(
+        key: String,
+        provider: String,
+        model: String,
+        answer: String,
+        created_at: NaiveDateTime,
+    ) -> Self {
+        Self {
+            key,
+            provider,
+            model,
+            answer,
+            created_at,
+        }
+    }
+}
@@ -0,0 +1 @@
+pub(crate) mod cached_answer_entity;
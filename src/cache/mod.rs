@@ -0,0 +1,3 @@
+pub(crate) mod entity;
+pub(crate) mod repository;
+pub(crate) mod service;
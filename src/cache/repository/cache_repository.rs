@@ -0,0 +1,53 @@
+use super::CacheRepository;
+use crate::cache::entity::cached_answer_entity::CachedAnswerEntity;
+use crate::repository::db::SqliteRepository;
+use chrono::NaiveDateTime;
+use rusqlite::{params, OptionalExtension, Result};
+
+const DATE_TIME_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+
+impl CacheRepository for SqliteRepository {
+    type Error = rusqlite::Error;
+
+    fn fetch_answer(&self, key: &str) -> Result<Option<CachedAnswerEntity>, Self::Error> {
+        self.conn
+            .query_row(
+                "SELECT key, provider, model, answer, created_at FROM response_cache WHERE key = ?1",
+                params![key],
+                |row| {
+                    let created_at_str: String = row.get(4)?;
+                    let created_at =
+                        NaiveDateTime::parse_from_str(&created_at_str, DATE_TIME_FORMAT)
+                            .expect("Invalid DateTime format");
+                    Ok(CachedAnswerEntity::new(
+                        row.get(0)?,
+                        row.get(1)?,
+                        row.get(2)?,
+                        row.get(3)?,
+                        created_at,
+                    ))
+                },
+            )
+            .optional()
+    }
+
+    fn save_answer(&self, answer: &CachedAnswerEntity) -> Result<(), Self::Error> {
+        let created_at_str = answer.created_at.format(DATE_TIME_FORMAT).to_string();
+        self.conn.execute(
+            "INSERT OR REPLACE INTO response_cache (key, provider, model, answer, created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![
+                answer.key,
+                answer.provider,
+                answer.model,
+                answer.answer,
+                created_at_str
+            ],
+        )?;
+        Ok(())
+    }
+
+    fn purge_answers(&self) -> Result<usize, Self::Error> {
+        self.conn.execute("DELETE FROM response_cache", [])
+    }
+}
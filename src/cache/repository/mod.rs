@@ -0,0 +1,15 @@
+use super::entity::cached_answer_entity::CachedAnswerEntity;
+use std::fmt::Debug;
+
+pub(crate) mod cache_repository;
+
+pub trait CacheRepository
+where
+    Self::Error: Debug,
+{
+    type Error;
+
+    fn fetch_answer(&self, key: &str) -> Result<Option<CachedAnswerEntity>, Self::Error>;
+    fn save_answer(&self, answer: &CachedAnswerEntity) -> Result<(), Self::Error>;
+    fn purge_answers(&self) -> Result<usize, Self::Error>;
+}
@@ -0,0 +1,183 @@
+use crate::args::CacheAction;
+use crate::cache::entity::cached_answer_entity::CachedAnswerEntity;
+use crate::cache::repository::CacheRepository;
+use crate::config::model::keys::ConfigKeys;
+use crate::config::repository::ConfigRepository;
+use crate::config::service::config_service;
+use crate::openai::model::sampling::Sampling;
+use crate::output::progress::status;
+use crate::provider::model::{Endpoint, Provider};
+use crate::session::model::message::Message;
+use anyhow::{anyhow, Result};
+use chrono::{Duration, Utc};
+use sha2::{Digest, Sha256};
+
+const DEFAULT_CACHE_HOURS: i64 = 24;
+
+pub fn is_enabled<R: ConfigRepository>(repo: &R) -> bool {
+    config_service::fetch_by_key(repo, &ConfigKeys::ResponseCache.to_key())
+        .is_ok_and(|config| config.value == "on")
+}
+
+// Two endpoints can serve different models under the same name, so the
+// provider and base URL are part of the key, the API key is not.
+pub fn cache_key(
+    endpoint: &Endpoint,
+    model: &str,
+    sampling: &Sampling,
+    messages: &[Message],
+) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(endpoint.provider.to_key().as_bytes());
+    hasher.update(b"\0");
+    hasher.update(endpoint.base_url.trim_end_matches('/').as_bytes());
+    hasher.update(b"\0");
+    hasher.update(model.as_bytes());
+    hasher.update(b"\0");
+    hasher.update(format!("{:?}", sampling).as_bytes());
+    for message in messages {
+        hasher.update(b"\0");
+        hasher.update(message.role.to_string().as_bytes());
+        hasher.update(b"\0");
+        hasher.update(normalize(&message.content).as_bytes());
+        for attachment in &message.attachments {
+            hasher.update(b"\0");
+            hasher.update(attachment.path.as_bytes());
+            hasher.update(b"\0");
+            hasher.update(attachment.content.as_bytes());
+        }
+    }
+    format!("{:x}", hasher.finalize())
+}
+
+pub fn lookup<R: ConfigRepository, CR: CacheRepository>(
+    repo: &R,
+    cache_repo: &CR,
+    key: &str,
+) -> Option<CachedAnswerEntity> {
+    let hours = config_service::fetch_by_key(repo, &ConfigKeys::ResponseCacheHours.to_key())
+        .ok()
+        .and_then(|config| config.value.parse::<i64>().ok())
+        .unwrap_or(DEFAULT_CACHE_HOURS);
+    let expires = Utc::now().naive_utc() - Duration::hours(hours);
+    cache_repo
+        .fetch_answer(key)
+        .ok()
+        .flatten()
+        .filter(|answer| answer.created_at > expires)
+}
+
+pub fn store<CR: CacheRepository>(
+    cache_repo: &CR,
+    key: String,
+    provider: Provider,
+    model: &str,
+    answer: &str,
+) {
+    let entity = CachedAnswerEntity::new(
+        key,
+        provider.to_key().to_string(),
+        model.to_string(),
+        answer.to_string(),
+        Utc::now().naive_utc(),
+    );
+    if let Err(err) = cache_repo.save_answer(&entity) {
+        status(&format!("could not cache the answer: {:?}", err));
+    }
+}
+
+pub fn cache<CR: CacheRepository>(cache_repo: &CR, action: &CacheAction) -> Result<()> {
+    match action {
+        CacheAction::Purge => purge(cache_repo),
+    }
+}
+
+pub fn purge<CR: CacheRepository>(cache_repo: &CR) -> Result<()> {
+    let removed = cache_repo
+        .purge_answers()
+        .map_err(|err| anyhow!("could not purge the response cache: {:?}", err))?;
+    println!("removed {} cached answer(s)", removed);
+    Ok(())
+}
+
+fn normalize(content: &str) -> String {
+    content.split_whitespace().collect::<Vec<&str>>().join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::openai::model::role::Role;
+
+    fn endpoint(provider: Provider, base_url: &str) -> Endpoint {
+        Endpoint {
+            provider,
+            base_url: base_url.to_string(),
+            api_key: None,
+        }
+    }
+
+    fn question(content: &str) -> Vec<Message> {
+        vec![Message {
+            id: String::new(),
+            role: Role::User,
+            content: content.to_string(),
+            attachments: Vec::new(),
+            redaction_mapping: None,
+            model: None,
+        }]
+    }
+
+    fn key(endpoint: &Endpoint, model: &str, sampling: &Sampling, content: &str) -> String {
+        cache_key(endpoint, model, sampling, &question(content))
+    }
+
+    #[test]
+    fn same_question_to_the_same_endpoint_hits() {
+        let openai = endpoint(Provider::OpenAi, "https://api.openai.com/v1");
+        let sampling = Sampling::default();
+        assert_eq!(
+            key(&openai, "gpt-4o", &sampling, "what is  a\nmonad"),
+            key(&openai, "gpt-4o", &sampling, "what is a monad")
+        );
+        let with_slash = endpoint(Provider::OpenAi, "https://api.openai.com/v1/");
+        assert_eq!(
+            key(&openai, "gpt-4o", &sampling, "q"),
+            key(&with_slash, "gpt-4o", &sampling, "q")
+        );
+    }
+
+    #[test]
+    fn provider_and_base_url_are_part_of_the_key() {
+        let sampling = Sampling::default();
+        let openai = endpoint(Provider::OpenAi, "https://api.openai.com/v1");
+        let compatible = endpoint(Provider::OpenAi, "http://localhost:8000/v1");
+        let ollama = endpoint(Provider::Ollama, "http://localhost:11434");
+        let keys = [
+            key(&openai, "llama3", &sampling, "q"),
+            key(&compatible, "llama3", &sampling, "q"),
+            key(&ollama, "llama3", &sampling, "q"),
+        ];
+        assert_ne!(keys[0], keys[1]);
+        assert_ne!(keys[0], keys[2]);
+        assert_ne!(keys[1], keys[2]);
+    }
+
+    #[test]
+    fn model_and_sampling_are_part_of_the_key() {
+        let openai = endpoint(Provider::OpenAi, "https://api.openai.com/v1");
+        let sampling = Sampling::default();
+        let warm = Sampling {
+            temperature: Some(1.2),
+            ..Sampling::default()
+        };
+        let stopped = Sampling {
+            stop: vec!["END".to_string()],
+            ..Sampling::default()
+        };
+        let base = key(&openai, "gpt-4o", &sampling, "q");
+        assert_ne!(base, key(&openai, "gpt-4o-mini", &sampling, "q"));
+        assert_ne!(base, key(&openai, "gpt-4o", &warm, "q"));
+        assert_ne!(base, key(&openai, "gpt-4o", &stopped, "q"));
+    }
+}
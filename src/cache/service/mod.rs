@@ -0,0 +1 @@
+pub(crate) mod cache_service;
@@ -0,0 +1,182 @@
+use crate::patch::model::strip_level;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ChangeManifest {
+    pub id: String,
+    #[serde(default)]
+    pub sequence: u64,
+    pub created_at: String,
+    pub source: String,
+    pub session: Option<String>,
+    pub message_id: Option<String>,
+    pub directory: String,
+    pub files: Vec<ChangedFile>,
+    pub patch: String,
+    #[serde(default)]
+    pub snapshots: Vec<FileSnapshot>,
+    pub reverted_at: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct FileSnapshot {
+    pub path: String,
+    pub before: FileState,
+    pub after_hash: Option<String>,
+}
+
+// The file as it was before the edit, its bytes are base64 so any file can be
+// restored, not only UTF-8 text.
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+#[serde(tag = "state", rename_all = "snake_case")]
+pub enum FileState {
+    Missing,
+    Existed { content: String },
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ChangedFile {
+    pub path: String,
+    #[serde(default)]
+    pub renamed_from: Option<String>,
+    pub hunks: Vec<String>,
+    pub added: u32,
+    pub removed: u32,
+}
+
+impl ChangedFile {
+    fn new(path: String, renamed_from: Option<String>) -> Self {
+        Self {
+            path,
+            renamed_from,
+            hunks: Vec::new(),
+            added: 0,
+            removed: 0,
+        }
+    }
+
+    // Every file the edit touches, undoing a rename brings the old file back.
+    pub fn paths(&self) -> impl Iterator<Item = &str> {
+        self.renamed_from
+            .as_deref()
+            .into_iter()
+            .chain(std::iter::once(self.path.as_str()))
+    }
+}
+
+pub struct ChangeOrigin {
+    pub source: String,
+    pub session: Option<String>,
+    pub message_id: Option<String>,
+}
+
+// A rename names both files, git writes it as `rename from`/`rename to` lines
+// and leaves out the ---/+++ headers when the content did not change.
+pub fn changed_files(diff: &str) -> Vec<ChangedFile> {
+    let strip = strip_level(diff);
+    let strip_prefix = |path: &str| {
+        path.splitn(strip + 1, '/')
+            .last()
+            .unwrap_or(path)
+            .to_string()
+    };
+    let mut files: Vec<ChangedFile> = Vec::new();
+    let mut renamed_from = None;
+    let mut named_by_rename = false;
+    let mut lines = diff.lines().peekable();
+    while let Some(line) = lines.next() {
+        if line.starts_with("diff --git ") {
+            renamed_from = None;
+            named_by_rename = false;
+            continue;
+        }
+        if let Some(source) = line.strip_prefix("rename from ") {
+            renamed_from = Some(source.trim().to_string());
+            continue;
+        }
+        if let Some(target) = line.strip_prefix("rename to ") {
+            files.push(ChangedFile::new(
+                target.trim().to_string(),
+                renamed_from.take(),
+            ));
+            named_by_rename = true;
+            continue;
+        }
+        let next_is_target = lines.peek().is_some_and(|next| next.starts_with("+++ "));
+        if line.starts_with("--- ") && next_is_target {
+            let target = lines.next().unwrap_or_default();
+            if named_by_rename {
+                continue;
+            }
+            let source = line[4..].split('\t').next().unwrap_or_default().trim();
+            let target = target[4..].split('\t').next().unwrap_or_default().trim();
+            let file = match (source, target) {
+                (source, "/dev/null") => ChangedFile::new(strip_prefix(source), None),
+                ("/dev/null", target) => ChangedFile::new(strip_prefix(target), None),
+                (source, target) => {
+                    let (source, target) = (strip_prefix(source), strip_prefix(target));
+                    let renamed_from = (source != target).then_some(source);
+                    ChangedFile::new(target, renamed_from)
+                }
+            };
+            files.push(file);
+            continue;
+        }
+        let Some(file) = files.last_mut() else {
+            continue;
+        };
+        if line.starts_with("@@") {
+            file.hunks.push(line.to_string());
+        } else if line.starts_with('+') {
+            file.added += 1;
+        } else if line.starts_with('-') {
+            file.removed += 1;
+        }
+    }
+    files
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn paths(diff: &str) -> Vec<(String, Option<String>)> {
+        changed_files(diff)
+            .into_iter()
+            .map(|file| (file.path, file.renamed_from))
+            .collect()
+    }
+
+    #[test]
+    fn renames_name_both_files() {
+        let pure = "diff --git a/old.rs b/new.rs\nsimilarity index 100%\nrename from old.rs\nrename to new.rs\n";
+        assert_eq!(
+            paths(pure),
+            vec![("new.rs".to_string(), Some("old.rs".to_string()))]
+        );
+
+        let edited = "diff --git a/old.rs b/src/new.rs\nsimilarity index 90%\nrename from old.rs\nrename to src/new.rs\n--- a/old.rs\n+++ b/src/new.rs\n@@ -1 +1 @@\n-a\n+b\n";
+        let files = changed_files(edited);
+        assert_eq!(files.len(), 1);
+        assert_eq!(
+            files[0].paths().collect::<Vec<&str>>(),
+            vec!["old.rs", "src/new.rs"]
+        );
+        assert_eq!((files[0].added, files[0].removed), (1, 1));
+
+        let headers_only = "--- a/old.rs\n+++ b/new.rs\n@@ -1 +1 @@\n-a\n+b\n";
+        assert_eq!(
+            paths(headers_only),
+            vec![("new.rs".to_string(), Some("old.rs".to_string()))]
+        );
+    }
+
+    #[test]
+    fn created_and_deleted_files_have_one_path() {
+        let diff = "--- /dev/null\n+++ b/new\n@@ -0,0 +1 @@\n+b\n--- a/gone\n+++ /dev/null\n@@ -1 +0,0 @@\n-a\n";
+        assert_eq!(
+            paths(diff),
+            vec![("new".to_string(), None), ("gone".to_string(), None)]
+        );
+    }
+}
@@ -0,0 +1,456 @@
+use crate::args::ChangesAction;
+use crate::changes::model::{changed_files, ChangeManifest, ChangeOrigin, FileSnapshot, FileState};
+use crate::common::unique_id::generate_uuid_v4;
+use crate::git::repository::repo_root;
+use crate::output::progress::status;
+use crate::patch::service::patch_service::reverse_patch;
+use anyhow::{anyhow, Result};
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use chrono::Local;
+use colored::*;
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+
+const CHANGES_DIR: &str = ".termai/changes";
+const DATE_TIME_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+
+// Fails when a file exists but cannot be read, the edit must not go ahead
+// without a snapshot to undo it.
+pub fn snapshot_files(directory: &Path, diff: &str) -> Result<Vec<FileSnapshot>> {
+    changed_files(diff)
+        .iter()
+        .flat_map(|file| file.paths().map(str::to_string).collect::<Vec<String>>())
+        .map(|path| {
+            let before = match fs::read(directory.join(&path)) {
+                Ok(content) => FileState::Existed {
+                    content: STANDARD.encode(content),
+                },
+                Err(err) if err.kind() == ErrorKind::NotFound => FileState::Missing,
+                Err(err) => {
+                    return Err(anyhow!(
+                        "could not read {} to take a snapshot: {}",
+                        path,
+                        err
+                    ))
+                }
+            };
+            Ok(FileSnapshot {
+                path,
+                before,
+                after_hash: None,
+            })
+        })
+        .collect()
+}
+
+pub fn record_change(
+    directory: &Path,
+    diff: &str,
+    origin: ChangeOrigin,
+    snapshots: Vec<FileSnapshot>,
+) -> Option<String> {
+    match write_manifest(directory, diff, origin, snapshots) {
+        Ok(id) => Some(id),
+        Err(err) => {
+            status(&format!("could not record the change: {:?}", err));
+            None
+        }
+    }
+}
+
+fn write_manifest(
+    directory: &Path,
+    diff: &str,
+    origin: ChangeOrigin,
+    mut snapshots: Vec<FileSnapshot>,
+) -> Result<String> {
+    let dir = changes_dir(directory)?;
+    fs::create_dir_all(&dir)?;
+    let ignore = dir.parent().unwrap_or(&dir).join(".gitignore");
+    if !ignore.exists() {
+        fs::write(&ignore, "*\n")?;
+    }
+
+    for snapshot in &mut snapshots {
+        snapshot.after_hash = file_hash(&directory.join(&snapshot.path));
+    }
+    let sequence = manifests(&dir)?
+        .iter()
+        .map(|manifest| manifest.sequence)
+        .max()
+        .unwrap_or_default()
+        + 1;
+    let now = Local::now().naive_local();
+    let id = format!(
+        "{}-{}",
+        now.format("%Y%m%d-%H%M%S"),
+        &generate_uuid_v4().to_string()[..4]
+    );
+    let manifest = ChangeManifest {
+        id: id.clone(),
+        sequence,
+        created_at: now.format(DATE_TIME_FORMAT).to_string(),
+        source: origin.source,
+        session: origin.session,
+        message_id: origin.message_id,
+        directory: directory.canonicalize()?.to_string_lossy().to_string(),
+        files: changed_files(diff),
+        patch: diff.to_string(),
+        snapshots,
+        reverted_at: None,
+    };
+    fs::write(
+        dir.join(format!("{}.json", id)),
+        serde_json::to_string_pretty(&manifest)?,
+    )?;
+    Ok(id)
+}
+
+pub fn changes(action: &ChangesAction) -> Result<()> {
+    let dir = changes_dir(Path::new("."))?;
+    match action {
+        ChangesAction::List { limit } => list_changes(&dir, *limit),
+        ChangesAction::Revert { id } => revert_change(&dir, id),
+    }
+}
+
+fn list_changes(dir: &Path, limit: usize) -> Result<()> {
+    let manifests = manifests(dir)?;
+    if manifests.is_empty() {
+        println!("no changes recorded yet, apply-diff and iterate record their edits");
+        return Ok(());
+    }
+    for manifest in manifests.iter().take(limit) {
+        let mut origin = manifest.source.clone();
+        if let Some(session) = &manifest.session {
+            origin = format!("{}, session {}", origin, session);
+        }
+        if let Some(message_id) = &manifest.message_id {
+            origin = format!(
+                "{}, message {}",
+                origin,
+                &message_id[..message_id.len().min(8)]
+            );
+        }
+        let header = format!(
+            "{}  {}  {}",
+            manifest.id,
+            manifest
+                .created_at
+                .get(..16)
+                .unwrap_or(&manifest.created_at),
+            origin
+        );
+        match manifest.reverted_at {
+            Some(_) => println!("{} {}", header.dimmed(), "(reverted)".dimmed()),
+            None => println!("{}", header.bold()),
+        }
+        for file in &manifest.files {
+            let path = match &file.renamed_from {
+                Some(source) => format!("{} -> {}", source, file.path),
+                None => file.path.clone(),
+            };
+            println!(
+                "  {} {} {} {}",
+                path,
+                format!("+{}", file.added).green(),
+                format!("-{}", file.removed).red(),
+                format!("({} hunks)", file.hunks.len()).dimmed()
+            );
+        }
+    }
+    Ok(())
+}
+
+fn revert_change(dir: &Path, id: &str) -> Result<()> {
+    let mut manifest = manifests(dir)?
+        .into_iter()
+        .find(|manifest| manifest.id.starts_with(id))
+        .ok_or_else(|| anyhow!("no change '{}', see `termai changes list`", id))?;
+    if let Some(reverted_at) = &manifest.reverted_at {
+        return Err(anyhow!(
+            "change {} was already reverted at {}",
+            manifest.id,
+            reverted_at
+        ));
+    }
+    reverse_patch(&manifest.patch, false, Path::new(&manifest.directory))?;
+
+    mark_reverted(dir, &mut manifest)?;
+    println!("{} {}", "reverted".green(), manifest.id);
+    Ok(())
+}
+
+pub fn undo(steps: usize, force: bool) -> Result<()> {
+    let dir = changes_dir(Path::new("."))?;
+    let pending = manifests(&dir)?
+        .into_iter()
+        .filter(|manifest| manifest.reverted_at.is_none())
+        .take(steps)
+        .collect::<Vec<ChangeManifest>>();
+    if pending.is_empty() {
+        println!("nothing to undo");
+        return Ok(());
+    }
+
+    for mut manifest in pending {
+        if manifest.snapshots.is_empty() {
+            return Err(anyhow!(
+                "change {} has no snapshot of the files, try `termai changes revert {}`",
+                manifest.id,
+                manifest.id
+            ));
+        }
+        let directory = PathBuf::from(&manifest.directory);
+        let conflicts = manifest
+            .snapshots
+            .iter()
+            .filter(|snapshot| file_hash(&directory.join(&snapshot.path)) != snapshot.after_hash)
+            .map(|snapshot| snapshot.path.as_str())
+            .collect::<Vec<&str>>();
+        if !conflicts.is_empty() && !force {
+            return Err(anyhow!(
+                "{} changed after change {}, nothing of it was undone\n`termai undo --force` restores the snapshot anyway and drops those later edits",
+                conflicts.join(", "),
+                manifest.id
+            ));
+        }
+        for snapshot in &manifest.snapshots {
+            restore_snapshot(&directory, snapshot)?;
+        }
+        mark_reverted(&dir, &mut manifest)?;
+        println!("{} {} ({})", "undid".green(), manifest.id, manifest.source);
+    }
+    Ok(())
+}
+
+// Only a file the edit created is removed, anything else is written back as it was.
+fn restore_snapshot(directory: &Path, snapshot: &FileSnapshot) -> Result<()> {
+    let path = directory.join(&snapshot.path);
+    match &snapshot.before {
+        FileState::Existed { content } => {
+            let content = STANDARD
+                .decode(content)
+                .map_err(|err| anyhow!("the snapshot of {} is damaged: {}", snapshot.path, err))?;
+            fs::write(&path, content)?;
+        }
+        FileState::Missing if path.exists() => fs::remove_file(&path)?,
+        FileState::Missing => {}
+    }
+    Ok(())
+}
+
+fn mark_reverted(dir: &Path, manifest: &mut ChangeManifest) -> Result<()> {
+    manifest.reverted_at = Some(
+        Local::now()
+            .naive_local()
+            .format(DATE_TIME_FORMAT)
+            .to_string(),
+    );
+    fs::write(
+        dir.join(format!("{}.json", manifest.id)),
+        serde_json::to_string_pretty(&manifest)?,
+    )?;
+    Ok(())
+}
+
+fn file_hash(path: &Path) -> Option<String> {
+    let content = fs::read(path).ok()?;
+    Some(format!("{:x}", Sha256::digest(&content)))
+}
+
+fn manifests(dir: &Path) -> Result<Vec<ChangeManifest>> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Ok(Vec::new());
+    };
+    let mut manifests = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.extension()
+                .is_some_and(|extension| extension == "json")
+        })
+        .filter_map(|path| {
+            let content = fs::read_to_string(&path).ok()?;
+            serde_json::from_str::<ChangeManifest>(&content).ok()
+        })
+        .collect::<Vec<ChangeManifest>>();
+    sort_newest_first(&mut manifests);
+    Ok(manifests)
+}
+
+// Several changes can be written in the same second, so the sequence orders
+// them and the id only breaks ties.
+fn sort_newest_first(manifests: &mut [ChangeManifest]) {
+    manifests.sort_by(|a, b| b.sequence.cmp(&a.sequence).then_with(|| b.id.cmp(&a.id)));
+}
+
+fn changes_dir(directory: &Path) -> Result<PathBuf> {
+    Ok(repo_root(directory)?.join(CHANGES_DIR))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn manifest(id: &str, sequence: u64) -> ChangeManifest {
+        ChangeManifest {
+            id: id.to_string(),
+            sequence,
+            created_at: String::new(),
+            source: "test".to_string(),
+            session: None,
+            message_id: None,
+            directory: String::new(),
+            files: Vec::new(),
+            patch: String::new(),
+            snapshots: Vec::new(),
+            reverted_at: None,
+        }
+    }
+
+    fn ids(manifests: &[ChangeManifest]) -> Vec<&str> {
+        manifests
+            .iter()
+            .map(|manifest| manifest.id.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn sequence_orders_changes_from_the_same_second() {
+        let mut manifests = vec![
+            manifest("20261016-120000-ffff", 1),
+            manifest("20261016-120000-0000", 3),
+            manifest("20261016-120000-aaaa", 2),
+        ];
+        sort_newest_first(&mut manifests);
+        assert_eq!(
+            ids(&manifests),
+            vec![
+                "20261016-120000-0000",
+                "20261016-120000-aaaa",
+                "20261016-120000-ffff"
+            ]
+        );
+    }
+
+    #[test]
+    fn manifests_without_a_sequence_come_before_numbered_ones() {
+        let mut manifests = vec![
+            manifest("20261016-120000-aaaa", 1),
+            manifest("20261016-110000-aaaa", 0),
+            manifest("20261016-115959-aaaa", 0),
+        ];
+        sort_newest_first(&mut manifests);
+        assert_eq!(
+            ids(&manifests),
+            vec![
+                "20261016-120000-aaaa",
+                "20261016-115959-aaaa",
+                "20261016-110000-aaaa"
+            ]
+        );
+    }
+
+    #[test]
+    fn snapshot_keeps_bytes_and_missing_files_apart() {
+        let directory = TempDir::new().unwrap();
+        fs::write(directory.path().join("binary"), [0xff, 0xfe, 0x00, 0x80]).unwrap();
+        let diff = "--- a/binary\n+++ b/binary\n@@ -1 +1 @@\n-a\n+b\n--- /dev/null\n+++ b/new\n@@ -0,0 +1 @@\n+b\n";
+
+        let snapshots = snapshot_files(directory.path(), diff).unwrap();
+        assert_eq!(snapshots.len(), 2);
+        assert_eq!(
+            snapshots[0].before,
+            FileState::Existed {
+                content: STANDARD.encode([0xff, 0xfe, 0x00, 0x80])
+            }
+        );
+        assert_eq!(snapshots[1].before, FileState::Missing);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn snapshot_fails_for_a_file_that_cannot_be_read() {
+        let directory = TempDir::new().unwrap();
+        fs::create_dir(directory.path().join("dir")).unwrap();
+        let diff = "--- a/dir\n+++ b/dir\n@@ -1 +1 @@\n-a\n+b\n";
+        assert!(snapshot_files(directory.path(), diff).is_err());
+    }
+
+    #[test]
+    fn undoing_a_rename_brings_the_old_file_back() {
+        let directory = TempDir::new().unwrap();
+        let old = directory.path().join("old.rs");
+        fs::write(&old, "fn main() {}\n").unwrap();
+        let diff = "diff --git a/old.rs b/new.rs\nsimilarity index 100%\nrename from old.rs\nrename to new.rs\n";
+        let snapshots = snapshot_files(directory.path(), diff).unwrap();
+        assert_eq!(snapshots.len(), 2);
+        fs::rename(&old, directory.path().join("new.rs")).unwrap();
+
+        for snapshot in &snapshots {
+            restore_snapshot(directory.path(), snapshot).unwrap();
+        }
+        assert_eq!(fs::read_to_string(&old).unwrap(), "fn main() {}\n");
+        assert!(!directory.path().join("new.rs").exists());
+    }
+
+    #[test]
+    fn restore_writes_back_files_that_are_not_utf8() {
+        let directory = TempDir::new().unwrap();
+        let path = directory.path().join("binary");
+        fs::write(&path, [0xff, 0xfe, 0x00, 0x80]).unwrap();
+        let diff = "--- a/binary\n+++ b/binary\n@@ -1 +1 @@\n-a\n+b\n";
+        let snapshots = snapshot_files(directory.path(), diff).unwrap();
+        fs::write(&path, "edited").unwrap();
+
+        restore_snapshot(directory.path(), &snapshots[0]).unwrap();
+        assert_eq!(fs::read(&path).unwrap(), vec![0xff, 0xfe, 0x00, 0x80]);
+    }
+
+    #[test]
+    fn restore_removes_only_files_the_edit_created() {
+        let directory = TempDir::new().unwrap();
+        let diff = "--- /dev/null\n+++ b/new\n@@ -0,0 +1 @@\n+b\n";
+        let snapshots = snapshot_files(directory.path(), diff).unwrap();
+        let path = directory.path().join("new");
+        fs::write(&path, "b").unwrap();
+
+        restore_snapshot(directory.path(), &snapshots[0]).unwrap();
+        assert!(!path.exists());
+        restore_snapshot(directory.path(), &snapshots[0]).unwrap();
+    }
+
+    #[test]
+    fn each_written_change_gets_the_next_sequence() {
+        let directory = TempDir::new().unwrap();
+        std::process::Command::new("git")
+            .args(["init", "-q"])
+            .current_dir(directory.path())
+            .status()
+            .unwrap();
+        let diff = "--- a/x\n+++ b/x\n@@ -1 +1 @@\n-a\n+b\n";
+        let origin = || ChangeOrigin {
+            source: "test".to_string(),
+            session: None,
+            message_id: None,
+        };
+        let first = write_manifest(directory.path(), diff, origin(), Vec::new()).unwrap();
+        let second = write_manifest(directory.path(), diff, origin(), Vec::new()).unwrap();
+        let third = write_manifest(directory.path(), diff, origin(), Vec::new()).unwrap();
+
+        let manifests = manifests(&changes_dir(directory.path()).unwrap()).unwrap();
+        assert_eq!(ids(&manifests), vec![&third, &second, &first]);
+        assert_eq!(
+            manifests
+                .iter()
+                .map(|manifest| manifest.sequence)
+                .collect::<Vec<u64>>(),
+            vec![3, 2, 1]
+        );
+    }
+}
@@ -0,0 +1 @@
+pub(crate) mod changes_service;
@@ -0,0 +1,21 @@
+use std::fmt;
+
+// The exit code of a process stopped by Ctrl+C.
+pub const CANCELLED_EXIT_CODE: i32 = 130;
+
+// Returned when the user pressed Ctrl+C, so the callers unwind and close the
+// database before the process exits.
+#[derive(Debug)]
+pub struct Cancelled;
+
+impl fmt::Display for Cancelled {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "cancelled")
+    }
+}
+
+impl std::error::Error for Cancelled {}
+
+pub fn is_cancelled(err: &anyhow::Error) -> bool {
+    err.downcast_ref::<Cancelled>().is_some()
+}
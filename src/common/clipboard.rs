@@ -0,0 +1,32 @@
+use anyhow::{anyhow, Result};
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+const CLIPBOARD_COMMANDS: [(&str, &[&str]); 5] = [
+    ("pbcopy", &[]),
+    ("wl-copy", &[]),
+    ("xclip", &["-selection", "clipboard"]),
+    ("xsel", &["--clipboard", "--input"]),
+    ("clip", &[]),
+];
+
+pub fn copy_to_clipboard(content: &str) -> Result<()> {
+    for (program, args) in CLIPBOARD_COMMANDS {
+        let child = Command::new(program)
+            .args(args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn();
+
+        if let Ok(mut child) = child {
+            if let Some(mut stdin) = child.stdin.take() {
+                stdin.write_all(content.as_bytes())?;
+            }
+            if child.wait()?.success() {
+                return Ok(());
+            }
+        }
+    }
+    Err(anyhow!("no clipboard utility available"))
+}
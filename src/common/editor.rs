@@ -0,0 +1,37 @@
+use anyhow::{anyhow, Result};
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+pub fn editor() -> String {
+    std::env::var("VISUAL")
+        .or_else(|_| std::env::var("EDITOR"))
+        .unwrap_or_else(|_| "vi".to_string())
+}
+
+pub fn open_in_editor(path: &Path) -> Result<()> {
+    let editor = editor();
+    let status = Command::new("sh")
+        .arg("-c")
+        .arg(format!("{} \"$1\"", editor))
+        .arg("sh")
+        .arg(path)
+        .status()
+        .map_err(|err| anyhow!("could not run '{}': {:?}", editor, err))?;
+    if !status.success() {
+        return Err(anyhow!("'{}' exited with {}", editor, status));
+    }
+    Ok(())
+}
+
+pub fn edit_text(text: &str, file_name: &str) -> Result<String> {
+    let path = std::env::temp_dir().join(file_name);
+    fs::write(&path, text)
+        .map_err(|err| anyhow!("could not write {}: {:?}", path.display(), err))?;
+    let edited = open_in_editor(&path).and_then(|_| {
+        fs::read_to_string(&path)
+            .map_err(|err| anyhow!("could not read {}: {:?}", path.display(), err))
+    });
+    let _ = fs::remove_file(&path);
+    edited
+}
@@ -1 +1,5 @@
+pub(crate) mod cancel;
+pub(crate) mod clipboard;
+pub(crate) mod editor;
+pub(crate) mod terminal;
 pub(crate) mod unique_id;
@@ -0,0 +1,32 @@
+use anyhow::{anyhow, Result};
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Write};
+
+// Questions are read from the terminal itself, so they work while stdin is piped.
+#[cfg(unix)]
+const TERMINAL: &str = "/dev/tty";
+#[cfg(windows)]
+const TERMINAL: &str = "CONIN$";
+
+pub fn is_interactive() -> bool {
+    File::open(TERMINAL).is_ok()
+}
+
+pub fn answer(question: &str) -> Result<String> {
+    let terminal = File::open(TERMINAL)
+        .map_err(|_| anyhow!("not running in a terminal, cannot ask: {}", question))?;
+    eprint!("{} ", question);
+    io::stderr().flush()?;
+    let mut answer = String::new();
+    BufReader::new(terminal).read_line(&mut answer)?;
+    Ok(answer.trim().to_string())
+}
+
+pub fn ask(question: &str, default: bool) -> Result<bool> {
+    let choices = if default { "[Y/n]" } else { "[y/N]" };
+    let answer = answer(&format!("{} {}", question, choices))?;
+    Ok(match answer.to_lowercase().as_str() {
+        "" => default,
+        answer => matches!(answer, "y" | "yes"),
+    })
+}
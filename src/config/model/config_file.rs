@@ -0,0 +1,145 @@
+use crate::config::model::diagnostic::{
+    line_of_key, line_of_offset, suggestion, Diagnostic, Severity,
+};
+use crate::config::model::keys::ConfigKeys;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+pub const CONFIG_FILE: &str = "config.toml";
+
+pub const CONFIG_FILE_TEMPLATE: &str =
+    "# termai settings, merged with the values stored by `termai config set`.
+# Values in this file take precedence. Secrets such as the API key stay in the database.
+#
+# highlight_theme = \"base16-ocean.dark\"
+# diff_style = \"unified\"        # or \"side-by-side\"
+# budget_monthly = 20
+# budget_session = 2
+# budget_mode = \"warn\"          # or \"block\"
+# project_context = true
+# hints = \"on\"                  # or \"off\"
+# model = \"o3-mini\"             # see `termai models list`
+# inline_images = \"on\"          # or \"off\", kitty and iTerm2 only
+# injection_guard = \"warn\"      # or \"block\" or \"off\"
+# provider = \"openai\"           # or \"ollama\" for a local server
+# ollama_base_url = \"http://localhost:11434/v1\"
+# ollama_model = \"llama3.2\"
+# response_cache = \"off\"        # or \"on\" to reuse answers to identical one-shot questions
+# response_cache_hours = 24
+# answer_language = \"auto\"      # answer in the question's language, \"off\" or a language name
+# file_uploads = \"off\"         # or \"on\" to upload large PDF attachments to OpenAI once per content
+# file_upload_min_kb = 100
+# paste_limit_tokens = 20000     # ask before sending larger piped input, 0 turns it off
+";
+
+#[derive(Debug, Default)]
+pub struct ConfigFile {
+    pub values: BTreeMap<String, String>,
+}
+
+impl ConfigFile {
+    pub fn get(&self, key: &str) -> Option<&String> {
+        self.values.get(key)
+    }
+}
+
+pub fn config_file_path() -> PathBuf {
+    dirs::home_dir()
+        .expect("Failed to get home directory")
+        .join(".config/termai")
+        .join(CONFIG_FILE)
+}
+
+pub fn parse_config_file(content: &str) -> Result<ConfigFile, String> {
+    let (config, diagnostics) = check_config_file(Path::new(CONFIG_FILE), content);
+    match diagnostics
+        .into_iter()
+        .find(|diagnostic| diagnostic.severity == Severity::Error)
+    {
+        Some(error) => Err(error.message),
+        None => Ok(config),
+    }
+}
+
+pub fn check_config_file(path: &Path, content: &str) -> (ConfigFile, Vec<Diagnostic>) {
+    let mut diagnostics = Vec::new();
+    let mut values = BTreeMap::new();
+    let table: toml::Table = match toml::from_str(content) {
+        Ok(table) => table,
+        Err(err) => {
+            let line = err.span().map(|span| line_of_offset(content, span.start));
+            diagnostics.push(Diagnostic::error(path, line, err.message().to_string()));
+            return (ConfigFile { values }, diagnostics);
+        }
+    };
+
+    for (key, value) in table {
+        let line = line_of_key(content, &key);
+        let Some(config_key) = ConfigKeys::from_key(&key) else {
+            let known = ConfigKeys::all()
+                .iter()
+                .filter(|key| !key.is_secret())
+                .map(|key| key.to_key())
+                .collect::<Vec<String>>();
+            let known = known.iter().map(String::as_str).collect::<Vec<&str>>();
+            diagnostics.push(Diagnostic::error(
+                path,
+                line,
+                format!("unknown config key '{}'{}", key, suggestion(&key, &known)),
+            ));
+            continue;
+        };
+        if config_key.is_secret() {
+            diagnostics.push(Diagnostic::error(
+                path,
+                line,
+                format!(
+                    "'{}' can't be stored in {}, use `termai config set` instead",
+                    key, CONFIG_FILE
+                ),
+            ));
+            continue;
+        }
+        let value = match value {
+            toml::Value::String(text) => text,
+            toml::Value::Integer(_) | toml::Value::Float(_) | toml::Value::Boolean(_) => {
+                value.to_string()
+            }
+            _ => {
+                diagnostics.push(Diagnostic::error(
+                    path,
+                    line,
+                    format!("'{}' must be a string, number or boolean", key),
+                ));
+                continue;
+            }
+        };
+        if let Err(err) = config_key.validate(&value) {
+            diagnostics.push(Diagnostic::error(path, line, format!("{}: {}", key, err)));
+            continue;
+        }
+        values.insert(key, value);
+    }
+
+    let amount = |key: ConfigKeys| {
+        values
+            .get(&key.to_key())
+            .and_then(|value| value.parse::<f64>().ok())
+    };
+    if let (Some(session), Some(monthly)) = (
+        amount(ConfigKeys::BudgetSession),
+        amount(ConfigKeys::BudgetMonthly),
+    ) {
+        if session > monthly {
+            diagnostics.push(Diagnostic::warning(
+                path,
+                line_of_key(content, "budget_session"),
+                format!(
+                    "budget_session ({}) is higher than budget_monthly ({}), the monthly budget is reached first",
+                    session, monthly
+                ),
+            ));
+        }
+    }
+    (ConfigFile { values }, diagnostics)
+}
@@ -0,0 +1,106 @@
+use colored::*;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+#[derive(Debug)]
+pub struct Diagnostic {
+    pub file: PathBuf,
+    pub line: Option<usize>,
+    pub severity: Severity,
+    pub message: String,
+}
+
+impl Diagnostic {
+    pub fn error(file: &Path, line: Option<usize>, message: String) -> Self {
+        Self {
+            file: file.to_path_buf(),
+            line,
+            severity: Severity::Error,
+            message,
+        }
+    }
+
+    pub fn warning(file: &Path, line: Option<usize>, message: String) -> Self {
+        Self {
+            file: file.to_path_buf(),
+            line,
+            severity: Severity::Warning,
+            message,
+        }
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let location = match self.line {
+            Some(line) => format!("{}:{}", self.file.display(), line),
+            None => self.file.display().to_string(),
+        };
+        let severity = match self.severity {
+            Severity::Error => "error".red().bold(),
+            Severity::Warning => "warning".yellow().bold(),
+        };
+        write!(f, "{}: {}: {}", location, severity, self.message)
+    }
+}
+
+pub fn line_of_offset(content: &str, offset: usize) -> usize {
+    content[..offset.min(content.len())].matches('\n').count() + 1
+}
+
+pub fn line_of_key(content: &str, path: &str) -> Option<usize> {
+    let mut table = String::new();
+    for (index, line) in content.lines().enumerate() {
+        let trimmed = line.trim();
+        let full = if let Some(header) = trimmed.strip_prefix('[') {
+            table = header.trim_end_matches(']').trim().to_string();
+            table.clone()
+        } else if let Some((name, _)) = trimmed.split_once('=') {
+            let name = name.trim().trim_matches('"');
+            match table.is_empty() {
+                true => name.to_string(),
+                false => format!("{}.{}", table, name),
+            }
+        } else {
+            continue;
+        };
+        if full == path || full.starts_with(&format!("{}.", path)) {
+            return Some(index + 1);
+        }
+    }
+    None
+}
+
+pub fn suggestion(unknown: &str, known: &[&str]) -> String {
+    known
+        .iter()
+        .map(|candidate| (edit_distance(unknown, candidate), candidate))
+        .filter(|(distance, _)| *distance <= 2)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| format!(", did you mean '{}'?", candidate))
+        .unwrap_or_default()
+}
+
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let cost = usize::from(ca != *cb);
+            current.push(
+                (previous[j] + cost)
+                    .min(previous[j + 1] + 1)
+                    .min(current[j] + 1),
+            );
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
@@ -1,13 +1,195 @@
+use crate::output::highlighting::THEMES;
+
 pub enum ConfigKeys {
     ChatGptApiKey,
     Redacted,
+    BudgetMonthly,
+    BudgetSession,
+    BudgetMode,
+    ProjectContext,
+    HighlightTheme,
+    DiffStyle,
+    Hints,
+    Model,
+    SystemPrompt,
+    InlineImages,
+    InjectionGuard,
+    Provider,
+    OllamaBaseUrl,
+    OllamaModel,
+    ResponseCache,
+    ResponseCacheHours,
+    AnswerLanguage,
+    FileUploads,
+    FileUploadMinKb,
+    PasteLimitTokens,
 }
 
 impl ConfigKeys {
+    pub fn all() -> [Self; 22] {
+        [
+            Self::ChatGptApiKey,
+            Self::Redacted,
+            Self::BudgetMonthly,
+            Self::BudgetSession,
+            Self::BudgetMode,
+            Self::ProjectContext,
+            Self::HighlightTheme,
+            Self::DiffStyle,
+            Self::Hints,
+            Self::Model,
+            Self::SystemPrompt,
+            Self::InlineImages,
+            Self::InjectionGuard,
+            Self::Provider,
+            Self::OllamaBaseUrl,
+            Self::OllamaModel,
+            Self::ResponseCache,
+            Self::ResponseCacheHours,
+            Self::AnswerLanguage,
+            Self::FileUploads,
+            Self::FileUploadMinKb,
+            Self::PasteLimitTokens,
+        ]
+    }
+
     pub fn to_key(&self) -> String {
         match self {
             Self::ChatGptApiKey => "chat_gpt_api_key".to_owned(),
             Self::Redacted => "redacted".to_owned(),
+            Self::BudgetMonthly => "budget_monthly".to_owned(),
+            Self::BudgetSession => "budget_session".to_owned(),
+            Self::BudgetMode => "budget_mode".to_owned(),
+            Self::ProjectContext => "project_context".to_owned(),
+            Self::HighlightTheme => "highlight_theme".to_owned(),
+            Self::DiffStyle => "diff_style".to_owned(),
+            Self::Hints => "hints".to_owned(),
+            Self::Model => "model".to_owned(),
+            Self::SystemPrompt => "system_prompt".to_owned(),
+            Self::InlineImages => "inline_images".to_owned(),
+            Self::InjectionGuard => "injection_guard".to_owned(),
+            Self::Provider => "provider".to_owned(),
+            Self::OllamaBaseUrl => "ollama_base_url".to_owned(),
+            Self::OllamaModel => "ollama_model".to_owned(),
+            Self::ResponseCache => "response_cache".to_owned(),
+            Self::ResponseCacheHours => "response_cache_hours".to_owned(),
+            Self::AnswerLanguage => "answer_language".to_owned(),
+            Self::FileUploads => "file_uploads".to_owned(),
+            Self::FileUploadMinKb => "file_upload_min_kb".to_owned(),
+            Self::PasteLimitTokens => "paste_limit_tokens".to_owned(),
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<Self> {
+        match key {
+            "chat_gpt_api_key" => Some(Self::ChatGptApiKey),
+            "redacted" => Some(Self::Redacted),
+            "budget_monthly" => Some(Self::BudgetMonthly),
+            "budget_session" => Some(Self::BudgetSession),
+            "budget_mode" => Some(Self::BudgetMode),
+            "project_context" => Some(Self::ProjectContext),
+            "highlight_theme" => Some(Self::HighlightTheme),
+            "diff_style" => Some(Self::DiffStyle),
+            "hints" => Some(Self::Hints),
+            "model" => Some(Self::Model),
+            "system_prompt" => Some(Self::SystemPrompt),
+            "inline_images" => Some(Self::InlineImages),
+            "injection_guard" => Some(Self::InjectionGuard),
+            "provider" => Some(Self::Provider),
+            "ollama_base_url" => Some(Self::OllamaBaseUrl),
+            "ollama_model" => Some(Self::OllamaModel),
+            "response_cache" => Some(Self::ResponseCache),
+            "response_cache_hours" => Some(Self::ResponseCacheHours),
+            "answer_language" => Some(Self::AnswerLanguage),
+            "file_uploads" => Some(Self::FileUploads),
+            "file_upload_min_kb" => Some(Self::FileUploadMinKb),
+            "paste_limit_tokens" => Some(Self::PasteLimitTokens),
+            _ => None,
+        }
+    }
+
+    pub fn is_secret(&self) -> bool {
+        matches!(self, Self::ChatGptApiKey | Self::Redacted)
+    }
+
+    pub fn validate(&self, value: &str) -> Result<(), String> {
+        match self {
+            Self::BudgetMonthly | Self::BudgetSession => match value.parse::<f64>() {
+                Ok(amount) if amount >= 0.0 => Ok(()),
+                _ => Err(format!("'{}' is not a valid amount in USD", value)),
+            },
+            Self::BudgetMode => match value {
+                "warn" | "block" => Ok(()),
+                _ => Err(format!("'{}' must be either 'warn' or 'block'", value)),
+            },
+            Self::ProjectContext => match value {
+                "true" | "false" => Ok(()),
+                _ => Err(format!("'{}' must be either 'true' or 'false'", value)),
+            },
+            Self::HighlightTheme => match THEMES.contains(&value) {
+                true => Ok(()),
+                false => Err(format!("'{}' must be one of: {}", value, THEMES.join(", "))),
+            },
+            Self::Hints | Self::InlineImages | Self::ResponseCache | Self::FileUploads => {
+                match value {
+                    "on" | "off" => Ok(()),
+                    _ => Err(format!("'{}' must be either 'on' or 'off'", value)),
+                }
+            }
+            Self::InjectionGuard => match value {
+                "warn" | "block" | "off" => Ok(()),
+                _ => Err(format!("'{}' must be one of: warn, block, off", value)),
+            },
+            Self::DiffStyle => match value {
+                "unified" | "side-by-side" => Ok(()),
+                _ => Err(format!(
+                    "'{}' must be either 'unified' or 'side-by-side'",
+                    value
+                )),
+            },
+            Self::ResponseCacheHours => match value.parse::<u32>() {
+                Ok(hours) if hours > 0 => Ok(()),
+                _ => Err(format!(
+                    "'{}' must be a whole number of hours above 0",
+                    value
+                )),
+            },
+            Self::FileUploadMinKb => match value.parse::<u32>() {
+                Ok(kb) if kb > 0 => Ok(()),
+                _ => Err(format!(
+                    "'{}' must be a whole number of kilobytes above 0",
+                    value
+                )),
+            },
+            Self::PasteLimitTokens => match value.parse::<u32>() {
+                Ok(_) => Ok(()),
+                _ => Err(format!(
+                    "'{}' must be a whole number of tokens, 0 turns the check off",
+                    value
+                )),
+            },
+            Self::Provider => match value {
+                "openai" | "ollama" => Ok(()),
+                _ => Err(format!("'{}' must be either 'openai' or 'ollama'", value)),
+            },
+            Self::OllamaBaseUrl => {
+                match value.starts_with("http://") || value.starts_with("https://") {
+                    true => Ok(()),
+                    false => Err(format!(
+                        "'{}' must be a URL such as http://localhost:11434/v1",
+                        value
+                    )),
+                }
+            }
+            Self::AnswerLanguage => match value.trim().is_empty() {
+                true => Err("the language must not be empty, use 'auto' or 'off'".to_string()),
+                false => Ok(()),
+            },
+            Self::Model | Self::OllamaModel => match value.trim().is_empty() {
+                true => Err("the model name must not be empty".to_string()),
+                false => Ok(()),
+            },
+            _ => Ok(()),
         }
     }
 }
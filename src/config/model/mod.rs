@@ -1 +1,3 @@
+pub(crate) mod config_file;
+pub(crate) mod diagnostic;
 pub(crate) mod keys;
@@ -0,0 +1,117 @@
+use crate::common::editor::open_in_editor;
+use crate::common::terminal::ask;
+use crate::config::model::config_file::{
+    check_config_file, config_file_path, parse_config_file, ConfigFile, CONFIG_FILE_TEMPLATE,
+};
+use crate::config::model::diagnostic::{Diagnostic, Severity};
+use crate::project::config::{check_project_config, find_project_config};
+use anyhow::{anyhow, Result};
+use colored::*;
+use std::fs;
+use std::path::Path;
+use std::sync::OnceLock;
+
+static CONFIG_FILE: OnceLock<ConfigFile> = OnceLock::new();
+
+pub fn config_file() -> &'static ConfigFile {
+    CONFIG_FILE.get_or_init(|| {
+        let path = config_file_path();
+        let Ok(content) = fs::read_to_string(&path) else {
+            return ConfigFile::default();
+        };
+        match parse_config_file(&content) {
+            Ok(config) => config,
+            Err(err) => {
+                eprintln!("ignoring {}: {}", path.display(), err);
+                ConfigFile::default()
+            }
+        }
+    })
+}
+
+pub fn edit_config_file() -> Result<()> {
+    let path = config_file_path();
+    let original = fs::read_to_string(&path).ok();
+    if original.is_none() {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)
+                .map_err(|err| anyhow!("could not create {}: {:?}", dir.display(), err))?;
+        }
+        fs::write(&path, CONFIG_FILE_TEMPLATE)
+            .map_err(|err| anyhow!("could not write {}: {:?}", path.display(), err))?;
+    }
+    loop {
+        open_in_editor(&path)?;
+
+        let content = fs::read_to_string(&path)
+            .map_err(|err| anyhow!("could not read {}: {:?}", path.display(), err))?;
+        let err = match parse_config_file(&content) {
+            Ok(config) => {
+                println!(
+                    "saved {} ({} settings)",
+                    path.display(),
+                    config.values.len()
+                );
+                return Ok(());
+            }
+            Err(err) => err,
+        };
+
+        eprintln!("{} is invalid: {}", path.display(), err);
+        if !ask("edit again?", true)? {
+            match &original {
+                Some(original) => fs::write(&path, original)?,
+                None => fs::remove_file(&path)?,
+            }
+            return Err(anyhow!(
+                "changes discarded, {} is unchanged",
+                path.display()
+            ));
+        }
+    }
+}
+
+pub fn validate_config(directory: &str) -> Result<()> {
+    let mut checked = Vec::new();
+    let mut diagnostics: Vec<Diagnostic> = Vec::new();
+
+    let global = config_file_path();
+    if let Ok(content) = fs::read_to_string(&global) {
+        diagnostics.extend(check_config_file(&global, &content).1);
+        checked.push(global);
+    }
+    if let Some(project) = find_project_config(Path::new(directory)) {
+        let content = fs::read_to_string(&project)
+            .map_err(|err| anyhow!("could not read {}: {:?}", project.display(), err))?;
+        diagnostics.extend(check_project_config(&project, &content));
+        checked.push(project);
+    }
+    if checked.is_empty() {
+        println!("no config files found");
+        return Ok(());
+    }
+
+    diagnostics.sort_by_key(|diagnostic| {
+        let file = checked.iter().position(|path| path == &diagnostic.file);
+        (file, diagnostic.line)
+    });
+    for diagnostic in &diagnostics {
+        println!("{}", diagnostic);
+    }
+    for path in &checked {
+        if !diagnostics
+            .iter()
+            .any(|diagnostic| &diagnostic.file == path)
+        {
+            println!("{}: {}", path.display(), "ok".green());
+        }
+    }
+    let errors = diagnostics
+        .iter()
+        .filter(|diagnostic| diagnostic.severity == Severity::Error)
+        .count();
+    match errors {
+        0 => Ok(()),
+        _ => Err(anyhow!("{} error(s) in config files", errors)),
+    }
+}
@@ -1,12 +1,23 @@
+use crate::args::ConfigAction;
 use crate::config::entity::config_entity::ConfigEntity;
+use crate::config::model::config_file::config_file_path;
+use crate::config::model::keys::ConfigKeys;
 use crate::config::repository::ConfigRepository;
+use crate::config::service::config_file_service::{self, config_file};
+use crate::provider::service::provider_service;
 use anyhow::{anyhow, Result};
 
+pub fn config<R: ConfigRepository>(repo: &R, action: &ConfigAction) -> Result<()> {
+    match action {
+        ConfigAction::Set { key, value } => set_config(repo, key, value),
+        ConfigAction::SetProvider { provider } => provider_service::set_provider(repo, provider),
+        ConfigAction::Edit => config_file_service::edit_config_file(),
+        ConfigAction::Validate { directory } => config_file_service::validate_config(directory),
+    }
+}
+
 pub fn write_config<R: ConfigRepository>(repo: &R, key: &str, value: &str) -> Result<()> {
-    let config = match repo.fetch_by_key(key) {
-        Ok(config) => Some(config),
-        Err(_) => None,
-    };
+    let config = repo.fetch_by_key(key).ok();
 
     let _ = match config {
         Some(c) => repo.update_config(c.id.unwrap_or_default(), key, value),
@@ -17,6 +28,9 @@ pub fn write_config<R: ConfigRepository>(repo: &R, key: &str, value: &str) -> Re
 }
 
 pub fn fetch_by_key<R: ConfigRepository>(repo: &R, key: &str) -> Result<ConfigEntity> {
+    if let Some(value) = config_file().get(key) {
+        return Ok(ConfigEntity::new(key, value));
+    }
     match repo.fetch_by_key(key) {
         Ok(config) => Ok(config),
         Err(_) => Err(anyhow!("failed to fetch configs")),
@@ -25,7 +39,33 @@ pub fn fetch_by_key<R: ConfigRepository>(repo: &R, key: &str) -> Result<ConfigEn
 
 pub fn fetch_config<R: ConfigRepository>(repo: &R) -> Result<Vec<ConfigEntity>> {
     match repo.fetch_all_configs() {
-        Ok(configs) => Ok(configs),
+        Ok(configs) => {
+            let file = config_file();
+            let mut merged = configs
+                .into_iter()
+                .filter(|config| file.get(&config.key).is_none())
+                .collect::<Vec<ConfigEntity>>();
+            merged.extend(
+                file.values
+                    .iter()
+                    .map(|(key, value)| ConfigEntity::new(key, value)),
+            );
+            Ok(merged)
+        }
         Err(_) => Err(anyhow!("failed to fetch configs")),
     }
 }
+
+pub fn set_config<R: ConfigRepository>(repo: &R, key: &str, value: &str) -> Result<()> {
+    let config_key =
+        ConfigKeys::from_key(key).ok_or_else(|| anyhow!("unknown config key '{}'", key))?;
+    config_key.validate(value).map_err(|err| anyhow!(err))?;
+    if config_file().get(key).is_some() {
+        eprintln!(
+            "{} also sets '{}' and takes precedence over this value",
+            config_file_path().display(),
+            key
+        );
+    }
+    write_config(repo, key, value)
+}
@@ -1,3 +1,6 @@
+pub(crate) mod config_file_service;
 pub(crate) mod config_service;
 pub(crate) mod open_ai_config;
+pub(crate) mod output_config;
+pub(crate) mod project_config;
 pub(crate) mod redacted_config;
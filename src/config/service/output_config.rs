@@ -0,0 +1,24 @@
+use crate::config::model::keys::ConfigKeys;
+use crate::config::repository::ConfigRepository;
+use crate::config::service::config_service;
+use crate::output::highlighting::DEFAULT_THEME;
+use crate::output::image::detect_image_protocol;
+use crate::output::outputter::OutputOptions;
+
+pub fn output_options<R: ConfigRepository>(repo: &R, session: &str) -> OutputOptions {
+    OutputOptions {
+        theme: value(repo, ConfigKeys::HighlightTheme).unwrap_or(DEFAULT_THEME.to_string()),
+        side_by_side: value(repo, ConfigKeys::DiffStyle).as_deref() == Some("side-by-side"),
+        session: session.to_string(),
+        images: match value(repo, ConfigKeys::InlineImages).as_deref() {
+            Some("off") => None,
+            _ => detect_image_protocol(),
+        },
+    }
+}
+
+fn value<R: ConfigRepository>(repo: &R, key: ConfigKeys) -> Option<String> {
+    config_service::fetch_by_key(repo, &key.to_key())
+        .ok()
+        .map(|config| config.value)
+}
@@ -0,0 +1,10 @@
+use crate::config::model::keys::ConfigKeys;
+use crate::config::repository::ConfigRepository;
+use crate::config::service::config_service;
+
+pub fn is_project_context_enabled<R: ConfigRepository>(repo: &R) -> bool {
+    match config_service::fetch_by_key(repo, &ConfigKeys::ProjectContext.to_key()) {
+        Ok(config) => config.value != "false",
+        Err(_) => true,
+    }
+}
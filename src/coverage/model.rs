@@ -0,0 +1,101 @@
+use regex::Regex;
+
+#[derive(Debug, Clone)]
+pub struct FileCoverage {
+    pub path: String,
+    pub total_lines: u32,
+    pub uncovered_lines: Vec<u32>,
+}
+
+impl FileCoverage {
+    pub fn coverage_percent(&self) -> f64 {
+        if self.total_lines == 0 {
+            return 100.0;
+        }
+        let covered = self.total_lines - self.uncovered_lines.len() as u32;
+        covered as f64 * 100.0 / self.total_lines as f64
+    }
+
+    pub fn uncovered_ranges(&self) -> Vec<(u32, u32)> {
+        let mut ranges: Vec<(u32, u32)> = Vec::new();
+        for line in &self.uncovered_lines {
+            match ranges.last_mut() {
+                Some((_, end)) if *end + 1 == *line => *end = *line,
+                _ => ranges.push((*line, *line)),
+            }
+        }
+        ranges
+    }
+}
+
+pub fn parse_report(report: &str) -> Vec<FileCoverage> {
+    if report.trim_start().starts_with('<') {
+        parse_cobertura(report)
+    } else {
+        parse_lcov(report)
+    }
+}
+
+pub fn parse_lcov(report: &str) -> Vec<FileCoverage> {
+    let mut files = Vec::new();
+    let mut current: Option<FileCoverage> = None;
+    for line in report.lines() {
+        if let Some(path) = line.strip_prefix("SF:") {
+            current = Some(FileCoverage {
+                path: path.trim().to_string(),
+                total_lines: 0,
+                uncovered_lines: vec![],
+            });
+        } else if let Some(data) = line.strip_prefix("DA:") {
+            let mut fields = data.split(',');
+            let line_number = fields.next().and_then(|n| n.trim().parse::<u32>().ok());
+            let hits = fields.next().and_then(|h| h.trim().parse::<u64>().ok());
+            if let (Some(file), Some(line_number), Some(hits)) =
+                (current.as_mut(), line_number, hits)
+            {
+                file.total_lines += 1;
+                if hits == 0 {
+                    file.uncovered_lines.push(line_number);
+                }
+            }
+        } else if line.trim() == "end_of_record" {
+            files.extend(current.take());
+        }
+    }
+    files.extend(current);
+    files
+}
+
+pub fn parse_cobertura(report: &str) -> Vec<FileCoverage> {
+    let class_re = Regex::new(r#"<class\b[^>]*\bfilename="([^"]+)""#).unwrap();
+    let line_re = Regex::new(r#"<line\b[^>]*\bnumber="(\d+)"[^>]*\bhits="(\d+)""#).unwrap();
+
+    let mut files: Vec<FileCoverage> = Vec::new();
+    for chunk in report.split("<class ").skip(1) {
+        let chunk = format!("<class {}", chunk);
+        let Some(path) = class_re.captures(&chunk).map(|caps| caps[1].to_string()) else {
+            continue;
+        };
+        let index = match files.iter().position(|file| file.path == path) {
+            Some(index) => index,
+            None => {
+                files.push(FileCoverage {
+                    path,
+                    total_lines: 0,
+                    uncovered_lines: vec![],
+                });
+                files.len() - 1
+            }
+        };
+        let file = &mut files[index];
+        for caps in line_re.captures_iter(&chunk) {
+            let line_number = caps[1].parse::<u32>().unwrap_or(0);
+            file.total_lines += 1;
+            if &caps[2] == "0" && !file.uncovered_lines.contains(&line_number) {
+                file.uncovered_lines.push(line_number);
+            }
+        }
+        file.uncovered_lines.sort();
+    }
+    files
+}
@@ -0,0 +1,98 @@
+use crate::config::repository::ConfigRepository;
+use crate::coverage::model::{parse_report, FileCoverage};
+use crate::openai::service::prompt::prompt;
+use crate::usage::repository::UsageRepository;
+use anyhow::{anyhow, Result};
+use std::fs;
+use std::path::Path;
+
+const MAX_SNIPPET_LINES: usize = 40;
+
+const PLAN_PROMPT: &str = "
+You help developers decide which tests to write next.
+You get a list of source files with their uncovered lines from a coverage report.
+Rank the gaps by how much a test would reduce risk: error handling, branching business logic and public APIs first, trivial getters and logging last.
+Answer with a numbered plan, one entry per test, naming the file, the function and the behaviour to assert.";
+
+const GENERATE_PROMPT: &str = "
+You help developers close gaps in test coverage.
+You get a list of source files with their uncovered lines from a coverage report.
+Pick the most valuable gaps first, give a short numbered plan and then write the tests for them in the style and framework the code already uses.";
+
+pub async fn coverage_gaps<R: ConfigRepository, UR: UsageRepository>(
+    repo: &R,
+    usage_repo: &UR,
+    report_path: &str,
+    top: usize,
+    generate: bool,
+) -> Result<()> {
+    let report = fs::read_to_string(report_path)
+        .map_err(|err| anyhow!("could not read {}: {:?}", report_path, err))?;
+    let mut files = parse_report(&report)
+        .into_iter()
+        .filter(|file| !file.uncovered_lines.is_empty())
+        .collect::<Vec<FileCoverage>>();
+    if files.is_empty() {
+        println!("no uncovered lines found in {}", report_path);
+        return Ok(());
+    }
+    files.sort_by_key(|file| std::cmp::Reverse(file.uncovered_lines.len()));
+    files.truncate(top);
+
+    println!("largest coverage gaps:");
+    for file in &files {
+        println!(
+            "  {:<50} {:>5.1}% covered, {} lines uncovered",
+            file.path,
+            file.coverage_percent(),
+            file.uncovered_lines.len()
+        );
+    }
+    println!();
+
+    let input = files
+        .iter()
+        .map(gap_description)
+        .collect::<Vec<String>>()
+        .join("\n");
+    let system_prompt = if generate {
+        GENERATE_PROMPT
+    } else {
+        PLAN_PROMPT
+    };
+    let answer = prompt(
+        repo,
+        usage_repo,
+        "coverage-gaps",
+        Path::new("."),
+        system_prompt,
+        &input,
+    )
+    .await?;
+    println!("{}", answer);
+    Ok(())
+}
+
+fn gap_description(file: &FileCoverage) -> String {
+    let source = fs::read_to_string(&file.path).unwrap_or_default();
+    let lines = source.lines().collect::<Vec<&str>>();
+    let mut description = format!(
+        "file: {} ({:.1}% covered)\n",
+        file.path,
+        file.coverage_percent()
+    );
+    let mut shown = 0;
+    for (start, end) in file.uncovered_ranges() {
+        description.push_str(&format!("uncovered lines {}-{}\n", start, end));
+        for number in start..=end {
+            if shown >= MAX_SNIPPET_LINES {
+                break;
+            }
+            if let Some(line) = (number as usize).checked_sub(1).and_then(|i| lines.get(i)) {
+                description.push_str(&format!("{:>5} | {}\n", number, line));
+                shown += 1;
+            }
+        }
+    }
+    description
+}
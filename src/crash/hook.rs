@@ -0,0 +1,36 @@
+use crate::crash::report::{crash_dir, environment_summary};
+use chrono::Utc;
+use std::backtrace::Backtrace;
+use std::fs::{self, create_dir_all};
+use std::panic::{self, PanicHookInfo};
+use std::path::PathBuf;
+
+pub fn install_panic_hook() {
+    let default_hook = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        match write_crash_report(info) {
+        Some(path) => eprintln!(
+            "termai crashed: {}\na crash report was written to {}\nrun `termai report-bug` to bundle it for a bug report",
+            info,
+            path.display()
+        ),
+        None => default_hook(info),
+    }
+    }));
+}
+
+fn write_crash_report(info: &PanicHookInfo) -> Option<PathBuf> {
+    let crash_dir = crash_dir()?;
+    create_dir_all(&crash_dir).ok()?;
+    let now = Utc::now();
+    let path = crash_dir.join(format!("crash-{}.txt", now.format("%Y%m%d-%H%M%S")));
+    let report = format!(
+        "{}time: {}\n\n{}\n\nbacktrace:\n{}\n",
+        environment_summary(),
+        now.format("%Y-%m-%d %H:%M:%S"),
+        info,
+        Backtrace::force_capture()
+    );
+    fs::write(&path, report).ok()?;
+    Some(path)
+}
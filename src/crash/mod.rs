@@ -0,0 +1,2 @@
+pub(crate) mod hook;
+pub(crate) mod report;
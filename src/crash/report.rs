@@ -0,0 +1,61 @@
+use anyhow::{anyhow, Result};
+use chrono::Utc;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const MAX_BUNDLED_REPORTS: usize = 5;
+
+pub fn crash_dir() -> Option<PathBuf> {
+    dirs::home_dir().map(|home| home.join(".config/termai/crashes"))
+}
+
+pub fn environment_summary() -> String {
+    format!(
+        "termai {}\nos: {} ({})\n",
+        env!("CARGO_PKG_VERSION"),
+        std::env::consts::OS,
+        std::env::consts::ARCH
+    )
+}
+
+pub fn report_bug() -> Result<()> {
+    let crash_dir = crash_dir().ok_or_else(|| anyhow!("could not find the home directory"))?;
+    let mut reports = crash_reports(&crash_dir);
+    reports.sort();
+    reports.reverse();
+
+    let mut bundle = environment_summary();
+    if reports.is_empty() {
+        bundle.push_str("\nno crash reports found\n");
+    }
+    for report in reports.iter().take(MAX_BUNDLED_REPORTS) {
+        let content = fs::read_to_string(report)
+            .map_err(|err| anyhow!("could not read {}: {:?}", report.display(), err))?;
+        bundle.push_str(&format!("\n--- {} ---\n{}", report.display(), content));
+    }
+
+    let timestamp = Utc::now().format("%Y%m%d-%H%M%S");
+    let bundle_path = crash_dir
+        .parent()
+        .unwrap_or(&crash_dir)
+        .join(format!("bug-report-{}.txt", timestamp));
+    fs::write(&bundle_path, bundle)
+        .map_err(|err| anyhow!("could not write the bug report: {:?}", err))?;
+    println!(
+        "bug report written to {}\nplease check it for anything private before sharing it",
+        bundle_path.display()
+    );
+    Ok(())
+}
+
+fn crash_reports(crash_dir: &Path) -> Vec<PathBuf> {
+    fs::read_dir(crash_dir)
+        .map(|entries| {
+            entries
+                .flatten()
+                .map(|entry| entry.path())
+                .filter(|path| path.extension().is_some_and(|e| e == "txt"))
+                .collect()
+        })
+        .unwrap_or_default()
+}
@@ -0,0 +1,2 @@
+pub(crate) mod model;
+pub(crate) mod service;
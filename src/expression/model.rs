@@ -0,0 +1,176 @@
+use anyhow::{anyhow, Result};
+use colored::*;
+use regex::Regex;
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExpressionKind {
+    Regex,
+    Jq,
+}
+
+impl ExpressionKind {
+    pub fn language(&self) -> &str {
+        match self {
+            ExpressionKind::Regex => "regex",
+            ExpressionKind::Jq => "jq",
+        }
+    }
+}
+
+pub struct RegexSample {
+    pub text: String,
+    pub should_match: Option<bool>,
+}
+
+pub fn parse_regex_samples(content: &str) -> Vec<RegexSample> {
+    content
+        .lines()
+        .filter(|line| !line.is_empty())
+        .map(
+            |line| match (line.strip_prefix("+ "), line.strip_prefix("- ")) {
+                (Some(text), _) => RegexSample {
+                    text: text.to_string(),
+                    should_match: Some(true),
+                },
+                (_, Some(text)) => RegexSample {
+                    text: text.to_string(),
+                    should_match: Some(false),
+                },
+                _ => RegexSample {
+                    text: line.to_string(),
+                    should_match: None,
+                },
+            },
+        )
+        .collect()
+}
+
+pub struct Validation {
+    pub passed: bool,
+    pub report: String,
+    pub feedback: String,
+}
+
+pub fn validate_regex(expression: &str, samples: &[RegexSample]) -> Validation {
+    let regex = match Regex::new(expression) {
+        Ok(regex) => regex,
+        Err(err) => {
+            let message = format!("the regex does not compile: {}", err);
+            return Validation {
+                passed: false,
+                report: format!("{}\n", message),
+                feedback: message,
+            };
+        }
+    };
+
+    let mut report = String::new();
+    let mut failures = Vec::new();
+    let mut matched_any = false;
+    for sample in samples {
+        let is_match = regex.is_match(&sample.text);
+        matched_any |= is_match;
+        let marker = match (sample.should_match, is_match) {
+            (Some(expected), actual) if expected != actual => {
+                failures.push(format!(
+                    "'{}' should {}match",
+                    sample.text,
+                    if expected { "" } else { "not " }
+                ));
+                "✗".red()
+            }
+            (Some(_), _) => "✓".green(),
+            (None, true) => "•".cyan(),
+            (None, false) => " ".normal(),
+        };
+        report.push_str(&format!("{} {}\n", marker, highlight(&regex, &sample.text)));
+    }
+
+    let has_expectations = samples.iter().any(|sample| sample.should_match.is_some());
+    if !has_expectations && !matched_any {
+        failures.push("the regex matches none of the samples".to_string());
+    }
+    let feedback = failures.join("\n");
+    if !failures.is_empty() {
+        report.push_str(&feedback);
+        report.push('\n');
+    }
+    Validation {
+        passed: failures.is_empty(),
+        report,
+        feedback,
+    }
+}
+
+fn highlight(regex: &Regex, text: &str) -> String {
+    let mut highlighted = String::new();
+    let mut last = 0;
+    for found in regex.find_iter(text) {
+        highlighted.push_str(&text[last..found.start()]);
+        highlighted.push_str(&found.as_str().black().on_yellow().to_string());
+        last = found.end();
+    }
+    highlighted.push_str(&text[last..]);
+    highlighted
+}
+
+pub fn validate_jq(expression: &str, input: &str, expected: Option<&str>) -> Result<Validation> {
+    let mut child = Command::new("jq")
+        .arg(expression)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|err| anyhow!("could not run jq: {:?}", err))?;
+    if let Some(stdin) = child.stdin.as_mut() {
+        stdin.write_all(input.as_bytes())?;
+    }
+    let output = child.wait_with_output()?;
+    if !output.status.success() {
+        let message = format!(
+            "jq failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+        return Ok(Validation {
+            passed: false,
+            report: format!("{}\n", message),
+            feedback: message,
+        });
+    }
+
+    let result = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    let problem = match expected {
+        Some(expected) if normalize_json(expected) != normalize_json(&result) => {
+            Some(format!("expected:\n{}", expected.trim()))
+        }
+        None if result.is_empty() || result == "null" => {
+            Some("the filter produced no output".to_string())
+        }
+        _ => None,
+    };
+    let mut report = format!("{}\n", result.cyan());
+    let mut feedback = String::new();
+    if let Some(problem) = &problem {
+        report.push_str(&format!("{}\n", problem));
+        feedback = format!("the output was:\n{}\n{}", result, problem);
+    }
+    Ok(Validation {
+        passed: problem.is_none(),
+        report,
+        feedback,
+    })
+}
+
+fn normalize_json(text: &str) -> String {
+    serde_json::Deserializer::from_str(text)
+        .into_iter::<serde_json::Value>()
+        .map(|value| {
+            value
+                .map(|value| value.to_string())
+                .unwrap_or_else(|_| text.trim().to_string())
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
@@ -0,0 +1,104 @@
+use crate::config::repository::ConfigRepository;
+use crate::expression::model::{
+    parse_regex_samples, validate_jq, validate_regex, ExpressionKind, Validation,
+};
+use crate::openai::model::role::Role;
+use crate::openai::service::prompt::follow_up;
+use crate::session::model::session::Session;
+use crate::snippet::model::code_block::extract_code_blocks;
+use crate::usage::repository::UsageRepository;
+use anyhow::{anyhow, Result};
+use colored::*;
+use std::fs;
+use std::path::Path;
+
+const MAX_ROUNDS: usize = 5;
+
+const EXPRESSION_PROMPT: &str = "
+You write {language} expressions from a description.
+Answer with exactly one expression in a ```{language} code block and a single sentence explaining it.
+When told that the expression failed against the samples, fix it and answer in the same format.";
+
+pub async fn build_expression<R: ConfigRepository, UR: UsageRepository>(
+    repo: &R,
+    usage_repo: &UR,
+    kind: ExpressionKind,
+    description: &str,
+    test_file: &str,
+    expected: Option<&str>,
+) -> Result<()> {
+    let samples = fs::read_to_string(test_file)
+        .map_err(|err| anyhow!("could not read {}: {:?}", test_file, err))?;
+    let expected = match expected {
+        Some(path) => Some(
+            fs::read_to_string(path)
+                .map_err(|err| anyhow!("could not read {}: {:?}", path, err))?,
+        ),
+        None => None,
+    };
+
+    let mut session = Session::new_temporary();
+    session.add_raw_message(
+        EXPRESSION_PROMPT.replace("{language}", kind.language()),
+        Role::System,
+    );
+    let mut input = format!(
+        "description: {}\n\nsamples:\n{}",
+        description,
+        sample_excerpt(&samples)
+    );
+
+    for round in 1..=MAX_ROUNDS {
+        let answer = follow_up(
+            repo,
+            usage_repo,
+            kind.language(),
+            Path::new("."),
+            &mut session,
+            &input,
+        )
+        .await?;
+        let Some(expression) = extract_expression(&answer) else {
+            input = "answer with the expression in a code block".to_string();
+            continue;
+        };
+
+        let validation = match kind {
+            ExpressionKind::Regex => validate_regex(&expression, &parse_regex_samples(&samples)),
+            ExpressionKind::Jq => validate_jq(&expression, &samples, expected.as_deref())?,
+        };
+        println!("{} {}", format!("round {}:", round).bold(), expression);
+        print!("{}", validation.report);
+        println!();
+
+        if validation.passed {
+            println!("{}", expression.green().bold());
+            return Ok(());
+        }
+        input = failure_message(&validation);
+    }
+
+    Err(anyhow!(
+        "no expression passed the samples after {} rounds",
+        MAX_ROUNDS
+    ))
+}
+
+fn extract_expression(answer: &str) -> Option<String> {
+    extract_code_blocks(answer)
+        .into_iter()
+        .next()
+        .map(|block| block.content.trim().to_string())
+        .filter(|expression| !expression.is_empty())
+}
+
+fn sample_excerpt(samples: &str) -> String {
+    samples.chars().take(4000).collect()
+}
+
+fn failure_message(validation: &Validation) -> String {
+    format!(
+        "the expression failed against the samples:\n{}\nfix it.",
+        validation.feedback
+    )
+}
@@ -0,0 +1 @@
+pub(crate) mod expression_service;
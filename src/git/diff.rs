@@ -0,0 +1,155 @@
+use crate::git::repository::run_git;
+use anyhow::Result;
+use std::path::Path;
+
+const LOCK_FILES: [&str; 9] = [
+    "Cargo.lock",
+    "package-lock.json",
+    "yarn.lock",
+    "pnpm-lock.yaml",
+    "poetry.lock",
+    "Pipfile.lock",
+    "Gemfile.lock",
+    "composer.lock",
+    "go.sum",
+];
+
+const GENERATED_SUFFIXES: [&str; 6] = [".min.js", ".min.css", ".map", ".pb.go", "_pb2.py", ".snap"];
+
+pub struct DiffOptions {
+    pub include_submodules: bool,
+    pub include_generated: bool,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum ChangeKind {
+    Added,
+    Modified,
+    Deleted,
+    Renamed { from: String, similarity: u32 },
+    Copied { from: String, similarity: u32 },
+    TypeChanged,
+}
+
+pub struct FileChange {
+    pub kind: ChangeKind,
+    pub path: String,
+}
+
+impl FileChange {
+    pub fn describe(&self) -> String {
+        match &self.kind {
+            ChangeKind::Added => format!("added {}", self.path),
+            ChangeKind::Modified => format!("modified {}", self.path),
+            ChangeKind::Deleted => format!("deleted {}", self.path),
+            ChangeKind::Renamed { from, similarity } => {
+                format!(
+                    "renamed {} -> {} ({}% similar)",
+                    from, self.path, similarity
+                )
+            }
+            ChangeKind::Copied { from, similarity } => {
+                format!("copied {} -> {} ({}% similar)", from, self.path, similarity)
+            }
+            ChangeKind::TypeChanged => format!("type changed {}", self.path),
+        }
+    }
+}
+
+pub fn commit_diff(dir: &Path, sha: &str, options: &DiffOptions) -> Result<String> {
+    let changes = commit_changes(dir, sha)?;
+    let skipped = if options.include_generated {
+        vec![]
+    } else {
+        changes
+            .iter()
+            .filter(|change| is_generated(&change.path))
+            .collect::<Vec<&FileChange>>()
+    };
+
+    let submodule = if options.include_submodules {
+        "--submodule=diff"
+    } else {
+        "--submodule=short"
+    };
+    let excludes = skipped
+        .iter()
+        .map(|change| format!(":(exclude){}", change.path))
+        .collect::<Vec<String>>();
+    let mut args = vec![
+        "show",
+        "--format=",
+        "--patch",
+        "-M",
+        "-C",
+        submodule,
+        sha,
+        "--",
+        ".",
+    ];
+    args.extend(excludes.iter().map(|exclude| exclude.as_str()));
+    let patch = run_git(dir, &args)?;
+
+    let mut diff = String::from("changed files:\n");
+    for change in &changes {
+        diff.push_str(&format!("  {}\n", change.describe()));
+    }
+    if !skipped.is_empty() {
+        diff.push_str("diff left out for lock and generated files:\n");
+        for change in &skipped {
+            diff.push_str(&format!("  {}\n", change.path));
+        }
+    }
+    diff.push('\n');
+    diff.push_str(&patch);
+    Ok(diff)
+}
+
+pub fn commit_changes(dir: &Path, sha: &str) -> Result<Vec<FileChange>> {
+    let status = run_git(
+        dir,
+        &["show", "--format=", "--name-status", "-M", "-C", sha],
+    )?;
+    Ok(status.lines().filter_map(parse_name_status).collect())
+}
+
+fn parse_name_status(line: &str) -> Option<FileChange> {
+    let mut fields = line.split('\t');
+    let status = fields.next()?;
+    let first = fields.next()?.to_string();
+    let second = fields.next().map(|path| path.to_string());
+    let similarity = status[1..].parse::<u32>().unwrap_or(100);
+
+    let (kind, path) = match (status.chars().next()?, second) {
+        ('A', _) => (ChangeKind::Added, first),
+        ('D', _) => (ChangeKind::Deleted, first),
+        ('T', _) => (ChangeKind::TypeChanged, first),
+        ('R', Some(path)) => (
+            ChangeKind::Renamed {
+                from: first,
+                similarity,
+            },
+            path,
+        ),
+        ('C', Some(path)) => (
+            ChangeKind::Copied {
+                from: first,
+                similarity,
+            },
+            path,
+        ),
+        _ => (ChangeKind::Modified, first),
+    };
+    Some(FileChange { kind, path })
+}
+
+pub fn is_generated(path: &str) -> bool {
+    let file_name = Path::new(path)
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or(path);
+    LOCK_FILES.contains(&file_name)
+        || GENERATED_SUFFIXES
+            .iter()
+            .any(|suffix| file_name.ends_with(suffix))
+}
@@ -0,0 +1,3 @@
+pub(crate) mod diff;
+pub(crate) mod prompt;
+pub(crate) mod repository;
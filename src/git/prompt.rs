@@ -0,0 +1,29 @@
+use crate::git::repository::current_branch;
+use crate::project::config::PromptLayer;
+use std::path::Path;
+
+pub fn layered_prompt(base: &str, layer: &PromptLayer, dir: &Path) -> String {
+    let mut lines = Vec::new();
+    if let Some(tone) = &layer.tone {
+        lines.push(format!("Use a {} tone.", tone.trim()));
+    }
+    if let Some(language) = &layer.language {
+        lines.push(format!("Write in {}.", language.trim()));
+    }
+    lines.extend(
+        layer
+            .instructions
+            .iter()
+            .map(|line| line.trim().to_string()),
+    );
+    if lines.is_empty() {
+        return base.to_string();
+    }
+
+    let mut layered = format!("{}\nProject instructions:\n{}", base, lines.join("\n"));
+    if layered.contains("{branch}") {
+        let branch = current_branch(dir).unwrap_or_default();
+        layered = layered.replace("{branch}", &branch);
+    }
+    layered
+}
@@ -0,0 +1,55 @@
+use anyhow::{anyhow, Result};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+pub fn run_git(dir: &Path, args: &[&str]) -> Result<String> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(args)
+        .output()
+        .map_err(|err| anyhow!("could not run git: {:?}", err))?;
+    if !output.status.success() {
+        return Err(anyhow!(
+            "git {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+pub fn repo_root(dir: &Path) -> Result<PathBuf> {
+    let root = run_git(dir, &["rev-parse", "--show-toplevel"])
+        .map_err(|_| anyhow!("{} is not inside a git repository", dir.display()))?;
+    Ok(PathBuf::from(root.trim()))
+}
+
+pub fn hooks_dir(dir: &Path) -> Result<PathBuf> {
+    let root = repo_root(dir)?;
+    let hooks = run_git(
+        &root,
+        &["rev-parse", "--path-format=absolute", "--git-path", "hooks"],
+    )?;
+    Ok(PathBuf::from(hooks.trim()))
+}
+
+pub fn submodules(dir: &Path) -> Result<Vec<String>> {
+    let root = repo_root(dir)?;
+    let status = run_git(&root, &["submodule", "status", "--recursive"])?;
+    Ok(status
+        .lines()
+        .filter_map(|line| line[1..].split_whitespace().nth(1))
+        .map(|path| path.to_string())
+        .collect())
+}
+
+pub fn commit_message(dir: &Path, sha: &str) -> Result<String> {
+    run_git(dir, &["log", "-1", "--format=%B", sha])
+}
+
+pub fn current_branch(dir: &Path) -> Result<String> {
+    Ok(run_git(dir, &["rev-parse", "--abbrev-ref", "HEAD"])?
+        .trim()
+        .to_string())
+}
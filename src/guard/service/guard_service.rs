@@ -0,0 +1,49 @@
+use crate::config::model::keys::ConfigKeys;
+use crate::config::repository::ConfigRepository;
+use crate::config::service::config_service;
+use crate::guard::model::{data_blocks, suspicious_patterns};
+use crate::openai::model::role::Role;
+use crate::session::model::session::Session;
+use anyhow::{anyhow, Result};
+use colored::*;
+
+pub fn check_injection<R: ConfigRepository>(repo: &R, session: &Session) -> Result<()> {
+    let mode = config_service::fetch_by_key(repo, &ConfigKeys::InjectionGuard.to_key())
+        .map(|config| config.value)
+        .unwrap_or_else(|_| "warn".to_string());
+    if mode == "off" {
+        return Ok(());
+    }
+    let Some(message) = session
+        .messages
+        .iter()
+        .rev()
+        .find(|message| message.role == Role::User)
+    else {
+        return Ok(());
+    };
+
+    let mut findings = Vec::new();
+    for (source, content) in data_blocks(&message.content_with_context()) {
+        for description in suspicious_patterns(&content) {
+            findings.push(format!("{} {}", source, description));
+        }
+    }
+    if findings.is_empty() {
+        return Ok(());
+    }
+    if mode == "block" {
+        return Err(anyhow!(
+            "possible prompt injection, nothing was sent: {}\nset injection_guard to warn to send it anyway",
+            findings.join("; ")
+        ));
+    }
+    for finding in findings {
+        eprintln!(
+            "{} possible prompt injection, {}",
+            "warning:".yellow().bold(),
+            finding
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,2 @@
+pub(crate) mod guard_service;
+pub(crate) mod paste_service;
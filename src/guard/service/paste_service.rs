@@ -0,0 +1,105 @@
+use crate::common::terminal::{answer, is_interactive};
+use crate::config::model::keys::ConfigKeys;
+use crate::config::repository::ConfigRepository;
+use crate::config::service::config_service;
+use crate::openai::service::prompt::prompt;
+use crate::output::markdown::truncate;
+use crate::output::progress::status;
+use crate::path::sample::sample_lines;
+use crate::usage::model::pricing::price_for_model;
+use crate::usage::model::token_usage::estimate_tokens;
+use crate::usage::repository::UsageRepository;
+use anyhow::{anyhow, Result};
+use colored::*;
+use std::path::Path;
+
+const DEFAULT_PASTE_LIMIT_TOKENS: u32 = 20000;
+const PREVIEW_LINES: usize = 5;
+const PREVIEW_WIDTH: usize = 100;
+const SUMMARY_INPUT_SHARE: u32 = 4;
+
+const PASTE_SUMMARY_PROMPT: &str = "
+You summarize text that was piped into a terminal assistant so it can be used as context for a question.
+Keep names, numbers, error messages and identifiers exactly as written.
+Answer with the summary only.";
+
+pub async fn check_piped_input<R: ConfigRepository, UR: UsageRepository>(
+    repo: &R,
+    usage_repo: &UR,
+    directory: &Path,
+    piped_input: Option<String>,
+    model: &str,
+    send_large: bool,
+) -> Result<Option<String>> {
+    let Some(piped) = piped_input.filter(|piped| !piped.trim().is_empty()) else {
+        return Ok(None);
+    };
+    let limit = config_service::fetch_by_key(repo, &ConfigKeys::PasteLimitTokens.to_key())
+        .ok()
+        .and_then(|config| config.value.parse::<u32>().ok())
+        .unwrap_or(DEFAULT_PASTE_LIMIT_TOKENS);
+    let tokens = estimate_tokens(&piped);
+    if send_large || limit == 0 || tokens <= limit {
+        return Ok(Some(piped));
+    }
+    if !is_interactive() {
+        return Err(anyhow!(
+            "the piped input is about {} tokens, above paste_limit_tokens ({}), pass --send-large to send it anyway",
+            tokens,
+            limit
+        ));
+    }
+
+    let lines = piped.lines().count();
+    eprintln!(
+        "{} the piped input is {} lines, about {} tokens (~${:.4} with {})",
+        "warning:".yellow().bold(),
+        lines,
+        tokens,
+        price_for_model(model).cost(tokens, 0),
+        model
+    );
+    for line in piped.lines().take(PREVIEW_LINES) {
+        eprintln!("  {}", truncate(line, PREVIEW_WIDTH).dimmed());
+    }
+    if lines > PREVIEW_LINES {
+        eprintln!(
+            "  {}",
+            format!("... {} more lines", lines - PREVIEW_LINES).dimmed()
+        );
+    }
+    let answer = answer(&format!(
+        "[s]end it all, [t]runcate to about {} tokens, su[m]marize it first or [c]ancel?",
+        limit
+    ))?;
+
+    match answer.to_lowercase().as_str() {
+        "s" | "send" => Ok(Some(piped)),
+        "t" | "truncate" => {
+            let sample = sample_lines(&piped, limit, |_| false);
+            status(&format!(
+                "kept {} of {} lines (head and tail)",
+                sample.kept_lines, sample.total_lines
+            ));
+            Ok(Some(sample.text))
+        }
+        "m" | "summarize" => {
+            let sample = sample_lines(&piped, limit * SUMMARY_INPUT_SHARE, |_| false);
+            let summary = prompt(
+                repo,
+                usage_repo,
+                "ask",
+                directory,
+                PASTE_SUMMARY_PROMPT,
+                &sample.text,
+            )
+            .await?;
+            Ok(Some(format!(
+                "[summary of the piped input, which had about {} tokens]\n{}",
+                tokens,
+                summary.trim()
+            )))
+        }
+        _ => Err(anyhow!("cancelled, nothing was sent")),
+    }
+}
@@ -0,0 +1,94 @@
+use anyhow::{anyhow, Result};
+use clap::Command;
+use colored::*;
+use serde::Deserialize;
+use std::sync::OnceLock;
+
+const EXAMPLES_TOML: &str = include_str!("examples.toml");
+const ASK: &str = "ask";
+const EPILOG_EXAMPLES: usize = 2;
+
+#[derive(Deserialize, Debug)]
+pub struct Example {
+    pub command: String,
+    pub title: String,
+    pub run: String,
+}
+
+#[derive(Deserialize)]
+struct Registry {
+    example: Vec<Example>,
+}
+
+static EXAMPLES: OnceLock<Vec<Example>> = OnceLock::new();
+
+pub fn examples() -> &'static [Example] {
+    EXAMPLES.get_or_init(|| {
+        toml::from_str::<Registry>(EXAMPLES_TOML)
+            .expect("examples.toml is embedded and must parse")
+            .example
+    })
+}
+
+pub fn examples_for(command: &str) -> Vec<&'static Example> {
+    examples()
+        .iter()
+        .filter(|example| example.command == command)
+        .collect()
+}
+
+pub fn print_examples(command: Option<&str>) -> Result<()> {
+    let Some(command) = command else {
+        println!("{}", "Recipes are available for:".bold());
+        let mut commands: Vec<&str> = Vec::new();
+        for example in examples() {
+            if !commands.contains(&example.command.as_str()) {
+                commands.push(&example.command);
+            }
+        }
+        for command in commands {
+            println!("  {:<16}{} recipes", command, examples_for(command).len());
+        }
+        println!("\nrun `termai examples <command>` to see them");
+        return Ok(());
+    };
+
+    let found = examples_for(command);
+    if found.is_empty() {
+        return Err(anyhow!(
+            "no examples for '{}', run `termai examples` to see the commands that have some",
+            command
+        ));
+    }
+    for example in found {
+        println!("{}", format!("# {}", example.title).dimmed());
+        println!("{}\n", example.run);
+    }
+    Ok(())
+}
+
+pub fn with_examples(command: Command) -> Command {
+    let names = command
+        .get_subcommands()
+        .map(|subcommand| subcommand.get_name().to_string())
+        .collect::<Vec<String>>();
+    let mut command = command.after_help(epilog(ASK, "termai examples"));
+    for name in names {
+        if examples_for(&name).is_empty() {
+            continue;
+        }
+        let more = format!("termai examples {}", name);
+        let epilog = epilog(&name, &more);
+        command = command.mut_subcommand(&name, |subcommand| subcommand.after_help(epilog));
+    }
+    command
+}
+
+fn epilog(command: &str, more: &str) -> String {
+    let mut epilog = String::from("Examples:\n");
+    for example in examples_for(command).iter().take(EPILOG_EXAMPLES) {
+        epilog.push_str(&format!("  # {}\n  {}\n", example.title, example.run));
+    }
+    epilog.push_str(&format!("\nMore recipes: `{}`", more));
+    epilog
+}
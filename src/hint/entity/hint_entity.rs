@@ -0,0 +1,10 @@
+pub struct HintEntity {
+    pub count: u32,
+    pub shown: bool,
+}
+
+impl HintEntity {
+    pub fn new(count: u32, shown: bool) -> Self {
+        Self { count, shown }
+    }
+}
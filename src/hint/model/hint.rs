@@ -0,0 +1,53 @@
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum HintEvent {
+    QuestionWithoutSession,
+    PipedDiff,
+    AnswerWithCode,
+    AnswerWithDiff,
+    ContextSummarized,
+}
+
+pub struct Hint {
+    pub event: HintEvent,
+    pub name: &'static str,
+    pub threshold: u32,
+    pub text: &'static str,
+}
+
+pub const HINTS: [Hint; 5] = [
+    Hint {
+        event: HintEvent::QuestionWithoutSession,
+        name: "session",
+        threshold: 3,
+        text: "follow-up questions can build on earlier answers: add `--session <name>`",
+    },
+    Hint {
+        event: HintEvent::PipedDiff,
+        name: "notes",
+        threshold: 2,
+        text: "`termai notes install-hook` describes every commit in git notes automatically",
+    },
+    Hint {
+        event: HintEvent::AnswerWithCode,
+        name: "snippet",
+        threshold: 3,
+        text:
+            "keep a code block from an answer with `termai snippet save <name> --session <session>`",
+    },
+    Hint {
+        event: HintEvent::AnswerWithDiff,
+        name: "apply-diff",
+        threshold: 1,
+        text: "`termai config set diff_style side-by-side` shows diffs in two columns",
+    },
+    Hint {
+        event: HintEvent::ContextSummarized,
+        name: "context-budget",
+        threshold: 1,
+        text: "set context.max_tokens in .termai.toml to control how much context is sent",
+    },
+];
+
+pub fn hint_for(event: HintEvent) -> Option<&'static Hint> {
+    HINTS.iter().find(|hint| hint.event == event)
+}
@@ -0,0 +1,37 @@
+use super::HintRepository;
+use crate::hint::entity::hint_entity::HintEntity;
+use crate::repository::db::SqliteRepository;
+use rusqlite::{params, Result};
+
+impl HintRepository for SqliteRepository {
+    type Error = rusqlite::Error;
+
+    fn record_event(&self, name: &str) -> Result<HintEntity, Self::Error> {
+        self.conn.execute(
+            "INSERT INTO hints (name, count, shown) VALUES (?1, 1, 0)
+             ON CONFLICT(name) DO UPDATE SET count = count + 1",
+            params![name],
+        )?;
+        self.conn.query_row(
+            "SELECT count, shown FROM hints WHERE name = ?1",
+            params![name],
+            |row| {
+                let count: u32 = row.get(0)?;
+                let shown: bool = row.get(1)?;
+
+                Ok(HintEntity::new(count, shown))
+            },
+        )
+    }
+
+    fn mark_shown(&self, name: &str) -> Result<(), Self::Error> {
+        self.conn
+            .execute("UPDATE hints SET shown = 1 WHERE name = ?1", params![name])?;
+        Ok(())
+    }
+
+    fn reset_hints(&self) -> Result<(), Self::Error> {
+        self.conn.execute("DELETE FROM hints", [])?;
+        Ok(())
+    }
+}
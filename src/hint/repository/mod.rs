@@ -0,0 +1,15 @@
+use super::entity::hint_entity::HintEntity;
+use std::fmt::Debug;
+
+pub(crate) mod hint_repository;
+
+pub trait HintRepository
+where
+    Self::Error: Debug,
+{
+    type Error;
+
+    fn record_event(&self, name: &str) -> Result<HintEntity, Self::Error>;
+    fn mark_shown(&self, name: &str) -> Result<(), Self::Error>;
+    fn reset_hints(&self) -> Result<(), Self::Error>;
+}
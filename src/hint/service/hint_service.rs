@@ -0,0 +1,66 @@
+use crate::config::model::keys::ConfigKeys;
+use crate::config::repository::ConfigRepository;
+use crate::config::service::config_service;
+use crate::hint::model::hint::{hint_for, HintEvent};
+use crate::hint::repository::HintRepository;
+use crate::output::progress::is_quiet;
+use anyhow::{anyhow, Result};
+use colored::*;
+use std::io::{self, IsTerminal};
+
+pub fn hints_enabled<R: ConfigRepository>(repo: &R) -> bool {
+    match config_service::fetch_by_key(repo, &ConfigKeys::Hints.to_key()) {
+        Ok(config) => config.value != "off",
+        Err(_) => true,
+    }
+}
+
+pub fn record_events<R: ConfigRepository, HR: HintRepository>(
+    repo: &R,
+    hint_repo: &HR,
+    events: &[HintEvent],
+) {
+    if !hints_enabled(repo) || is_quiet() || !io::stderr().is_terminal() {
+        return;
+    }
+    for event in events {
+        let Some(hint) = hint_for(*event) else {
+            continue;
+        };
+        let Ok(recorded) = hint_repo.record_event(hint.name) else {
+            continue;
+        };
+        if recorded.shown || recorded.count < hint.threshold {
+            continue;
+        }
+        eprintln!(
+            "{} {} {}",
+            "hint:".cyan(),
+            hint.text,
+            "(`termai hints off` to stop these)".dimmed()
+        );
+        let _ = hint_repo.mark_shown(hint.name);
+        return;
+    }
+}
+
+pub fn set_hints<R: ConfigRepository, HR: HintRepository>(
+    repo: &R,
+    hint_repo: &HR,
+    action: &str,
+) -> Result<()> {
+    match action {
+        "reset" => {
+            hint_repo
+                .reset_hints()
+                .map_err(|err| anyhow!("could not reset hints: {:?}", err))?;
+            println!("hints will be shown again");
+            Ok(())
+        }
+        _ => {
+            config_service::set_config(repo, &ConfigKeys::Hints.to_key(), action)?;
+            println!("hints are {}", action);
+            Ok(())
+        }
+    }
+}
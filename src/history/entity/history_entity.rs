@@ -0,0 +1,27 @@
+use chrono::NaiveDateTime;
+
+pub struct HistoryEntity {
+    pub id: String,
+    pub prompt: String,
+    pub arguments: String,
+    pub working_directory: String,
+    pub created_at: NaiveDateTime,
+}
+
+impl HistoryEntity {
+    pub fn new(
+        id: String,
+        prompt: String,
+        arguments: String,
+        working_directory: String,
+        created_at: NaiveDateTime,
+    ) -> Self {
+        Self {
+            id,
+            prompt,
+            arguments,
+            working_directory,
+            created_at,
+        }
+    }
+}
@@ -0,0 +1,73 @@
+use crate::args::Args;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Default)]
+pub struct HistoryArguments {
+    pub flags: Vec<String>,
+    pub directory: Option<String>,
+    pub piped: bool,
+}
+
+impl HistoryArguments {
+    pub fn from_args(args: &Args, piped: bool) -> Self {
+        let mut flags = Vec::new();
+        let options = [
+            ("--session", &args.session),
+            ("--system-prompt", &args.system_prompt),
+            ("--model", &args.model),
+            ("--context-set", &args.context_set),
+            ("--from-editor", &args.from_editor),
+            ("--answer-language", &args.answer_language),
+        ];
+        for (flag, value) in options {
+            if let Some(value) = value {
+                flags.push(flag.to_string());
+                flags.push(value.clone());
+            }
+        }
+        let numbers = [
+            (
+                "--temperature",
+                args.temperature.map(|value| value.to_string()),
+            ),
+            ("--top-p", args.top_p.map(|value| value.to_string())),
+            (
+                "--max-output-tokens",
+                args.max_output_tokens.map(|value| value.to_string()),
+            ),
+        ];
+        for (flag, value) in numbers {
+            if let Some(value) = value {
+                flags.push(flag.to_string());
+                flags.push(value);
+            }
+        }
+        for stop in &args.stop {
+            flags.push("--stop".to_string());
+            flags.push(stop.clone());
+        }
+        if !args.exclude.is_empty() {
+            flags.push("--exclude".to_string());
+            flags.push(args.exclude.join(","));
+        }
+        let switches = [
+            ("--ignore-budget", args.ignore_budget),
+            ("--no-summarize", args.no_summarize),
+            ("--code-only", args.code_only),
+            ("--read-only", args.read_only),
+            ("--quiet", args.quiet),
+            ("--no-stream", args.no_stream),
+            ("--no-cache", args.no_cache),
+        ];
+        for (flag, set) in switches {
+            if set {
+                flags.push(flag.to_string());
+            }
+        }
+        Self {
+            flags,
+            directory: args.directory.clone(),
+            piped,
+        }
+    }
+}
@@ -0,0 +1,57 @@
+use super::HistoryRepository;
+use crate::history::entity::history_entity::HistoryEntity;
+use crate::repository::db::SqliteRepository;
+use chrono::NaiveDateTime;
+use rusqlite::{params, Result};
+
+const DATE_TIME_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+
+impl HistoryRepository for SqliteRepository {
+    type Error = rusqlite::Error;
+
+    fn fetch_history(&self, limit: u32) -> Result<Vec<HistoryEntity>, Self::Error> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, prompt, arguments, working_directory, created_at
+             FROM history ORDER BY created_at DESC, rowid DESC LIMIT ?1",
+        )?;
+        let rows = stmt.query_map(params![limit], |row| {
+            let id: String = row.get(0)?;
+            let prompt: String = row.get(1)?;
+            let arguments: String = row.get(2)?;
+            let working_directory: String = row.get(3)?;
+            let created_at_str: String = row.get(4)?;
+            let created_at = NaiveDateTime::parse_from_str(&created_at_str, DATE_TIME_FORMAT)
+                .expect("Invalid DateTime format");
+
+            Ok(HistoryEntity::new(
+                id,
+                prompt,
+                arguments,
+                working_directory,
+                created_at,
+            ))
+        })?;
+
+        let mut history = Vec::new();
+        for entry in rows {
+            history.push(entry?);
+        }
+        Ok(history)
+    }
+
+    fn add_history(&self, entry: &HistoryEntity) -> Result<(), Self::Error> {
+        let created_at_str = entry.created_at.format(DATE_TIME_FORMAT).to_string();
+        self.conn.execute(
+            "INSERT INTO history (id, prompt, arguments, working_directory, created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![
+                entry.id,
+                entry.prompt,
+                entry.arguments,
+                entry.working_directory,
+                created_at_str
+            ],
+        )?;
+        Ok(())
+    }
+}
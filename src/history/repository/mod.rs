@@ -0,0 +1,14 @@
+use super::entity::history_entity::HistoryEntity;
+use std::fmt::Debug;
+
+pub(crate) mod history_repository;
+
+pub trait HistoryRepository
+where
+    Self::Error: Debug,
+{
+    type Error;
+
+    fn fetch_history(&self, limit: u32) -> Result<Vec<HistoryEntity>, Self::Error>;
+    fn add_history(&self, entry: &HistoryEntity) -> Result<(), Self::Error>;
+}
@@ -0,0 +1,149 @@
+use crate::args::{Args, HistoryAction};
+use crate::common::editor::edit_text;
+use crate::common::unique_id::generate_uuid_v4;
+use crate::config::repository::ConfigRepository;
+use crate::config::service::redacted_config;
+use crate::history::entity::history_entity::HistoryEntity;
+use crate::history::model::history_arguments::HistoryArguments;
+use crate::history::repository::HistoryRepository;
+use crate::output::markdown::truncate;
+use crate::output::progress::status;
+use crate::redactions::redact::redact_with_mapping;
+use anyhow::{anyhow, Result};
+use chrono::Utc;
+use colored::*;
+use std::collections::HashMap;
+use std::process::Command;
+
+const PROMPT_WIDTH: usize = 60;
+
+pub fn record_prompt<HR: HistoryRepository>(history_repo: &HR, args: &Args, piped: bool) {
+    let Some(prompt) = &args.data else {
+        return;
+    };
+    let arguments = HistoryArguments::from_args(args, piped);
+    let working_directory = std::env::current_dir()
+        .map(|dir| dir.to_string_lossy().to_string())
+        .unwrap_or_else(|_| ".".to_string());
+    let entry = HistoryEntity::new(
+        generate_uuid_v4().to_string(),
+        prompt.clone(),
+        serde_json::to_string(&arguments).unwrap_or_default(),
+        working_directory,
+        Utc::now().naive_utc(),
+    );
+    if let Err(err) = history_repo.add_history(&entry) {
+        status(&format!(
+            "could not add the prompt to the history: {:?}",
+            err
+        ));
+    }
+}
+
+pub fn history<R: ConfigRepository, HR: HistoryRepository>(
+    repo: &R,
+    history_repo: &HR,
+    action: Option<&HistoryAction>,
+    limit: u32,
+    read_only: bool,
+) -> Result<()> {
+    match action {
+        None => list_history(repo, history_repo, limit),
+        Some(HistoryAction::Rerun { number, edit }) => {
+            rerun(history_repo, *number, *edit, read_only)
+        }
+    }
+}
+
+pub fn list_history<R: ConfigRepository, HR: HistoryRepository>(
+    repo: &R,
+    history_repo: &HR,
+    limit: u32,
+) -> Result<()> {
+    let history = history_repo
+        .fetch_history(limit)
+        .map_err(|err| anyhow!("could not read the history: {:?}", err))?;
+    if history.is_empty() {
+        println!("no prompts yet");
+        return Ok(());
+    }
+
+    let masks = redacted_config::fetch_redactions(repo)
+        .into_iter()
+        .map(|redaction| (redaction, "[redacted]".to_string()))
+        .collect::<HashMap<String, String>>();
+    for (number, entry) in history.iter().enumerate() {
+        let arguments = arguments(entry);
+        let prompt = redact_with_mapping(&masks, &entry.prompt).replace('\n', " ");
+        let mut context = arguments.flags.join(" ");
+        if let Some(directory) = &arguments.directory {
+            context = format!("{} {}", context, directory);
+        }
+        if arguments.piped {
+            context = format!("{} (piped input not kept)", context);
+        }
+        println!(
+            "{:>3}  {}  {}  {}",
+            number + 1,
+            entry
+                .created_at
+                .format("%Y-%m-%d %H:%M")
+                .to_string()
+                .dimmed(),
+            truncate(&prompt, PROMPT_WIDTH),
+            context.trim().dimmed()
+        );
+    }
+    Ok(())
+}
+
+pub fn rerun<HR: HistoryRepository>(
+    history_repo: &HR,
+    number: usize,
+    edit: bool,
+    read_only: bool,
+) -> Result<()> {
+    let history = history_repo
+        .fetch_history(number as u32)
+        .map_err(|err| anyhow!("could not read the history: {:?}", err))?;
+    let entry = history
+        .get(number.saturating_sub(1))
+        .ok_or_else(|| anyhow!("there is no prompt {} in the history", number))?;
+
+    let prompt = if edit {
+        edit_text(&entry.prompt, "termai-prompt.txt")?
+            .trim()
+            .to_string()
+    } else {
+        entry.prompt.clone()
+    };
+    if prompt.is_empty() {
+        return Err(anyhow!("the prompt is empty, nothing to run"));
+    }
+
+    let mut arguments = arguments(entry);
+    if read_only && !arguments.flags.iter().any(|flag| flag == "--read-only") {
+        arguments.flags.push("--read-only".to_string());
+    }
+    if arguments.piped {
+        status("the original piped input was not kept, running without it");
+    }
+    let mut command = Command::new(std::env::current_exe()?);
+    command
+        .args(&arguments.flags)
+        .arg("--")
+        .arg(&prompt)
+        .args(&arguments.directory)
+        .current_dir(&entry.working_directory);
+    let exit = command
+        .status()
+        .map_err(|err| anyhow!("could not run termai: {:?}", err))?;
+    if !exit.success() {
+        std::process::exit(exit.code().unwrap_or(1));
+    }
+    Ok(())
+}
+
+fn arguments(entry: &HistoryEntity) -> HistoryArguments {
+    serde_json::from_str(&entry.arguments).unwrap_or_default()
+}
@@ -0,0 +1 @@
+pub(crate) mod history_service;
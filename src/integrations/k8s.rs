@@ -0,0 +1,380 @@
+use crate::args::K8sAction;
+use crate::common::terminal::{ask, is_interactive};
+use crate::config::repository::ConfigRepository;
+use crate::openai::service::prompt::prompt;
+use crate::usage::repository::UsageRepository;
+use anyhow::{anyhow, Result};
+use std::path::Path;
+use std::process::Command;
+
+const LOG_TAIL_LINES: &str = "--tail=200";
+const LOG_KINDS: [&str; 17] = [
+    "po",
+    "pod",
+    "pods",
+    "deploy",
+    "deployment",
+    "deployments",
+    "sts",
+    "statefulset",
+    "statefulsets",
+    "ds",
+    "daemonset",
+    "daemonsets",
+    "job",
+    "jobs",
+    "rs",
+    "replicaset",
+    "replicasets",
+];
+
+const MASK: &str = "[masked]";
+const LAST_APPLIED: &str = "kubectl.kubernetes.io/last-applied-configuration:";
+const DATA_FIELDS: [&str; 3] = ["data:", "stringData:", "binaryData:"];
+
+const EXPLAIN_PROMPT: &str = "
+You explain Kubernetes resources to developers.
+Describe what the resource does, how it is configured and how it relates to other resources it references.
+Point out anything unusual in its configuration. Use plain text, no markdown.";
+
+const DIAGNOSE_PROMPT: &str = "
+You diagnose problems with Kubernetes resources.
+Use the manifest, the events from describe and the logs to find out what is wrong.
+Answer with the most likely cause first, then numbered steps to confirm and fix it, including the kubectl commands to run. Use plain text, no markdown.";
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum K8sMode {
+    Explain,
+    Diagnose,
+}
+
+pub struct K8sTarget<'a> {
+    pub resource: &'a str,
+    pub namespace: Option<&'a str>,
+    pub context: Option<&'a str>,
+}
+
+pub async fn k8s<R: ConfigRepository, UR: UsageRepository>(
+    repo: &R,
+    usage_repo: &UR,
+    action: &K8sAction,
+) -> Result<()> {
+    let (mode, resource, namespace, context, yes) = match action {
+        K8sAction::Explain {
+            resource,
+            namespace,
+            context,
+            yes,
+        } => (K8sMode::Explain, resource, namespace, context, yes),
+        K8sAction::Diagnose {
+            resource,
+            namespace,
+            context,
+            yes,
+        } => (K8sMode::Diagnose, resource, namespace, context, yes),
+    };
+    let target = K8sTarget {
+        resource,
+        namespace: namespace.as_deref(),
+        context: context.as_deref(),
+    };
+    explain_resource(repo, usage_repo, mode, &target, *yes).await
+}
+
+async fn explain_resource<R: ConfigRepository, UR: UsageRepository>(
+    repo: &R,
+    usage_repo: &UR,
+    mode: K8sMode,
+    target: &K8sTarget<'_>,
+    yes: bool,
+) -> Result<()> {
+    let kind = target
+        .resource
+        .split('/')
+        .next()
+        .unwrap_or_default()
+        .to_lowercase();
+    if target.resource.split('/').count() != 2 {
+        return Err(anyhow!(
+            "resources are given as kind/name, e.g. pod/api-7d9c"
+        ));
+    }
+    if kind.starts_with("secret") {
+        return Err(anyhow!("secrets are never sent to the model"));
+    }
+
+    let commands = kubectl_commands(&kind, &mode, target);
+    println!("termai will run:");
+    for command in &commands {
+        println!("  kubectl {}", command.join(" "));
+    }
+    if !yes && !confirm()? {
+        println!("cancelled");
+        return Ok(());
+    }
+
+    let mut context = String::new();
+    for command in &commands {
+        let output = match command.first().map(String::as_str) {
+            Some("get") => mask_manifest(&kubectl(command)?),
+            Some("describe") => mask_description(&kubectl(command)?),
+            _ => kubectl(command)?,
+        };
+        context.push_str(&format!("$ kubectl {}\n{}\n", command.join(" "), output));
+    }
+
+    let system_prompt = match mode {
+        K8sMode::Explain => EXPLAIN_PROMPT,
+        K8sMode::Diagnose => DIAGNOSE_PROMPT,
+    };
+    let answer = prompt(
+        repo,
+        usage_repo,
+        "k8s",
+        Path::new("."),
+        system_prompt,
+        &context,
+    )
+    .await?;
+    println!("\n{}", answer);
+    Ok(())
+}
+
+fn kubectl_commands(kind: &str, mode: &K8sMode, target: &K8sTarget<'_>) -> Vec<Vec<String>> {
+    let mut scope = Vec::new();
+    if let Some(context) = target.context {
+        scope.push(format!("--context={}", context));
+    }
+    if let Some(namespace) = target.namespace {
+        scope.push(format!("--namespace={}", namespace));
+    }
+    let with_scope = |args: &[&str]| {
+        let mut command = args.iter().map(|arg| arg.to_string()).collect::<Vec<_>>();
+        command.extend(scope.iter().cloned());
+        command
+    };
+
+    let mut commands = vec![
+        with_scope(&["get", target.resource, "-o", "yaml"]),
+        with_scope(&["describe", target.resource]),
+    ];
+    let base_kind = kind.split('.').next().unwrap_or_default();
+    let has_logs = LOG_KINDS.contains(&base_kind);
+    if *mode == K8sMode::Diagnose && has_logs {
+        commands.push(with_scope(&[
+            "logs",
+            target.resource,
+            "--all-containers",
+            LOG_TAIL_LINES,
+        ]));
+    }
+    commands
+}
+
+fn confirm() -> Result<bool> {
+    if !is_interactive() {
+        return Err(anyhow!(
+            "not running in a terminal, pass --yes to run the commands"
+        ));
+    }
+    ask("continue?", false)
+}
+
+fn kubectl(args: &[String]) -> Result<String> {
+    let output = Command::new("kubectl")
+        .args(args)
+        .output()
+        .map_err(|err| anyhow!("could not run kubectl: {:?}", err))?;
+    let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+        if args.first().is_some_and(|command| command == "logs") {
+            return Ok(format!("(no logs: {})", stderr));
+        }
+        return Err(anyhow!("kubectl {} failed: {}", args.join(" "), stderr));
+    }
+    Ok(stdout)
+}
+
+// Values in a manifest that are often credentials never reach the model: env `value:`
+// literals, the `data` of a ConfigMap and the last-applied-configuration annotation,
+// which repeats the whole manifest. Only the keys are kept.
+fn mask_manifest(manifest: &str) -> String {
+    let mut masked = Vec::new();
+    let mut data_indent = None;
+    let mut dropped_indent = None;
+    for line in manifest.lines() {
+        let trimmed = line.trim_start();
+        let indent = key_indent(line);
+        if let Some(dropped) = dropped_indent {
+            if trimmed.is_empty() || indent > dropped {
+                continue;
+            }
+            dropped_indent = None;
+        }
+        if let Some(data) = data_indent {
+            if !trimmed.is_empty() && indent > data {
+                masked.push(masked_entry(line));
+                dropped_indent = Some(indent);
+                continue;
+            }
+            data_indent = None;
+        }
+
+        let key = trimmed.trim_start_matches("- ");
+        if indent == 0 && DATA_FIELDS.contains(&key) {
+            masked.push(line.to_string());
+            data_indent = Some(indent);
+        } else if key.starts_with("value:") || key.starts_with(LAST_APPLIED) {
+            masked.push(masked_entry(line));
+            dropped_indent = Some(indent);
+        } else {
+            masked.push(line.to_string());
+        }
+    }
+    masked.join("\n")
+}
+
+// `kubectl describe` prints the data of a ConfigMap and the environment of
+// each container as plain text.
+fn mask_description(description: &str) -> String {
+    let mut masked = Vec::new();
+    let mut in_data = false;
+    let mut environment_indent = None;
+    let mut lines = description.lines().peekable();
+    while let Some(line) = lines.next() {
+        let trimmed = line.trim_start();
+        let indent = line.len() - trimmed.len();
+        if in_data {
+            if !(line.starts_with("BinaryData") || line.starts_with("Events:")) {
+                continue;
+            }
+            in_data = false;
+        }
+        if let Some(environment) = environment_indent {
+            if !trimmed.is_empty() && indent > environment {
+                match trimmed.split_once(':') {
+                    Some((name, value)) if !value.trim().starts_with('<') => {
+                        masked.push(format!("{}{}:  {}", &line[..indent], name, MASK))
+                    }
+                    _ => masked.push(line.to_string()),
+                }
+                continue;
+            }
+            environment_indent = None;
+        }
+
+        masked.push(line.to_string());
+        if line == "Data" && lines.peek().is_some_and(|next| next.starts_with("====")) {
+            masked.extend(lines.next().map(str::to_string));
+            masked.push(MASK.to_string());
+            masked.push(String::new());
+            in_data = true;
+        } else if trimmed == "Environment:" {
+            environment_indent = Some(indent);
+        }
+    }
+    masked.join("\n")
+}
+
+// The column of the key, a list item `- key: value` counts from after the dash.
+fn key_indent(line: &str) -> usize {
+    let trimmed = line.trim_start();
+    let indent = line.len() - trimmed.len();
+    if trimmed.starts_with("- ") {
+        indent + 2
+    } else {
+        indent
+    }
+}
+
+fn masked_entry(line: &str) -> String {
+    match line.split_once(':') {
+        Some((key, _)) => format!("{}: {}", key, MASK),
+        None => format!("{}{}", &line[..line.len() - line.trim_start().len()], MASK),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn manifest_values_that_hold_credentials_are_masked() {
+        let manifest = "\
+apiVersion: v1
+kind: ConfigMap
+data:
+  DATABASE_URL: postgres://admin:hunter2@db/app
+  app.properties: |
+    password=hunter2
+    mode=prod
+metadata:
+  annotations:
+    kubectl.kubernetes.io/last-applied-configuration: |
+      {\"data\":{\"DATABASE_URL\":\"postgres://admin:hunter2@db/app\"}}
+    team: payments
+  name: app-config
+spec:
+  containers:
+  - env:
+    - name: API_TOKEN
+      value: tok-123
+    - value: inline-456
+      name: OTHER
+    - name: FROM_SECRET
+      valueFrom:
+        secretKeyRef:
+          key: token
+    image: app:1.2
+";
+        let masked = mask_manifest(manifest);
+        for secret in ["hunter2", "tok-123", "inline-456", "mode=prod"] {
+            assert!(!masked.contains(secret), "{} leaked:\n{}", secret, masked);
+        }
+        for kept in [
+            "  DATABASE_URL: [masked]",
+            "  app.properties: [masked]",
+            "    kubectl.kubernetes.io/last-applied-configuration: [masked]",
+            "    team: payments",
+            "      value: [masked]",
+            "    - value: [masked]",
+            "      name: OTHER",
+            "          key: token",
+            "    image: app:1.2",
+        ] {
+            assert!(masked.contains(kept), "{} missing:\n{}", kept, masked);
+        }
+    }
+
+    #[test]
+    fn description_data_and_environment_are_masked() {
+        let description = "\
+Name:         app-config
+Data
+====
+DATABASE_URL:
+----
+postgres://admin:hunter2@db/app
+
+BinaryData
+====
+
+Events:  <none>
+Containers:
+  app:
+    Environment:
+      API_TOKEN:    tok-123
+      FROM_SECRET:  <set to the key 'token' in secret 'app'>
+    Mounts:
+      /data from data (rw)
+";
+        let masked = mask_description(description);
+        assert!(!masked.contains("hunter2"), "{}", masked);
+        assert!(!masked.contains("tok-123"), "{}", masked);
+        assert!(masked.contains("      API_TOKEN:  [masked]"));
+        assert!(masked.contains("<set to the key 'token' in secret 'app'>"));
+        assert!(masked.contains("Events:  <none>"));
+        assert!(masked.contains("      /data from data (rw)"));
+    }
+}
@@ -0,0 +1,137 @@
+use regex::Regex;
+
+const MIN_WORD_HITS: usize = 2;
+const MIN_SCRIPT_SHARE: f64 = 0.3;
+
+const STOPWORDS: [(&str, &[&str]); 7] = [
+    (
+        "English",
+        &[
+            "the", "and", "is", "are", "what", "how", "this", "that", "with", "for", "to", "of",
+            "in", "do", "does", "can", "you", "it", "my", "why", "i",
+        ],
+    ),
+    (
+        "German",
+        &[
+            "der", "die", "das", "und", "ist", "nicht", "wie", "was", "ich", "mit", "ein", "eine",
+            "für", "warum", "kann", "auf", "zu", "den", "dem", "es", "wird", "bitte",
+        ],
+    ),
+    (
+        "French",
+        &[
+            "le", "la", "les", "et", "est", "pas", "comment", "que", "je", "avec", "un", "une",
+            "pour", "pourquoi", "des", "du", "ce", "dans", "qui", "sur", "peux", "mon",
+        ],
+    ),
+    (
+        "Spanish",
+        &[
+            "el", "los", "las", "y", "es", "cómo", "qué", "con", "una", "para", "por", "del",
+            "porque", "puedo", "esto", "mi", "hay", "está", "pero", "muy",
+        ],
+    ),
+    (
+        "Portuguese",
+        &[
+            "o", "os", "as", "e", "é", "não", "com", "um", "uma", "para", "do", "da", "isso",
+            "porque", "posso", "em", "meu", "está", "mas", "muito", "você",
+        ],
+    ),
+    (
+        "Italian",
+        &[
+            "il", "lo", "gli", "e", "è", "non", "che", "con", "un", "una", "per", "perché", "del",
+            "della", "questo", "posso", "sono", "di", "nel", "mio",
+        ],
+    ),
+    (
+        "Dutch",
+        &[
+            "de", "het", "een", "en", "is", "niet", "hoe", "wat", "ik", "met", "voor", "waarom",
+            "kan", "van", "dit", "op", "te", "zijn", "er", "maar",
+        ],
+    ),
+];
+
+const SCRIPTS: [(&str, char, char); 9] = [
+    ("Japanese", '\u{3040}', '\u{30ff}'),
+    ("Korean", '\u{ac00}', '\u{d7af}'),
+    ("Chinese", '\u{4e00}', '\u{9fff}'),
+    ("Russian", '\u{0400}', '\u{04ff}'),
+    ("Greek", '\u{0370}', '\u{03ff}'),
+    ("Hebrew", '\u{0590}', '\u{05ff}'),
+    ("Arabic", '\u{0600}', '\u{06ff}'),
+    ("Hindi", '\u{0900}', '\u{097f}'),
+    ("Thai", '\u{0e00}', '\u{0e7f}'),
+];
+
+pub fn detect_language(text: &str) -> Option<&'static str> {
+    let prose = strip_code(text);
+    detect_script(&prose).or_else(|| detect_latin(&prose))
+}
+
+fn strip_code(text: &str) -> String {
+    let fenced = Regex::new(r"(?s)```.*?(```|$)").unwrap();
+    let inline = Regex::new(r"`[^`]*`").unwrap();
+    inline
+        .replace_all(&fenced.replace_all(text, " "), " ")
+        .to_string()
+}
+
+fn detect_script(text: &str) -> Option<&'static str> {
+    let letters = text
+        .chars()
+        .filter(|c| c.is_alphabetic())
+        .collect::<Vec<char>>();
+    if letters.is_empty() {
+        return None;
+    }
+    let count = |start: char, end: char| {
+        letters
+            .iter()
+            .filter(|c| (start..=end).contains(*c))
+            .count()
+    };
+    // Japanese mixes kana with Chinese characters, so kana wins over Han
+    let (language, hits) = SCRIPTS
+        .iter()
+        .map(|(language, start, end)| (*language, count(*start, *end)))
+        .filter(|(_, hits)| *hits > 0)
+        .max_by_key(|(language, hits)| (*language == "Japanese", *hits))?;
+    if (hits as f64) < letters.len() as f64 * MIN_SCRIPT_SHARE {
+        return None;
+    }
+    match language {
+        "Russian" if letters.iter().any(|c| "іїєґІЇЄҐ".contains(*c)) => Some("Ukrainian"),
+        language => Some(language),
+    }
+}
+
+fn detect_latin(text: &str) -> Option<&'static str> {
+    let words = text
+        .split_whitespace()
+        .map(|word| {
+            word.trim_matches(|c: char| !c.is_alphabetic())
+                .to_lowercase()
+        })
+        .filter(|word| !word.is_empty() && word.chars().all(char::is_alphabetic))
+        .collect::<Vec<String>>();
+    let scores = STOPWORDS
+        .iter()
+        .map(|(language, stopwords)| {
+            let hits = words
+                .iter()
+                .filter(|word| stopwords.contains(&word.as_str()))
+                .count();
+            (*language, hits)
+        })
+        .collect::<Vec<(&str, usize)>>();
+    let english = scores[0].1;
+    let (language, hits) = scores.into_iter().skip(1).max_by_key(|(_, hits)| *hits)?;
+    if hits < MIN_WORD_HITS || hits <= english {
+        return None;
+    }
+    Some(language)
+}
@@ -0,0 +1,36 @@
+use crate::config::model::keys::ConfigKeys;
+use crate::config::repository::ConfigRepository;
+use crate::config::service::config_service;
+use crate::language::model::detect_language;
+use crate::output::progress::status;
+
+pub fn answer_language<R: ConfigRepository>(
+    repo: &R,
+    requested: Option<&str>,
+    question: &str,
+) -> Option<String> {
+    let setting = requested.map(str::to_string).unwrap_or_else(|| {
+        config_service::fetch_by_key(repo, &ConfigKeys::AnswerLanguage.to_key())
+            .map(|config| config.value)
+            .unwrap_or_else(|_| "auto".to_string())
+    });
+    match setting.as_str() {
+        "off" => None,
+        "auto" => {
+            let language = detect_language(question)?;
+            status(&format!(
+                "answering in {}, change it with --answer-language",
+                language
+            ));
+            Some(language.to_string())
+        }
+        language => Some(language.to_string()),
+    }
+}
+
+pub fn language_instruction(language: &str) -> String {
+    format!(
+        "Answer in {}, unless the user asks for another language. Keep code, commands and identifiers as they are.",
+        language
+    )
+}
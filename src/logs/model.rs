@@ -0,0 +1,76 @@
+use regex::Regex;
+
+const ERROR_MARKERS: [&str; 8] = [
+    "error",
+    "exception",
+    "panic",
+    "fatal",
+    "traceback",
+    "failed",
+    "critical",
+    "segfault",
+];
+
+const MAX_STACK_TRACES: usize = 5;
+const MAX_STACK_TRACE_LINES: usize = 30;
+
+pub fn is_error_line(line: &str) -> bool {
+    let lower = line.to_lowercase();
+    ERROR_MARKERS.iter().any(|marker| lower.contains(marker))
+}
+
+fn is_stack_frame(line: &str) -> bool {
+    let trimmed = line.trim_start();
+    line.starts_with(char::is_whitespace)
+        && (trimmed.starts_with("at ")
+            || trimmed.starts_with("File \"")
+            || trimmed.starts_with("...")
+            || trimmed.chars().next().is_some_and(|c| c.is_ascii_digit()))
+}
+
+pub fn stack_traces(log: &str) -> Vec<String> {
+    let lines = log.lines().collect::<Vec<&str>>();
+    let mut traces: Vec<String> = Vec::new();
+    let mut index = 0;
+    while index < lines.len() {
+        let starts_trace = lines
+            .get(index + 1)
+            .is_some_and(|next| is_stack_frame(next))
+            && (is_error_line(lines[index]) || lines[index].contains("stack backtrace"));
+        if !starts_trace {
+            index += 1;
+            continue;
+        }
+        let mut trace = vec![lines[index]];
+        index += 1;
+        while index < lines.len() && is_stack_frame(lines[index]) {
+            if trace.len() < MAX_STACK_TRACE_LINES {
+                trace.push(lines[index]);
+            }
+            index += 1;
+        }
+        let trace = trace.join("\n");
+        if !traces.contains(&trace) {
+            traces.push(trace);
+        }
+        if traces.len() >= MAX_STACK_TRACES {
+            break;
+        }
+    }
+    traces
+}
+
+pub fn time_range(log: &str) -> Option<(String, String)> {
+    let timestamp = Regex::new(
+        r"\d{4}-\d{2}-\d{2}[T ]\d{2}:\d{2}:\d{2}|[A-Z][a-z]{2} +\d{1,2} \d{2}:\d{2}:\d{2}",
+    )
+    .unwrap();
+    let first = log
+        .lines()
+        .find_map(|line| timestamp.find(line).map(|m| m.as_str().to_string()))?;
+    let last = log
+        .lines()
+        .rev()
+        .find_map(|line| timestamp.find(line).map(|m| m.as_str().to_string()))?;
+    Some((first, last))
+}
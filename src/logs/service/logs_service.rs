@@ -0,0 +1,78 @@
+use crate::config::repository::ConfigRepository;
+use crate::logs::model::{is_error_line, stack_traces, time_range};
+use crate::openai::service::prompt::prompt;
+use crate::output::progress::status;
+use crate::path::sample::sample_lines;
+use crate::usage::repository::UsageRepository;
+use anyhow::{anyhow, Result};
+use std::fs;
+use std::io::{self, Read};
+use std::path::Path;
+
+const LOGS_PROMPT: &str = "
+You analyse application logs for an incident review.
+The log may be sampled, omitted ranges are marked. Answer in plain text with these sections:
+summary: one or two sentences
+timeline: the key events with their timestamps
+errors: the distinct errors and how often they appear
+likely cause: your best explanation, with the evidence
+next steps: what to check or fix first";
+
+pub async fn logs<R: ConfigRepository, UR: UsageRepository>(
+    repo: &R,
+    usage_repo: &UR,
+    source: &str,
+    max_tokens: u32,
+) -> Result<()> {
+    let log = if source == "-" {
+        let mut buffer = String::new();
+        io::stdin().read_to_string(&mut buffer)?;
+        buffer
+    } else {
+        let bytes =
+            fs::read(source).map_err(|err| anyhow!("could not read {}: {:?}", source, err))?;
+        String::from_utf8_lossy(&bytes).to_string()
+    };
+    if log.trim().is_empty() {
+        return Err(anyhow!("the log is empty"));
+    }
+
+    let error_lines = log.lines().filter(|line| is_error_line(line)).count();
+    let traces = stack_traces(&log);
+    let sample = sample_lines(&log, max_tokens, is_error_line);
+    if sample.kept_lines < sample.total_lines {
+        status(&format!(
+            "sampled {} of {} lines (head, tail and error-dense windows)",
+            sample.kept_lines, sample.total_lines
+        ));
+    }
+
+    let mut input = format!(
+        "lines: {}\nlines mentioning errors: {}\n",
+        sample.total_lines, error_lines
+    );
+    if let Some((first, last)) = time_range(&log) {
+        input.push_str(&format!("time range: {} to {}\n", first, last));
+    }
+    if !traces.is_empty() {
+        input.push_str("\nstack traces:\n");
+        for trace in &traces {
+            input.push_str(trace);
+            input.push_str("\n\n");
+        }
+    }
+    input.push_str("\nlog:\n");
+    input.push_str(&sample.text);
+
+    let answer = prompt(
+        repo,
+        usage_repo,
+        "logs",
+        Path::new("."),
+        LOGS_PROMPT,
+        &input,
+    )
+    .await?;
+    println!("{}", answer);
+    Ok(())
+}
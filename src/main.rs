@@ -1,41 +1,165 @@
+mod agent;
 mod args;
+mod bulk;
+mod cache;
+mod changes;
 mod common;
 mod config;
+mod coverage;
+mod crash;
+mod expression;
+mod git;
+mod guard;
+mod help;
+mod hint;
+mod history;
+mod integrations;
+mod language;
+mod logs;
+mod models;
+mod notes;
+mod onboarding;
 mod openai;
 mod output;
+mod patch;
 mod path;
+mod perf;
+mod project;
+mod provider;
+mod rating;
 mod redactions;
 mod repository;
+mod sandbox;
+mod schedule;
 mod session;
+mod snippet;
+mod sql;
+mod summary;
+mod tag;
+mod trust;
+mod upload;
+mod usage;
 
-use crate::args::Args;
+use crate::agent::service::agent_service::{self, AgentInput};
+use crate::args::{Args, Commands, NotesAction, TagAction};
+use crate::bulk::service::bulk_service::{self, BulkInput};
+use crate::cache::repository::CacheRepository;
+use crate::cache::service::cache_service;
+use crate::common::cancel::{is_cancelled, Cancelled, CANCELLED_EXIT_CODE};
 use crate::config::repository::ConfigRepository;
-use crate::config::service::{open_ai_config, redacted_config};
+use crate::config::service::{open_ai_config, output_config, project_config, redacted_config};
+use crate::expression::model::ExpressionKind;
+use crate::expression::service::expression_service;
+use crate::guard::model::wrap_data;
+use crate::guard::service::guard_service::check_injection;
+use crate::guard::service::paste_service;
+use crate::help::examples::{self, with_examples};
+use crate::hint::model::hint::HintEvent;
+use crate::hint::service::hint_service;
+use crate::history::service::history_service;
+use crate::integrations::k8s;
+use crate::language::service::language_service;
+use crate::models::service::models_service;
+use crate::onboarding::{completion, extras, setup};
 use crate::openai::model::role::Role;
+use crate::openai::model::sampling::Sampling;
+use crate::patch::model::diff_blocks;
+use crate::patch::service::iterate_service::{self, IterateInput};
+use crate::patch::service::patch_service;
+use crate::path::budget::{apply_budget, PIPED_INPUT_NAME};
+use crate::path::context_set::context_set_files;
 use crate::path::extract::extract_content;
+use crate::path::focus::{with_focused_file, Focus};
 use crate::path::model::Files;
-use crate::session::model::message::{contains_system_prompt, messages_with_system_prompt};
+use crate::path::reference::{expand_file_references, with_referenced_files};
+use crate::perf::service::perf_service;
+use crate::project::config::load_project_config;
+use crate::project::detect::project_preamble;
+use crate::provider::model::{Endpoint, Provider};
+use crate::provider::service::provider_service;
+use crate::rating::service::tune_service;
+use crate::sandbox::service::sandbox_service;
+use crate::session::model::attachment::Attachment;
+use crate::session::model::message::{
+    contains_system_prompt, messages_with_system_prompt, Message as SessionMessage,
+};
 use crate::session::model::session::Session;
-use crate::session::repository::{MessageRepository, SessionRepository};
+use crate::session::repository::{AttachmentRepository, MessageRepository, SessionRepository};
 use crate::session::service::sessions_service;
 use crate::session::service::sessions_service::session_add_messages;
-use anyhow::Result;
-use clap::Parser;
-use config::{model::keys::ConfigKeys, service::config_service};
-use openai::service::chat::chat;
+use crate::session::service::{digest_service, drift_service, pin_service};
+use crate::snippet::model::code_block::{
+    extract_code_blocks, fence_code_blocks, prose_outside_code_blocks,
+};
+use crate::snippet::service::snippet_service;
+use crate::summary::service::summary_service::summarize_oversized_files;
+use crate::trust::service::trust_service;
+use crate::upload::model::{rejects_uploads, Uploads};
+use crate::upload::repository::UploadRepository;
+use crate::upload::service::upload_service;
+use crate::usage::model::pricing::price_for_model;
+use crate::usage::model::token_usage::{estimate_tokens, TokenUsage};
+use crate::usage::repository::UsageRepository;
+use crate::usage::service::{budget_service, usage_service};
+use anyhow::{anyhow, Result};
+use clap::{CommandFactory, FromArgMatches};
+use config::service::config_service;
+use openai::service::chat::{
+    chat, chat_stream, CODE_ONLY_INSTRUCTION, CODE_ONLY_RETRIES, CODE_ONLY_RETRY,
+};
 use output::message::Message;
 use output::outputter;
+use output::progress::{self, Progress};
+use output::stream::StreamPrinter;
 use repository::db::SqliteRepository;
 use std::fs::create_dir_all;
 use std::io::IsTerminal;
 use std::io::{self, Read};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 #[tokio::main]
 async fn main() -> Result<()> {
-    let args = args::Args::parse();
-    let db_path = db_path();
-    let repo = SqliteRepository::new(db_path.to_str().unwrap())?;
+    match run().await {
+        Err(err) if is_cancelled(&err) => {
+            eprintln!("request cancelled, nothing was written to the session");
+            std::process::exit(CANCELLED_EXIT_CODE);
+        }
+        result => result,
+    }
+}
+
+async fn run() -> Result<()> {
+    let args = args::Args::from_arg_matches(&with_examples(args::Args::command()).get_matches())
+        .unwrap_or_else(|err| err.exit());
+    progress::set_quiet(args.quiet);
+    match &args.command {
+        Some(Commands::Completions { shell }) => {
+            completion::print_completions(*shell);
+            return Ok(());
+        }
+        Some(Commands::Examples { command }) => {
+            return examples::print_examples(command.as_deref())
+        }
+        _ => {}
+    }
+    if !args.read_only {
+        crash::hook::install_panic_hook();
+    }
+    if args.read_only && args.is_write_command() {
+        return Err(anyhow!(
+            "this command writes to disk and is disabled by --read-only"
+        ));
+    }
+    let db_path = db_path(args.read_only);
+    let repo = if args.read_only {
+        SqliteRepository::new_in_memory_copy(db_path.to_str().unwrap())?
+    } else {
+        SqliteRepository::new(db_path.to_str().unwrap())?
+    };
+
+    if let Some(command) = &args.command {
+        return run_command(&repo, &args, command).await;
+    }
 
     if args.is_chat_gpt_api_key() {
         open_ai_config::write_open_ai_key(&repo, &args)?;
@@ -48,13 +172,186 @@ async fn main() -> Result<()> {
     }
 
     if args.is_sessions_all() {
-        sessions_service::fetch_all_sessions(&repo, &repo)?;
+        sessions_service::fetch_all_sessions(&repo, &repo, &repo)?;
         return Ok(());
     }
 
+    if !args.print_config
+        && provider_service::configured_provider(&repo) == Provider::OpenAi
+        && !setup::has_api_key(&repo)
+    {
+        return setup::first_run(&repo, &repo, &repo, args.read_only).await;
+    }
+
+    ask_question(&repo, args).await
+}
+
+async fn run_command(repo: &SqliteRepository, args: &Args, command: &Commands) -> Result<()> {
+    match command {
+        Commands::Snippet { action } => snippet_service::snippet(repo, repo, repo, action),
+        Commands::Config { action } => config_service::config(repo, action),
+        Commands::Session { action } => sessions_service::sessions(repo, action).await,
+        Commands::DiffAnswers {
+            session,
+            first,
+            second,
+        } => sessions_service::diff_answers(repo, repo, repo, session, first, second),
+        Commands::ApplyDiff {
+            session,
+            answer,
+            block,
+            check,
+            directory,
+        } => {
+            let session = sessions_service::existing_session(repo, repo, repo, session)?;
+            patch_service::apply_diff(&session, answer, *block, *check, Path::new(directory))
+        }
+        Commands::Iterate {
+            task,
+            run,
+            max_iterations,
+            max_tokens,
+            max_minutes,
+            confirm_every,
+            report,
+            directory,
+        } => {
+            let input = IterateInput {
+                task,
+                run,
+                max_iterations: *max_iterations,
+                max_tokens: *max_tokens,
+                max_minutes: *max_minutes,
+                confirm_every: *confirm_every,
+                report: report.as_deref(),
+                directory: Path::new(directory),
+            };
+            trust_service::require_trust(repo, input.directory, args.trust, "running commands")?;
+            iterate_service::iterate(repo, repo, repo, &input).await
+        }
+        Commands::Agent {
+            task,
+            session,
+            max_steps,
+            max_tokens,
+            max_minutes,
+            directory,
+        } => {
+            let input = AgentInput {
+                task,
+                session: session.as_deref(),
+                max_steps: *max_steps,
+                max_tokens: *max_tokens,
+                max_minutes: *max_minutes,
+                directory: Path::new(directory),
+            };
+            trust_service::require_trust(repo, input.directory, args.trust, "running commands")?;
+            agent_service::run_agent(repo, repo, repo, repo, repo, repo, &input).await
+        }
+        Commands::Bulk {
+            task,
+            files,
+            jobs,
+            restart,
+            report,
+        } => {
+            let input = BulkInput {
+                task,
+                files,
+                jobs: *jobs,
+                restart: *restart,
+                report: report.as_deref(),
+            };
+            trust_service::require_trust(repo, Path::new("."), args.trust, "bulk changes")?;
+            bulk_service::bulk(repo, repo, &input).await
+        }
+        Commands::Changes { action } => changes::service::changes_service::changes(action),
+        Commands::Undo { steps, force } => changes::service::changes_service::undo(*steps, *force),
+        Commands::Notes { action } => {
+            if matches!(action, NotesAction::InstallHook) {
+                trust_service::require_trust(
+                    repo,
+                    Path::new("."),
+                    args.trust,
+                    "installing git hooks",
+                )?;
+            }
+            notes::service::notes_service::notes(repo, repo, action).await
+        }
+        Commands::CoverageGaps {
+            report,
+            top,
+            generate,
+        } => {
+            coverage::service::coverage_service::coverage_gaps(repo, repo, report, *top, *generate)
+                .await
+        }
+        Commands::Tag {
+            action: TagAction::Suggest { create },
+        } => tag::service::tag_service::suggest(repo, repo, *create).await,
+        Commands::Perf { action } => perf_service::perf(repo, repo, action).await,
+        Commands::Sql {
+            db,
+            question,
+            execute,
+            limit,
+        } => sql::service::sql_service::sql(repo, repo, db, question, *execute, *limit).await,
+        Commands::K8s { action } => k8s::k8s(repo, repo, action).await,
+        Commands::Logs { source, max_tokens } => {
+            logs::service::logs_service::logs(repo, repo, source, *max_tokens).await
+        }
+        Commands::Regex {
+            description,
+            test_file,
+        } => {
+            expression_service::build_expression(
+                repo,
+                repo,
+                ExpressionKind::Regex,
+                description,
+                test_file,
+                None,
+            )
+            .await
+        }
+        Commands::Jq {
+            description,
+            test_file,
+            expect,
+        } => {
+            expression_service::build_expression(
+                repo,
+                repo,
+                ExpressionKind::Jq,
+                description,
+                test_file,
+                expect.as_deref(),
+            )
+            .await
+        }
+        Commands::Schedule { action } => schedule::service::schedule_service::schedule(action),
+        Commands::ReportBug => crash::report::report_bug(),
+        // answered before the database is opened
+        Commands::Completions { .. } | Commands::Examples { .. } => Ok(()),
+        Commands::Setup => setup::setup(repo, repo, repo).await,
+        Commands::InstallExtras { shell } => extras::install_extras(*shell),
+        Commands::Cache { action } => cache_service::cache(repo, action),
+        Commands::Uploads { action } => upload_service::uploads(repo, repo, action).await,
+        Commands::Hints { action } => hint_service::set_hints(repo, repo, action),
+        Commands::History { action, limit } => {
+            history_service::history(repo, repo, action.as_ref(), *limit, args.read_only)
+        }
+        Commands::Trust { action } => trust_service::trust(repo, action),
+        Commands::Audit { limit } => sandbox_service::list_audit(repo, *limit),
+        Commands::Tune { limit } => tune_service::tune(repo, repo, repo, repo, *limit).await,
+        Commands::Models { action } => models_service::models(repo, repo, action).await,
+    }
+}
+
+async fn ask_question(repo: &SqliteRepository, args: Args) -> Result<()> {
     let mut session = if args.is_session() {
         if let Some(name) = &args.session {
-            sessions_service::session(&repo, &repo, name)?
+            sessions_service::session(repo, repo, repo, name)?
         } else {
             Session::new_temporary()
         }
@@ -63,29 +360,133 @@ async fn main() -> Result<()> {
     };
 
     if args.print_config {
-        return print_config(&repo);
-    }
-
-    let local_context = extract_content(&args.directory, &args.exclude);
-    let input = extract_input_or_quit(&args);
-    request_response_from_ai(
-        &repo,
-        &repo,
-        &repo,
-        &input,
-        &mut session,
-        args.system_prompt,
-        &local_context,
+        return print_config(repo);
+    }
+
+    let model = match pin_service::pinned_model(repo, repo, repo, &session, args.model.as_deref())?
+    {
+        Some(model) => model,
+        None => models_service::resolve_model(repo, repo, args.model.as_deref())?,
+    };
+
+    let project_dir = Path::new(args.directory.as_deref().unwrap_or("."));
+    let project_preamble = if project_config::is_project_context_enabled(repo) {
+        project_preamble(project_dir)
+    } else {
+        None
+    };
+    let project_config = load_project_config(project_dir);
+    let sampling = args.sampling().or(project_config.sampling_for("ask"));
+    sampling.validate().map_err(|err| anyhow!(err))?;
+    let mut local_context = extract_content(&args.directory, &args.exclude);
+    if let Some(name) = &args.context_set {
+        // the files of a set are chosen by .termai.toml, not by this invocation
+        trust_service::require_trust(repo, project_dir, args.trust, "reading context sets")?;
+        let files = context_set_files(
+            Path::new(args.directory.as_deref().unwrap_or(".")),
+            &project_config,
+            name,
+            &args.exclude,
+        )?;
+        local_context.get_or_insert_with(Vec::new).extend(files);
+    }
+    let (mut data, referenced_files) = expand_file_references(args.data.as_deref().unwrap_or(""));
+    let mut context_files =
+        with_referenced_files(local_context, referenced_files).unwrap_or_default();
+    if let Some(region) = &args.from_editor {
+        let focus = Focus::parse(region)?;
+        let focused = focus.read()?;
+        data = format!("{}\n\n{}", focus.excerpt(&focused.content), data)
+            .trim_end()
+            .to_string();
+        context_files = with_focused_file(context_files, focused);
+    }
+    if !session.temporary {
+        let refreshed = drift_service::refresh_context(&session, &context_files)?;
+        context_files.extend(refreshed);
+        digest_service::resume_with_digest(repo, &mut session);
+    }
+    let mut hint_events = Vec::new();
+    if !args.no_summarize {
+        let threshold = project_config.context.summarize_above_tokens;
+        if context_files
+            .iter()
+            .any(|file| !file.is_binary() && estimate_tokens(&file.content) > threshold)
+        {
+            hint_events.push(HintEvent::ContextSummarized);
+        }
+        context_files =
+            summarize_oversized_files(repo, repo, repo, project_dir, context_files, threshold)
+                .await?;
+    }
+    let piped_input = read_piped_input();
+    let piped = piped_input.is_some();
+    let piped_input = paste_service::check_piped_input(
+        repo,
+        repo,
+        project_dir,
+        piped_input,
+        &model,
+        args.send_large,
     )
-    .await
+    .await?;
+    let (budgeted_files, piped_input) =
+        apply_budget(&project_config.context, context_files, piped_input);
+    let local_context = if budgeted_files.is_empty() {
+        None
+    } else {
+        Some(budgeted_files)
+    };
+    let input = extract_input_or_quit(&data, piped_input);
+    if args.session.is_none() {
+        hint_events.push(HintEvent::QuestionWithoutSession);
+    }
+    if local_context
+        .iter()
+        .flatten()
+        .any(|file| file.path == PIPED_INPUT_NAME)
+    {
+        hint_events.push(HintEvent::PipedDiff);
+    }
+    history_service::record_prompt(repo, &args, piped);
+    let options = RequestOptions {
+        model,
+        sampling,
+        code_only: args.code_only,
+        stream: !args.no_stream && !args.code_only && io::stdout().is_terminal(),
+        use_cache: !args.no_cache,
+        ignore_budget: args.ignore_budget,
+        answer_language: language_service::answer_language(
+            repo,
+            args.answer_language.as_deref(),
+            args.data.as_deref().unwrap_or(""),
+        ),
+        system_prompt: args
+            .system_prompt
+            .or_else(|| open_ai_config::system_prompt(repo)),
+        project_preamble,
+    };
+    request_response_from_ai(repo, &input, &mut session, &local_context, &options).await?;
+
+    if let Some(answer) = session.messages.last() {
+        if !diff_blocks(&answer.content).is_empty() {
+            hint_events.push(HintEvent::AnswerWithDiff);
+        }
+        if !extract_code_blocks(&answer.content).is_empty() {
+            hint_events.push(HintEvent::AnswerWithCode);
+        }
+    }
+    hint_service::record_events(repo, repo, &hint_events);
+    Ok(())
 }
 
-fn db_path() -> PathBuf {
+fn db_path(read_only: bool) -> PathBuf {
     let home_dir = dirs::home_dir().expect("Failed to get home directory");
     let default_dir = home_dir.join(".config/termai");
-    create_dir_all(&default_dir).expect("Failed to create default directory");
-    let db_path = default_dir.join("app.db");
-    db_path
+    if !read_only {
+        create_dir_all(&default_dir).expect("Failed to create default directory");
+    }
+    default_dir.join("app.db")
 }
 
 fn print_config<R: ConfigRepository>(repo: &R) -> Result<()> {
@@ -103,53 +504,127 @@ fn print_config<R: ConfigRepository>(repo: &R) -> Result<()> {
     }
 }
 
-async fn request_response_from_ai<
-    R: ConfigRepository,
-    SR: SessionRepository,
-    MR: MessageRepository,
->(
+// How a question is asked: the model and its settings, and what is added to the prompt.
+struct RequestOptions {
+    model: String,
+    sampling: Sampling,
+    code_only: bool,
+    stream: bool,
+    use_cache: bool,
+    ignore_budget: bool,
+    answer_language: Option<String>,
+    system_prompt: Option<String>,
+    project_preamble: Option<String>,
+}
+
+async fn request_response_from_ai<R>(
     repo: &R,
-    session_repository: &SR,
-    message_repository: &MR,
-    input: &String,
+    input: &str,
     session: &mut Session,
-    user_defined_system_prompt: Option<String>,
     local_context: &Option<Vec<Files>>,
-) -> Result<()> {
-    let open_ai_api_key = config_service::fetch_by_key(repo, &ConfigKeys::ChatGptApiKey.to_key())?;
+    options: &RequestOptions,
+) -> Result<()>
+where
+    R: ConfigRepository
+        + SessionRepository
+        + MessageRepository
+        + AttachmentRepository
+        + UsageRepository
+        + CacheRepository
+        + UploadRepository,
+{
+    let endpoint = provider_service::endpoint(repo)?;
+    let model = options.model.as_str();
+    let code_only = options.code_only;
 
     let contains_system_prompt = contains_system_prompt(&session.messages);
     if !contains_system_prompt {
-        session.messages =
-            messages_with_system_prompt(user_defined_system_prompt, &session.messages);
+        session.messages = messages_with_system_prompt(
+            options.system_prompt.clone(),
+            options.project_preamble.clone(),
+            &session.messages,
+        );
     }
 
-    let input_with_local_context = match local_context {
-        Some(files) => {
-            let local_context: Vec<String> = files
-                .iter()
-                .map(|file| {
-                    let file_path = file.path.clone();
-                    let file_content = file.content.clone();
-                    format!("{}\n```\n{}```", file_path, file_content)
-                })
-                .collect();
-            format!("{}\n{}", input, local_context.join("\n"))
-        }
-        None => input.clone(),
-    };
-
-    session.add_raw_message(input_with_local_context, Role::User);
-    session.redact(repo);
-
-    if let Err(err) = chat(&open_ai_api_key.value, session).await {
-        println!("{:#?}", err);
-        return Err(err);
+    let attachments = local_context
+        .iter()
+        .flatten()
+        .map(Attachment::from)
+        .collect::<Vec<Attachment>>();
+    let mut instructions = Vec::new();
+    if code_only {
+        instructions.push(CODE_ONLY_INSTRUCTION.to_string());
+    }
+    if let Some(language) = &options.answer_language {
+        instructions.push(language_service::language_instruction(language));
+    }
+    for instruction in &instructions {
+        session.add_raw_message(instruction.clone(), Role::System);
+    }
+    session.add_message_with_attachments(input.to_string(), Role::User, attachments);
+    check_injection(repo, session)?;
+    let cache_key = (options.use_cache && session.temporary && cache_service::is_enabled(repo))
+        .then(|| cache_service::cache_key(&endpoint, model, &options.sampling, &session.messages));
+    let cached = cache_key
+        .as_ref()
+        .and_then(|key| cache_service::lookup(repo, repo, key));
+    let stream = options.stream && cached.is_none();
+    match &cached {
+        Some(cached) => {
+            progress::status("reusing a cached answer, pass --no-cache to ask again");
+            session.messages.push(SessionMessage {
+                id: "".to_string(),
+                role: Role::Assistant,
+                content: cached.answer.clone(),
+                attachments: vec![],
+                redaction_mapping: None,
+                model: Some(cached.model.clone()),
+            });
+        }
+        None => send_to_model(repo, &endpoint, session, options).await?,
+    }
+    session.messages.retain(|message| {
+        !(message.id.is_empty()
+            && message.role == Role::System
+            && instructions.contains(&message.content))
+    });
+    if code_only {
+        let answer = session
+            .messages
+            .last_mut()
+            .filter(|message| message.role == Role::Assistant)
+            .ok_or_else(|| anyhow!("no answer received"))?;
+        let blocks = extract_code_blocks(&answer.content);
+        if blocks.is_empty() {
+            return Err(anyhow!("the answer has no code block"));
+        }
+        if !prose_outside_code_blocks(&answer.content).is_empty() {
+            progress::status("removed the text around the code from the answer");
+            answer.content = fence_code_blocks(&blocks);
+        }
+    }
+    if let (Some(key), None) = (cache_key, &cached) {
+        if let Some(answer) = session
+            .messages
+            .last()
+            .filter(|message| message.role == Role::Assistant)
+        {
+            cache_service::store(repo, key, endpoint.provider, model, &answer.content);
+        }
     }
+    session_add_messages(repo, repo, repo, session).expect("could not write new messages to repo");
 
-    session.unredact();
-    session_add_messages(session_repository, message_repository, session)
-        .expect("could not write new messages to repo");
+    if code_only {
+        if let Some(answer) = session.messages.last() {
+            for block in extract_code_blocks(&answer.content) {
+                print!("{}", block.content);
+            }
+        }
+        return Ok(());
+    }
+    if stream {
+        return Ok(());
+    }
 
     let output_messages = session
         .messages
@@ -158,25 +633,187 @@ async fn request_response_from_ai<
         .map(|message| message.to_output_message())
         .collect::<Vec<Message>>();
 
-    outputter::print(output_messages);
+    outputter::print(
+        output_messages,
+        &session.context_paths(),
+        &output_config::output_options(repo, &session.name),
+    );
     Ok(())
 }
 
-fn extract_input_or_quit(args: &Args) -> String {
-    let mut input = String::new();
-    if let Some(ref data_arg) = args.data {
-        input.push_str(data_arg);
+async fn send_to_model<R: ConfigRepository + UsageRepository + UploadRepository>(
+    repo: &R,
+    endpoint: &Endpoint,
+    session: &mut Session,
+    options: &RequestOptions,
+) -> Result<()> {
+    let model = options.model.as_str();
+    let sampling = &options.sampling;
+    if options.stream {
+        let output_messages = session
+            .messages
+            .iter()
+            .filter(|message| message.role != Role::System)
+            .map(|message| message.to_output_message())
+            .collect::<Vec<Message>>();
+        outputter::print(
+            output_messages,
+            &session.context_paths(),
+            &output_config::output_options(repo, &session.name),
+        );
     }
-    if !io::stdin().is_terminal() {
-        let mut buffer = String::new();
-        io::stdin()
-            .read_to_string(&mut buffer)
-            .expect("Failed to read from stdin");
+    session.redact(repo);
+
+    let estimated_prompt_tokens = session
+        .messages
+        .iter()
+        .map(|message| estimate_tokens(&message.content_with_context()))
+        .sum();
+    let estimated_cost = price_for_model(model).cost(estimated_prompt_tokens, 0);
+    budget_service::check_budget(
+        repo,
+        repo,
+        &session.id,
+        estimated_cost,
+        options.ignore_budget,
+    )?;
+    let uploads = upload_service::upload_large_attachments(repo, repo, endpoint, session).await;
+
+    let mut printer = options.stream.then(|| {
+        outputter::print_header(&Role::Assistant, Some(model));
+        StreamPrinter::new(session.redaction_mappings())
+    });
+    let usage = chat_or_cancel(
+        endpoint,
+        model,
+        sampling,
+        session,
+        &uploads,
+        printer.as_mut(),
+    )
+    .await;
+    if let Some(printer) = printer.as_mut() {
+        printer.finish();
+        println!();
+    }
+    let usage = usage?;
+    if let Some(usage) = usage {
+        usage_service::record_usage(repo, &session.id, &usage)?;
+    }
+    let mut retries = 0;
+    while options.code_only && retries < CODE_ONLY_RETRIES {
+        let rejected = session.messages.len() - 1;
+        let answer = &session.messages[rejected];
+        if answer.role != Role::Assistant || prose_outside_code_blocks(&answer.content).is_empty() {
+            break;
+        }
+        retries += 1;
+        progress::status("the answer has text around the code, asking for code only");
+        session.add_raw_message(CODE_ONLY_RETRY.to_string(), Role::User);
+        let usage = chat_or_cancel(endpoint, model, sampling, session, &uploads, None).await?;
+        if let Some(usage) = usage {
+            usage_service::record_usage(repo, &session.id, &usage)?;
+        }
+        session.messages.drain(rejected..rejected + 2);
+    }
+
+    session.unredact();
+    Ok(())
+}
+
+async fn chat_or_cancel(
+    endpoint: &Endpoint,
+    model: &str,
+    sampling: &Sampling,
+    session: &mut Session,
+    uploads: &Uploads,
+    mut printer: Option<&mut StreamPrinter>,
+) -> Result<Option<TokenUsage>> {
+    let waiting = Progress::spinner("waiting for the answer");
+    let request = async {
+        let result = ask(
+            endpoint,
+            model,
+            sampling,
+            session,
+            uploads,
+            printer.as_deref_mut(),
+            &waiting,
+        )
+        .await;
+        match result {
+            Err(err) if !uploads.is_empty() && rejects_uploads(&err) => {
+                progress::status(&format!(
+                    "the uploaded files were not accepted, sending them inline: {}",
+                    err
+                ));
+                ask(
+                    endpoint,
+                    model,
+                    sampling,
+                    session,
+                    &Uploads::new(),
+                    printer,
+                    &waiting,
+                )
+                .await
+            }
+            result => result,
+        }
+    };
+    tokio::select! {
+        result = request => {
+            waiting.finish();
+            result
+        },
+        _ = tokio::signal::ctrl_c() => {
+            waiting.finish();
+            Err(Cancelled.into())
+        }
+    }
+}
+
+async fn ask(
+    endpoint: &Endpoint,
+    model: &str,
+    sampling: &Sampling,
+    session: &mut Session,
+    uploads: &Uploads,
+    printer: Option<&mut StreamPrinter>,
+    waiting: &Progress,
+) -> Result<Option<TokenUsage>> {
+    match printer {
+        Some(printer) => {
+            let mut on_delta = |delta: &str| {
+                waiting.finish();
+                printer.push(delta);
+            };
+            chat_stream(endpoint, model, sampling, session, uploads, &mut on_delta).await
+        }
+        None => chat(endpoint, model, sampling, session, uploads).await,
+    }
+}
+
+fn read_piped_input() -> Option<String> {
+    if io::stdin().is_terminal() {
+        return None;
+    }
+    let mut buffer = String::new();
+    io::stdin()
+        .read_to_string(&mut buffer)
+        .expect("Failed to read from stdin");
+    // `termai < /dev/null`, cron and CI give an empty stdin, that is no input
+    Some(buffer.trim().to_string()).filter(|piped| !piped.is_empty())
+}
+
+fn extract_input_or_quit(data: &str, piped_input: Option<String>) -> String {
+    let mut input = data.to_string();
+    if let Some(piped_input) = piped_input.filter(|piped| !piped.trim().is_empty()) {
         if !input.is_empty() {
             input.push('\n');
             input.push('\n');
         }
-        input.push_str(buffer.trim());
+        input.push_str(&wrap_data(PIPED_INPUT_NAME, &piped_input));
     }
     if input.is_empty() {
         eprintln!("No input provided. Use positional arguments or pipe data.");
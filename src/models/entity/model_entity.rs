@@ -0,0 +1,12 @@
+use chrono::NaiveDateTime;
+
+pub struct ModelEntity {
+    pub id: String,
+    pub fetched_at: NaiveDateTime,
+}
+
+impl ModelEntity {
+    pub fn new(id: String, fetched_at: NaiveDateTime) -> Self {
+        Self { id, fetched_at }
+    }
+}
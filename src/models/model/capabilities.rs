@@ -0,0 +1,52 @@
+pub struct Capabilities {
+    pub context_window: u32,
+    pub modality: &'static str,
+}
+
+impl Capabilities {
+    const fn new(context_window: u32, modality: &'static str) -> Self {
+        Self {
+            context_window,
+            modality,
+        }
+    }
+}
+
+const CAPABILITIES: [(&str, Capabilities); 15] = [
+    (
+        "gpt-4o-mini-realtime",
+        Capabilities::new(128_000, "text+audio"),
+    ),
+    (
+        "gpt-4o-mini-audio",
+        Capabilities::new(128_000, "text+audio"),
+    ),
+    ("gpt-4o-mini", Capabilities::new(128_000, "text+image")),
+    ("gpt-4o-realtime", Capabilities::new(128_000, "text+audio")),
+    ("gpt-4o-audio", Capabilities::new(128_000, "text+audio")),
+    ("gpt-4o", Capabilities::new(128_000, "text+image")),
+    ("gpt-4-turbo", Capabilities::new(128_000, "text+image")),
+    ("gpt-4-0125-preview", Capabilities::new(128_000, "text")),
+    ("gpt-4-1106-preview", Capabilities::new(128_000, "text")),
+    ("gpt-4", Capabilities::new(8_192, "text")),
+    ("gpt-3.5-turbo", Capabilities::new(16_385, "text")),
+    ("o1-mini", Capabilities::new(128_000, "text")),
+    ("o1-preview", Capabilities::new(128_000, "text")),
+    ("o1", Capabilities::new(200_000, "text+image")),
+    ("o3-mini", Capabilities::new(200_000, "text")),
+];
+
+pub fn capabilities_for(model: &str) -> Option<&'static Capabilities> {
+    CAPABILITIES
+        .iter()
+        .find(|(prefix, _)| model.starts_with(prefix))
+        .map(|(_, capabilities)| capabilities)
+}
+
+pub fn accepts_sampling(model: &str) -> bool {
+    !["o1", "o3"].iter().any(|prefix| model.starts_with(prefix))
+}
+
+pub fn is_chat_model(model: &str) -> bool {
+    capabilities_for(model).is_some() && !model.contains("instruct")
+}
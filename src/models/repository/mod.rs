@@ -0,0 +1,14 @@
+use super::entity::model_entity::ModelEntity;
+use std::fmt::Debug;
+
+pub(crate) mod model_repository;
+
+pub trait ModelRepository
+where
+    Self::Error: Debug,
+{
+    type Error;
+
+    fn fetch_models(&self, provider: &str) -> Result<Vec<ModelEntity>, Self::Error>;
+    fn replace_models(&self, provider: &str, models: &[ModelEntity]) -> Result<(), Self::Error>;
+}
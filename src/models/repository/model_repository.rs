@@ -0,0 +1,44 @@
+use super::ModelRepository;
+use crate::models::entity::model_entity::ModelEntity;
+use crate::repository::db::SqliteRepository;
+use chrono::NaiveDateTime;
+use rusqlite::{params, Result};
+
+const DATE_TIME_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+
+impl ModelRepository for SqliteRepository {
+    type Error = rusqlite::Error;
+
+    fn fetch_models(&self, provider: &str) -> Result<Vec<ModelEntity>, Self::Error> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT id, fetched_at FROM models WHERE provider = ?1 ORDER BY id")?;
+        let rows = stmt.query_map(params![provider], |row| {
+            let id: String = row.get(0)?;
+            let fetched_at_str: String = row.get(1)?;
+            let fetched_at = NaiveDateTime::parse_from_str(&fetched_at_str, DATE_TIME_FORMAT)
+                .expect("Invalid DateTime format");
+
+            Ok(ModelEntity::new(id, fetched_at))
+        })?;
+
+        let mut models = Vec::new();
+        for model in rows {
+            models.push(model?);
+        }
+        Ok(models)
+    }
+
+    fn replace_models(&self, provider: &str, models: &[ModelEntity]) -> Result<(), Self::Error> {
+        self.conn
+            .execute("DELETE FROM models WHERE provider = ?1", params![provider])?;
+        for model in models {
+            let fetched_at_str = model.fetched_at.format(DATE_TIME_FORMAT).to_string();
+            self.conn.execute(
+                "INSERT INTO models (id, provider, fetched_at) VALUES (?1, ?2, ?3)",
+                params![model.id, provider, fetched_at_str],
+            )?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1 @@
+pub(crate) mod models_service;
@@ -0,0 +1,193 @@
+use crate::args::ModelsAction;
+use crate::config::model::keys::ConfigKeys;
+use crate::config::repository::ConfigRepository;
+use crate::config::service::config_service;
+use crate::models::entity::model_entity::ModelEntity;
+use crate::models::model::capabilities::{capabilities_for, is_chat_model};
+use crate::models::repository::ModelRepository;
+use crate::openai::adapter::open_ai_adapter;
+use crate::openai::service::chat::MODEL;
+use crate::output::progress::{status, Progress};
+use crate::provider::model::{Provider, OLLAMA_MODEL};
+use crate::provider::service::provider_service::{configured_provider, endpoint};
+use crate::usage::model::pricing::price_for_model;
+use anyhow::{anyhow, Result};
+use chrono::{Duration, Utc};
+use colored::*;
+
+const MODELS_CACHE_HOURS: i64 = 24;
+
+pub fn configured_model<R: ConfigRepository>(repo: &R) -> String {
+    let (key, default) = match configured_provider(repo) {
+        Provider::OpenAi => (ConfigKeys::Model, MODEL.to_string()),
+        Provider::Ollama => (ConfigKeys::OllamaModel, OLLAMA_MODEL.to_string()),
+    };
+    match config_service::fetch_by_key(repo, &key.to_key()) {
+        Ok(config) => config.value,
+        Err(_) => default,
+    }
+}
+
+pub fn resolve_model<R: ConfigRepository, MR: ModelRepository>(
+    repo: &R,
+    model_repo: &MR,
+    requested: Option<&str>,
+) -> Result<String> {
+    let model = requested
+        .map(str::to_string)
+        .unwrap_or_else(|| configured_model(repo));
+    let provider = configured_provider(repo);
+    let cached = model_repo
+        .fetch_models(provider.to_key())
+        .unwrap_or_default();
+    let known = if cached.is_empty() {
+        provider == Provider::Ollama || capabilities_for(&model).is_some()
+    } else {
+        cached.iter().any(|cached| cached.id == model)
+    };
+    if !known {
+        return Err(anyhow!(
+            "unknown model '{}', run `termai models list` to see the available ones",
+            model
+        ));
+    }
+    Ok(model)
+}
+
+pub fn is_model_offered<R: ConfigRepository, MR: ModelRepository>(
+    repo: &R,
+    model_repo: &MR,
+    model: &str,
+) -> Option<bool> {
+    let provider = configured_provider(repo);
+    let cached = model_repo
+        .fetch_models(provider.to_key())
+        .unwrap_or_default();
+    if cached.is_empty() {
+        return None;
+    }
+    Some(cached.iter().any(|cached| cached.id == model))
+}
+
+pub async fn available_models<R: ConfigRepository, MR: ModelRepository>(
+    repo: &R,
+    model_repo: &MR,
+    refresh: bool,
+) -> Result<Vec<String>> {
+    let endpoint = endpoint(repo)?;
+    let cached = model_repo
+        .fetch_models(endpoint.provider.to_key())
+        .map_err(|err| anyhow!("could not read the cached models: {:?}", err))?;
+    let expires = Utc::now().naive_utc() - Duration::hours(MODELS_CACHE_HOURS);
+    if !refresh && !cached.is_empty() && cached.iter().all(|model| model.fetched_at > expires) {
+        return Ok(cached.into_iter().map(|model| model.id).collect());
+    }
+
+    let fetching = Progress::spinner("fetching the available models");
+    let response = open_ai_adapter::list_models(&endpoint).await;
+    fetching.finish();
+    let mut ids = response
+        .map_err(|err| {
+            anyhow!(
+                "could not fetch the models from {}: {}",
+                endpoint.base_url,
+                err
+            )
+        })?
+        .data
+        .into_iter()
+        .map(|model| model.id)
+        .collect::<Vec<String>>();
+    ids.sort();
+
+    let fetched_at = Utc::now().naive_utc();
+    let entities = ids
+        .iter()
+        .map(|id| ModelEntity::new(id.clone(), fetched_at))
+        .collect::<Vec<ModelEntity>>();
+    if let Err(err) = model_repo.replace_models(endpoint.provider.to_key(), &entities) {
+        status(&format!("could not cache the models: {:?}", err));
+    }
+    Ok(ids)
+}
+
+pub async fn models<R: ConfigRepository, MR: ModelRepository>(
+    repo: &R,
+    model_repo: &MR,
+    action: &ModelsAction,
+) -> Result<()> {
+    match action {
+        ModelsAction::List { refresh, all } => list_models(repo, model_repo, *refresh, *all).await,
+    }
+}
+
+pub async fn list_models<R: ConfigRepository, MR: ModelRepository>(
+    repo: &R,
+    model_repo: &MR,
+    refresh: bool,
+    all: bool,
+) -> Result<()> {
+    let current = configured_model(repo);
+    let models = available_models(repo, model_repo, refresh)
+        .await?
+        .into_iter()
+        .filter(|model| {
+            all || configured_provider(repo) == Provider::Ollama || is_chat_model(model)
+        })
+        .collect::<Vec<String>>();
+    if models.is_empty() {
+        println!("no chat models available, use --all to see every model");
+        return Ok(());
+    }
+
+    let width = models.iter().map(|model| model.len()).max().unwrap_or(0);
+    println!(
+        "  {:<width$}  {:>8}  {:<10}  {:>14}",
+        "MODEL",
+        "CONTEXT",
+        "MODALITY",
+        "$/1M IN/OUT",
+        width = width
+    );
+    for model in &models {
+        println!("{}", model_line(model, model == &current, width));
+    }
+    status(&format!(
+        "* current model, the list is cached for {}h, `--refresh` fetches it again",
+        MODELS_CACHE_HOURS
+    ));
+    Ok(())
+}
+
+pub fn model_line(model: &str, current: bool, width: usize) -> String {
+    let (context, modality) = match capabilities_for(model) {
+        Some(capabilities) => (
+            format!("{}k", capabilities.context_window / 1000),
+            capabilities.modality,
+        ),
+        None => ("-".to_string(), "-"),
+    };
+    let price = price_for_model(model);
+    let price = if price.input_per_million == 0.0 && price.output_per_million == 0.0 {
+        "-".to_string()
+    } else {
+        format!(
+            "{:.2}/{:.2}",
+            price.input_per_million, price.output_per_million
+        )
+    };
+    let line = format!(
+        "{} {:<width$}  {:>8}  {:<10}  {:>14}",
+        if current { "*" } else { " " },
+        model,
+        context,
+        modality,
+        price,
+        width = width
+    );
+    if current {
+        line.green().to_string()
+    } else {
+        line
+    }
+}
@@ -0,0 +1,101 @@
+use crate::args::NotesAction;
+use crate::config::repository::ConfigRepository;
+use crate::git::diff::{commit_diff, DiffOptions};
+use crate::git::prompt::layered_prompt;
+use crate::git::repository::{commit_message, hooks_dir, repo_root, run_git, submodules};
+use crate::openai::service::prompt::prompt;
+use crate::output::progress::status;
+use crate::project::config::load_project_config;
+use crate::usage::repository::UsageRepository;
+use anyhow::{anyhow, Result};
+use std::fs;
+use std::path::Path;
+
+const NOTES_REF: &str = "refs/notes/termai";
+
+const NOTES_PROMPT: &str = "
+You write extended descriptions of git commits for other developers.
+Explain what the change does, why it was likely made and anything a reviewer should double check.
+Use plain text, no markdown, and keep lines under 80 characters.";
+
+const POST_COMMIT_HOOK: &str = "#!/bin/sh
+# added by termai: store an extended description of each commit in git notes
+termai notes generate HEAD >/dev/null 2>&1 &
+";
+
+pub async fn notes<R: ConfigRepository, UR: UsageRepository>(
+    repo: &R,
+    usage_repo: &UR,
+    action: &NotesAction,
+) -> Result<()> {
+    let dir = repo_root(Path::new("."))?;
+    match action {
+        NotesAction::Generate {
+            sha,
+            include_submodules,
+            include_generated,
+        } => {
+            let options = DiffOptions {
+                include_submodules: *include_submodules,
+                include_generated: *include_generated,
+            };
+            generate_note(repo, usage_repo, &dir, sha, &options).await
+        }
+        NotesAction::Show { sha } => show_note(&dir, sha),
+        NotesAction::InstallHook => install_hook(&dir),
+    }
+}
+
+async fn generate_note<R: ConfigRepository, UR: UsageRepository>(
+    repo: &R,
+    usage_repo: &UR,
+    dir: &Path,
+    sha: &str,
+    options: &DiffOptions,
+) -> Result<()> {
+    let message = commit_message(dir, sha)?;
+    let diff = commit_diff(dir, sha, options)?;
+    if !options.include_submodules && !submodules(dir)?.is_empty() {
+        status("submodule changes are shown as commit ranges, pass --include-submodules to include their diffs");
+    }
+    let input = format!("commit message:\n{}\n\ndiff:\n{}", message.trim(), diff);
+    let project = load_project_config(dir);
+    let system_prompt = layered_prompt(NOTES_PROMPT, &project.prompts.notes, dir);
+    let note = prompt(repo, usage_repo, "notes", dir, &system_prompt, &input).await?;
+
+    run_git(
+        dir,
+        &[
+            "notes", "--ref", NOTES_REF, "add", "--force", "-m", &note, sha,
+        ],
+    )?;
+    println!("{}", note);
+    Ok(())
+}
+
+fn show_note(dir: &Path, sha: &str) -> Result<()> {
+    let note = run_git(dir, &["notes", "--ref", NOTES_REF, "show", sha])
+        .map_err(|_| anyhow!("no termai note for {}", sha))?;
+    print!("{}", note);
+    Ok(())
+}
+
+pub fn install_hook(dir: &Path) -> Result<()> {
+    let hooks_dir = hooks_dir(dir)?;
+    let hook_path = hooks_dir.join("post-commit");
+    if hook_path.exists() {
+        return Err(anyhow!(
+            "{} already exists, add `termai notes generate HEAD` to it manually",
+            hook_path.display()
+        ));
+    }
+    fs::create_dir_all(&hooks_dir)?;
+    fs::write(&hook_path, POST_COMMIT_HOOK)?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(&hook_path, fs::Permissions::from_mode(0o755))?;
+    }
+    println!("installed {}", hook_path.display());
+    Ok(())
+}
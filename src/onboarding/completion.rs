@@ -0,0 +1,30 @@
+use crate::args::Args;
+use crate::onboarding::layout::Layout;
+use anyhow::{anyhow, Result};
+use clap::CommandFactory;
+use clap_complete::{generate, Shell};
+use std::fs;
+use std::io::{self, Write};
+
+pub const BIN_NAME: &str = "termai";
+
+pub fn print_completions(shell: Shell) {
+    let mut script = Vec::new();
+    generate(shell, &mut Args::command(), BIN_NAME, &mut script);
+    let _ = io::stdout().write_all(&script);
+}
+
+pub fn install_completions(shell: Shell, layout: &Layout) -> Result<String> {
+    let (path, hint) = layout.completion_path(shell)?;
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)
+            .map_err(|err| anyhow!("could not create {}: {:?}", dir.display(), err))?;
+    }
+    let mut file = fs::File::create(&path)
+        .map_err(|err| anyhow!("could not write {}: {:?}", path.display(), err))?;
+    generate(shell, &mut Args::command(), BIN_NAME, &mut file);
+    Ok(match hint {
+        Some(hint) => format!("wrote {}, {}", path.display(), hint),
+        None => format!("wrote {}", path.display()),
+    })
+}
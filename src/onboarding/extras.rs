@@ -0,0 +1,60 @@
+use crate::args::Args;
+use crate::onboarding::completion::{install_completions, BIN_NAME};
+use crate::onboarding::layout::Layout;
+use anyhow::{anyhow, Result};
+use clap::CommandFactory;
+use clap_complete::Shell;
+use clap_mangen::Man;
+use colored::*;
+use std::env;
+use std::fs;
+use std::path::Path;
+
+pub fn install_extras(shell: Option<Shell>) -> Result<()> {
+    let layout = Layout::detect()?;
+    println!("installing for a {} install", layout.name());
+
+    let shell = shell
+        .or_else(Shell::from_env)
+        .ok_or_else(|| anyhow!("could not detect your shell, pass it with --shell"))?;
+    match install_completions(shell, &layout) {
+        Ok(done) => println!("{} {}", "completions:".green(), done),
+        Err(err) => println!("{} {}", "completions skipped:".yellow(), err),
+    }
+
+    match layout.man_dir() {
+        Some(dir) => {
+            let path = install_man_page(&dir)?;
+            println!("{} wrote {}", "man page:".green(), path);
+            if let Some(hint) = manpath_hint(&dir) {
+                println!("  {}", hint);
+            }
+        }
+        None => println!("{} not used on this platform", "man page skipped:".yellow()),
+    }
+    Ok(())
+}
+
+fn install_man_page(dir: &Path) -> Result<String> {
+    fs::create_dir_all(dir)
+        .map_err(|err| anyhow!("could not create {}: {:?}", dir.display(), err))?;
+    let path = dir.join(format!("{}.1", BIN_NAME));
+    let mut file = fs::File::create(&path)
+        .map_err(|err| anyhow!("could not write {}: {:?}", path.display(), err))?;
+    Man::new(Args::command())
+        .render(&mut file)
+        .map_err(|err| anyhow!("could not write {}: {:?}", path.display(), err))?;
+    Ok(path.display().to_string())
+}
+
+fn manpath_hint(dir: &Path) -> Option<String> {
+    let manpath = env::var_os("MANPATH")?;
+    let root = dir.parent()?;
+    if env::split_paths(&manpath).any(|path| path == root || path.as_os_str().is_empty()) {
+        return None;
+    }
+    Some(format!(
+        "MANPATH does not include {}, add it to read `man termai`",
+        root.display()
+    ))
+}
@@ -0,0 +1,205 @@
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub struct Prompt {
+    pub name: String,
+    pub text: String,
+}
+
+pub struct Imported {
+    pub source: String,
+    pub api_key: Option<String>,
+    pub model: Option<String>,
+    pub prompts: Vec<Prompt>,
+}
+
+impl Imported {
+    fn new(source: &str) -> Self {
+        Self {
+            source: source.to_string(),
+            api_key: None,
+            model: None,
+            prompts: vec![],
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.api_key.is_none() && self.model.is_none() && self.prompts.is_empty()
+    }
+}
+
+pub fn detect() -> Vec<Imported> {
+    [from_env(), from_aichat(), from_llm(), from_aider()]
+        .into_iter()
+        .filter(|imported| !imported.is_empty())
+        .collect()
+}
+
+pub fn mask_key(key: &str) -> String {
+    let chars = key.chars().collect::<Vec<char>>();
+    if chars.len() <= 8 {
+        return "*".repeat(chars.len());
+    }
+    format!(
+        "{}...{}",
+        chars[..3].iter().collect::<String>(),
+        chars[chars.len() - 4..].iter().collect::<String>()
+    )
+}
+
+fn from_env() -> Imported {
+    let mut imported = Imported::new("OPENAI_API_KEY environment variable");
+    imported.api_key = non_empty(env::var("OPENAI_API_KEY").ok());
+    imported
+}
+
+fn from_aichat() -> Imported {
+    let mut imported = Imported::new("aichat");
+    let Some(dir) = env::var_os("AICHAT_CONFIG_DIR")
+        .map(PathBuf::from)
+        .or_else(|| dirs::config_dir().map(|dir| dir.join("aichat")))
+    else {
+        return imported;
+    };
+    if let Ok(config) = fs::read_to_string(dir.join("config.yaml")) {
+        imported.model = yaml_value(&config, "model", true)
+            .and_then(|model| model.strip_prefix("openai:").map(str::to_string));
+        imported.api_key = yaml_value(&config, "api_key", false);
+    }
+    for (name, content) in read_dir(&dir.join("roles"), "md") {
+        let text = match content.strip_prefix("---") {
+            Some(rest) => rest
+                .split_once("\n---")
+                .map(|(_, body)| body.to_string())
+                .unwrap_or_default(),
+            None => content,
+        };
+        push_prompt(&mut imported, name, text);
+    }
+    imported
+}
+
+fn from_llm() -> Imported {
+    let mut imported = Imported::new("llm");
+    let Some(dir) = env::var_os("LLM_USER_PATH")
+        .map(PathBuf::from)
+        .or_else(|| dirs::config_dir().map(|dir| dir.join("io.datasette.llm")))
+    else {
+        return imported;
+    };
+    imported.api_key = fs::read_to_string(dir.join("keys.json"))
+        .ok()
+        .and_then(|keys| serde_json::from_str::<serde_json::Value>(&keys).ok())
+        .and_then(|keys| keys["openai"].as_str().map(str::to_string));
+    imported.model = non_empty(
+        fs::read_to_string(dir.join("default_model.txt"))
+            .ok()
+            .map(|model| model.trim().to_string()),
+    );
+    for (name, content) in read_dir(&dir.join("templates"), "yaml") {
+        if let Some(text) = yaml_value(&content, "system", true) {
+            push_prompt(&mut imported, name, text);
+        }
+    }
+    imported
+}
+
+fn from_aider() -> Imported {
+    let mut imported = Imported::new("aider");
+    let Some(home) = dirs::home_dir() else {
+        return imported;
+    };
+    if let Ok(config) = fs::read_to_string(home.join(".aider.conf.yml")) {
+        imported.api_key = yaml_value(&config, "openai-api-key", true);
+        imported.model = yaml_value(&config, "model", true);
+    }
+    if imported.api_key.is_none() {
+        imported.api_key = fs::read_to_string(home.join(".env"))
+            .ok()
+            .and_then(|dotenv| {
+                dotenv.lines().find_map(|line| {
+                    let value = line.trim().strip_prefix("OPENAI_API_KEY=")?;
+                    Some(unquote(value))
+                })
+            });
+    }
+    imported
+}
+
+fn push_prompt(imported: &mut Imported, name: String, text: String) {
+    let text = text.trim();
+    if !text.is_empty() {
+        imported.prompts.push(Prompt {
+            name,
+            text: text.to_string(),
+        });
+    }
+}
+
+fn read_dir(dir: &Path, extension: &str) -> Vec<(String, String)> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return vec![];
+    };
+    let mut files = entries
+        .filter_map(|entry| {
+            let path = entry.ok()?.path();
+            if path.extension()? != extension {
+                return None;
+            }
+            let name = path.file_stem()?.to_string_lossy().to_string();
+            Some((name, fs::read_to_string(&path).ok()?))
+        })
+        .collect::<Vec<(String, String)>>();
+    files.sort();
+    files
+}
+
+// Reads `key: value` from a simple YAML file, including `|` and `>` block scalars.
+// Keys are only looked up at the top level when `top_level` is set.
+fn yaml_value(content: &str, key: &str, top_level: bool) -> Option<String> {
+    let mut lines = content.lines();
+    while let Some(line) = lines.next() {
+        let trimmed = line.trim_start().trim_start_matches("- ");
+        let indent = line.len() - line.trim_start().len();
+        if top_level && indent > 0 {
+            continue;
+        }
+        let Some(value) = trimmed
+            .strip_prefix(key)
+            .and_then(|rest| rest.strip_prefix(':'))
+        else {
+            continue;
+        };
+        let value = value.trim();
+        if value.starts_with('|') || value.starts_with('>') {
+            let block = lines
+                .by_ref()
+                .take_while(|line| line.trim().is_empty() || line.starts_with([' ', '\t']))
+                .map(str::trim)
+                .collect::<Vec<&str>>();
+            let separator = if value.starts_with('|') { "\n" } else { " " };
+            return non_empty(Some(block.join(separator).trim().to_string()));
+        }
+        return non_empty(Some(unquote(value)));
+    }
+    None
+}
+
+fn unquote(value: &str) -> String {
+    let value = value.trim();
+    value
+        .strip_prefix('"')
+        .and_then(|value| value.strip_suffix('"'))
+        .or_else(|| {
+            value
+                .strip_prefix('\'')
+                .and_then(|value| value.strip_suffix('\''))
+        })
+        .unwrap_or(value)
+        .to_string()
+}
+
+fn non_empty(value: Option<String>) -> Option<String> {
+    value.filter(|value| !value.is_empty())
+}
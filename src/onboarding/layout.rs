@@ -0,0 +1,106 @@
+use anyhow::{anyhow, Result};
+use clap_complete::Shell;
+use std::env;
+use std::ffi::OsStr;
+use std::path::{Path, PathBuf};
+
+pub enum Layout {
+    Homebrew { prefix: PathBuf },
+    Scoop { home: PathBuf },
+    User { home: PathBuf },
+}
+
+impl Layout {
+    pub fn detect() -> Result<Self> {
+        let home = dirs::home_dir().ok_or_else(|| anyhow!("could not find the home directory"))?;
+        let exe = env::current_exe()
+            .and_then(|exe| exe.canonicalize())
+            .unwrap_or_default();
+        if let Some(prefix) = prefix_before(&exe, &["Cellar"]) {
+            return Ok(Self::Homebrew { prefix });
+        }
+        if prefix_before(&exe, &["scoop", "apps"]).is_some() {
+            return Ok(Self::Scoop { home });
+        }
+        Ok(Self::User { home })
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Homebrew { .. } => "Homebrew",
+            Self::Scoop { .. } => "Scoop",
+            Self::User { .. } => "user",
+        }
+    }
+
+    pub fn completion_path(&self, shell: Shell) -> Result<(PathBuf, Option<String>)> {
+        let path = match (self, shell) {
+            (Self::Homebrew { prefix }, Shell::Bash) => prefix.join("etc/bash_completion.d/termai"),
+            (Self::Homebrew { prefix }, Shell::Zsh) => {
+                prefix.join("share/zsh/site-functions/_termai")
+            }
+            (Self::Homebrew { prefix }, Shell::Fish) => {
+                prefix.join("share/fish/vendor_completions.d/termai.fish")
+            }
+            (Self::User { home } | Self::Scoop { home }, Shell::Bash) => {
+                data_dir(home).join("bash-completion/completions/termai")
+            }
+            (Self::User { home } | Self::Scoop { home }, Shell::Zsh) => {
+                let dir = data_dir(home).join("zsh/site-functions");
+                let hint = format!(
+                    "add `fpath=({} $fpath)` before `compinit` in ~/.zshrc",
+                    dir.display()
+                );
+                return Ok((dir.join("_termai"), Some(hint)));
+            }
+            (Self::User { home } | Self::Scoop { home }, Shell::Fish) => {
+                config_dir(home).join("fish/completions/termai.fish")
+            }
+            (_, Shell::PowerShell) => {
+                let home =
+                    dirs::home_dir().ok_or_else(|| anyhow!("could not find the home directory"))?;
+                let path = data_dir(&home).join("termai/completions/termai.ps1");
+                let hint = format!("add `. {}` to your $PROFILE", path.display());
+                return Ok((path, Some(hint)));
+            }
+            _ => {
+                return Err(anyhow!(
+                    "run `termai completions {}` and install the output manually",
+                    shell
+                ))
+            }
+        };
+        Ok((path, None))
+    }
+
+    pub fn man_dir(&self) -> Option<PathBuf> {
+        match self {
+            Self::Homebrew { prefix } => Some(prefix.join("share/man/man1")),
+            Self::Scoop { .. } => None,
+            Self::User { home } => Some(data_dir(home).join("man/man1")),
+        }
+    }
+}
+
+fn prefix_before(path: &Path, names: &[&str]) -> Option<PathBuf> {
+    let components = path.components().collect::<Vec<_>>();
+    let start = components.windows(names.len()).position(|window| {
+        window
+            .iter()
+            .zip(names)
+            .all(|(component, name)| component.as_os_str() == OsStr::new(name))
+    })?;
+    Some(components[..start].iter().collect())
+}
+
+fn data_dir(home: &Path) -> PathBuf {
+    env::var_os("XDG_DATA_HOME")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| home.join(".local/share"))
+}
+
+fn config_dir(home: &Path) -> PathBuf {
+    env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| home.join(".config"))
+}
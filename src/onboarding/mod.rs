@@ -0,0 +1,5 @@
+pub(crate) mod completion;
+pub(crate) mod extras;
+pub(crate) mod import;
+pub(crate) mod layout;
+pub(crate) mod setup;
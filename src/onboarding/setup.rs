@@ -0,0 +1,245 @@
+use crate::common::terminal::{answer, ask, is_interactive};
+use crate::config::model::keys::ConfigKeys;
+use crate::config::repository::ConfigRepository;
+use crate::config::service::config_service;
+use crate::git::repository::repo_root;
+use crate::models::model::capabilities::is_chat_model;
+use crate::models::repository::ModelRepository;
+use crate::models::service::models_service::{available_models, configured_model, model_line};
+use crate::notes::service::notes_service::install_hook;
+use crate::onboarding::completion::install_completions;
+use crate::onboarding::import::{detect, mask_key, Imported, Prompt};
+use crate::onboarding::layout::Layout;
+use crate::openai::service::prompt::prompt;
+use crate::output::markdown::truncate;
+use crate::usage::repository::UsageRepository;
+use anyhow::{anyhow, Result};
+use clap_complete::Shell;
+use colored::*;
+use std::io::{self, IsTerminal};
+use std::path::Path;
+
+const PROMPT_PREVIEW_WIDTH: usize = 60;
+const API_KEYS_URL: &str = "https://platform.openai.com/api-keys";
+const SAMPLE_PROMPT: &str = "In two sentences, what does `git rebase -i HEAD~3` do?";
+const SAMPLE_SYSTEM_PROMPT: &str =
+    "You are a concise assistant for developers working in a terminal.";
+
+pub fn has_api_key<R: ConfigRepository>(repo: &R) -> bool {
+    config_service::fetch_by_key(repo, &ConfigKeys::ChatGptApiKey.to_key()).is_ok()
+}
+
+pub async fn first_run<R: ConfigRepository, UR: UsageRepository, MR: ModelRepository>(
+    repo: &R,
+    usage_repo: &UR,
+    model_repo: &MR,
+    read_only: bool,
+) -> Result<()> {
+    if read_only || !is_interactive() || !io::stdout().is_terminal() {
+        return Err(anyhow!(
+            "no OpenAI API key is configured, run `termai setup` in a terminal or `termai -c <key>`"
+        ));
+    }
+    setup(repo, usage_repo, model_repo).await
+}
+
+pub async fn setup<R: ConfigRepository, UR: UsageRepository, MR: ModelRepository>(
+    repo: &R,
+    usage_repo: &UR,
+    model_repo: &MR,
+) -> Result<()> {
+    if !is_interactive() {
+        return Err(anyhow!("setup asks questions, run it in a terminal"));
+    }
+    println!("{}", "Welcome to termai.".bold());
+    println!("A few quick steps to get you going, press Ctrl+C to stop at any time.");
+
+    let mut number = 0;
+    let found = detect();
+    let mut key_imported = false;
+    if !found.is_empty() {
+        step(&mut number, "Settings from other tools");
+        key_imported = import_settings(repo, &found)?;
+    }
+
+    step(&mut number, "OpenAI API key");
+    if key_imported {
+        println!("using the imported key");
+    } else if !has_api_key(repo) || ask("an API key is already configured, replace it?", false)? {
+        println!("create one at {}", API_KEYS_URL);
+        let key = rpassword::prompt_password("API key (hidden): ")
+            .map_err(|err| anyhow!("could not read the key: {:?}", err))?;
+        let key = key.trim();
+        if key.is_empty() {
+            return Err(anyhow!(
+                "no key entered, run `termai setup` again when you have one"
+            ));
+        }
+        config_service::write_config(repo, &ConfigKeys::ChatGptApiKey.to_key(), key)?;
+        println!("{}", "saved".green());
+    }
+
+    step(&mut number, "Model");
+    match available_models(repo, model_repo, false).await {
+        Ok(models) => {
+            let models = models
+                .into_iter()
+                .filter(|model| is_chat_model(model))
+                .collect::<Vec<String>>();
+            choose_model(repo, &models)?;
+        }
+        Err(err) => println!("{} {}", "skipped:".yellow(), err),
+    }
+
+    step(&mut number, "Shell completion");
+    match Shell::from_env() {
+        Some(shell) => {
+            if ask(&format!("install tab completion for {}?", shell), true)? {
+                match Layout::detect().and_then(|layout| install_completions(shell, &layout)) {
+                    Ok(done) => println!("{}", done),
+                    Err(err) => println!("{} {}", "skipped:".yellow(), err),
+                }
+            }
+        }
+        None => println!("could not detect your shell, see `termai completions --help`"),
+    }
+
+    step(&mut number, "Git hook");
+    match repo_root(Path::new(".")) {
+        Ok(root) => {
+            if ask(
+                "write an AI note for every commit in this repository (one request per commit)?",
+                false,
+            )? {
+                if let Err(err) = install_hook(&root) {
+                    println!("{} {}", "skipped:".yellow(), err);
+                }
+            }
+        }
+        Err(_) => {
+            println!("not inside a git repository, run `termai notes install-hook` in one later")
+        }
+    }
+
+    step(&mut number, "Try it");
+    if ask(&format!("ask \"{}\"?", SAMPLE_PROMPT), true)? {
+        let answer = prompt(
+            repo,
+            usage_repo,
+            "setup",
+            Path::new("."),
+            SAMPLE_SYSTEM_PROMPT,
+            SAMPLE_PROMPT,
+        )
+        .await?;
+        println!("\n{}\n", answer);
+    }
+
+    println!("{}", "You're set.".bold());
+    println!("  termai \"how do I undo the last commit?\"");
+    println!("  git diff | termai \"review this change\"");
+    println!("  termai --session work \"explain\" src/");
+    Ok(())
+}
+
+fn choose_model<R: ConfigRepository>(repo: &R, models: &[String]) -> Result<()> {
+    let current = configured_model(repo);
+    let width = models.iter().map(|model| model.len()).max().unwrap_or(0);
+    for (number, model) in models.iter().enumerate() {
+        println!(
+            "{:>3}) {}",
+            number + 1,
+            model_line(model, model == &current, width)
+        );
+    }
+    loop {
+        let answer = answer(&format!("model number, Enter keeps {}:", current))?;
+        if answer.is_empty() {
+            return Ok(());
+        }
+        match answer
+            .parse::<usize>()
+            .ok()
+            .and_then(|number| models.get(number.wrapping_sub(1)))
+        {
+            Some(model) => {
+                config_service::write_config(repo, &ConfigKeys::Model.to_key(), model)?;
+                println!("{}", "saved".green());
+                return Ok(());
+            }
+            None => println!("enter a number between 1 and {}", models.len()),
+        }
+    }
+}
+
+fn import_settings<R: ConfigRepository>(repo: &R, found: &[Imported]) -> Result<bool> {
+    let mut key_imported = false;
+    let mut model_imported = false;
+    for imported in found {
+        println!("found {}", imported.source.bold());
+        if let Some(key) = imported.api_key.as_ref().filter(|_| !key_imported) {
+            if ask(&format!("  import the API key {}?", mask_key(key)), true)? {
+                config_service::write_config(repo, &ConfigKeys::ChatGptApiKey.to_key(), key)?;
+                println!("  {}", "saved".green());
+                key_imported = true;
+            }
+        }
+        if let Some(model) = imported.model.as_ref().filter(|_| !model_imported) {
+            if !is_chat_model(model) {
+                println!(
+                    "  skipping the default model {}, it is not an OpenAI chat model",
+                    model
+                );
+            } else if ask(&format!("  use {} as the default model?", model), true)? {
+                config_service::write_config(repo, &ConfigKeys::Model.to_key(), model)?;
+                println!("  {}", "saved".green());
+                model_imported = true;
+            }
+        }
+        if !imported.prompts.is_empty() {
+            choose_prompt(repo, &imported.prompts)?;
+        }
+    }
+    Ok(key_imported)
+}
+
+fn choose_prompt<R: ConfigRepository>(repo: &R, prompts: &[Prompt]) -> Result<()> {
+    println!("  custom prompts:");
+    for (number, prompt) in prompts.iter().enumerate() {
+        let first_line = prompt.text.lines().next().unwrap_or_default();
+        println!(
+            "  {:>3}) {} {}",
+            number + 1,
+            prompt.name,
+            truncate(first_line, PROMPT_PREVIEW_WIDTH).dimmed()
+        );
+    }
+    loop {
+        let answer = answer("  prompt number to use as the system prompt, Enter skips:")?;
+        if answer.is_empty() {
+            return Ok(());
+        }
+        match answer
+            .parse::<usize>()
+            .ok()
+            .and_then(|number| prompts.get(number.wrapping_sub(1)))
+        {
+            Some(prompt) => {
+                config_service::write_config(
+                    repo,
+                    &ConfigKeys::SystemPrompt.to_key(),
+                    &prompt.text,
+                )?;
+                println!("  {}", "saved".green());
+                return Ok(());
+            }
+            None => println!("  enter a number between 1 and {}", prompts.len()),
+        }
+    }
+}
+
+fn step(number: &mut usize, title: &str) {
+    *number += 1;
+    println!();
+    println!("{}", format!("{}. {}", number, title).cyan().bold());
+}
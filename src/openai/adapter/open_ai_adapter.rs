@@ -1,22 +1,199 @@
+use crate::common::unique_id::generate_uuid_v4;
+use crate::openai::model::api_error::ApiError;
+use crate::openai::model::chat_completion_chunk::ChatCompletionChunk;
 use crate::openai::model::chat_completion_request::ChatCompletionRequest;
 use crate::openai::model::chat_completion_response::ChatCompletionResponse;
-use anyhow::Result;
-use reqwest::Client;
+use crate::openai::model::choice::Choice;
+use crate::openai::model::file_object::FileObject;
+use crate::openai::model::message_content::MessageContent;
+use crate::openai::model::models_response::ModelsResponse;
+use crate::provider::model::Endpoint;
+use anyhow::{anyhow, Result};
+use reqwest::{Client, RequestBuilder, Response, StatusCode};
 
 pub async fn chat(
     request: &ChatCompletionRequest,
-    api_key: &str,
+    endpoint: &Endpoint,
 ) -> Result<ChatCompletionResponse> {
     let client = Client::new();
-    let response: ChatCompletionResponse = client
-        .post("https://api.openai.com/v1/chat/completions")
+    let response = authorized(client.post(endpoint.url("chat/completions")), endpoint)
         .header("Content-Type", "application/json")
-        .bearer_auth(api_key)
         .json(&request)
+        .send()
+        .await?;
+
+    Ok(successful(response, endpoint).await?.json().await?)
+}
+
+pub async fn chat_stream(
+    request: &ChatCompletionRequest,
+    endpoint: &Endpoint,
+    on_delta: &mut impl FnMut(&str),
+) -> Result<ChatCompletionResponse> {
+    let client = Client::new();
+    let response = authorized(client.post(endpoint.url("chat/completions")), endpoint)
+        .header("Content-Type", "application/json")
+        .json(&request)
+        .send()
+        .await?;
+    let mut response = successful(response, endpoint).await?;
+
+    let mut model = None;
+    let mut role = "assistant".to_string();
+    let mut content = String::new();
+    let mut finish_reason = "stop".to_string();
+    let mut usage = None;
+    let mut pending = Vec::new();
+    'read: while let Some(bytes) = response.chunk().await? {
+        pending.extend_from_slice(&bytes);
+        while let Some(end) = pending.iter().position(|byte| *byte == b'\n') {
+            let line = pending.drain(..=end).collect::<Vec<u8>>();
+            let line = String::from_utf8_lossy(&line);
+            let Some(data) = line.trim().strip_prefix("data:").map(str::trim) else {
+                continue;
+            };
+            if data == "[DONE]" {
+                break 'read;
+            }
+            let chunk: ChatCompletionChunk = serde_json::from_str(data)
+                .map_err(|err| anyhow!("could not read the streamed answer: {:?}", err))?;
+            model = chunk.model.or(model);
+            usage = chunk.usage.or(usage);
+            for choice in chunk.choices {
+                if let Some(delta_role) = choice.delta.role {
+                    role = delta_role;
+                }
+                if let Some(delta) = choice.delta.content {
+                    on_delta(&delta);
+                    content.push_str(&delta);
+                }
+                if let Some(reason) = choice.finish_reason {
+                    finish_reason = reason;
+                }
+            }
+        }
+    }
+
+    Ok(ChatCompletionResponse {
+        id: None,
+        object: None,
+        created: None,
+        model,
+        system_fingerprint: None,
+        choices: Some(vec![Choice {
+            index: 0,
+            message: MessageContent { role, content },
+            logprobs: None,
+            finish_reason,
+        }]),
+        usage,
+    })
+}
+
+pub async fn list_models(endpoint: &Endpoint) -> Result<ModelsResponse> {
+    let client = Client::new();
+    let response: ModelsResponse = authorized(client.get(endpoint.url("models")), endpoint)
         .send()
         .await?
+        .error_for_status()?
         .json()
         .await?;
 
     Ok(response)
 }
+
+pub async fn upload_file(
+    endpoint: &Endpoint,
+    filename: &str,
+    content_type: &str,
+    content: &[u8],
+) -> Result<FileObject> {
+    let boundary = format!("termai-{}", generate_uuid_v4());
+    let body = multipart_body(&boundary, filename, content_type, content);
+
+    let client = Client::new();
+    let response = authorized(client.post(endpoint.url("files")), endpoint)
+        .header(
+            "Content-Type",
+            format!("multipart/form-data; boundary={}", boundary),
+        )
+        .body(body)
+        .send()
+        .await?;
+
+    Ok(successful(response, endpoint).await?.json().await?)
+}
+
+pub async fn delete_file(endpoint: &Endpoint, file_id: &str) -> Result<()> {
+    let client = Client::new();
+    let response = authorized(
+        client.delete(endpoint.url(&format!("files/{}", file_id))),
+        endpoint,
+    )
+    .send()
+    .await?;
+    if response.status() != StatusCode::NOT_FOUND {
+        successful(response, endpoint).await?;
+    }
+
+    Ok(())
+}
+
+fn multipart_body(boundary: &str, filename: &str, content_type: &str, content: &[u8]) -> Vec<u8> {
+    let filename = filename.replace(['"', '\r', '\n'], "_");
+    let mut body = format!(
+        "--{boundary}\r\nContent-Disposition: form-data; name=\"purpose\"\r\n\r\nuser_data\r\n\
+         --{boundary}\r\nContent-Disposition: form-data; name=\"file\"; filename=\"{filename}\"\r\n\
+         Content-Type: {content_type}\r\n\r\n"
+    )
+    .into_bytes();
+    body.extend_from_slice(content);
+    body.extend_from_slice(format!("\r\n--{boundary}--\r\n").as_bytes());
+    body
+}
+
+async fn successful(response: Response, endpoint: &Endpoint) -> Result<Response> {
+    if response.status().is_success() {
+        return Ok(response);
+    }
+    let status = response.status();
+    let body = response.text().await.unwrap_or_default();
+    Err(ApiError {
+        base_url: endpoint.base_url.clone(),
+        status,
+        body,
+    }
+    .into())
+}
+
+fn authorized(request: RequestBuilder, endpoint: &Endpoint) -> RequestBuilder {
+    match &endpoint.api_key {
+        Some(api_key) => request.bearer_auth(api_key),
+        None => request,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn multipart_body_has_the_purpose_and_the_raw_file() {
+        let body = multipart_body("b", "spec.pdf", "application/pdf", b"%PDF-\xff");
+        let mut expected =
+            b"--b\r\nContent-Disposition: form-data; name=\"purpose\"\r\n\r\nuser_data\r\n\
+              --b\r\nContent-Disposition: form-data; name=\"file\"; filename=\"spec.pdf\"\r\n\
+              Content-Type: application/pdf\r\n\r\n%PDF-"
+                .to_vec();
+        expected.push(0xff);
+        expected.extend_from_slice(b"\r\n--b--\r\n");
+        assert_eq!(body, expected);
+    }
+
+    #[test]
+    fn multipart_filename_cannot_break_the_header() {
+        let body = multipart_body("b", "a\"b\r\n.pdf", "application/pdf", b"");
+        let body = String::from_utf8(body).unwrap();
+        assert!(body.contains("filename=\"a_b__.pdf\""));
+    }
+}
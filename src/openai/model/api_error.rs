@@ -0,0 +1,28 @@
+use reqwest::StatusCode;
+use std::fmt;
+
+// An error status the API answered with, kept apart from network errors so
+// callers can tell what was rejected.
+pub struct ApiError {
+    pub base_url: String,
+    pub status: StatusCode,
+    pub body: String,
+}
+
+impl fmt::Display for ApiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} answered {}: {}",
+            self.base_url, self.status, self.body
+        )
+    }
+}
+
+impl fmt::Debug for ApiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+impl std::error::Error for ApiError {}
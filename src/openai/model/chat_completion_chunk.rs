@@ -0,0 +1,22 @@
+use crate::openai::model::usage::Usage;
+use serde::Deserialize;
+
+#[derive(Deserialize, Debug)]
+pub struct ChatCompletionChunk {
+    pub model: Option<String>,
+    #[serde(default)]
+    pub choices: Vec<ChunkChoice>,
+    pub usage: Option<Usage>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct ChunkChoice {
+    pub delta: Delta,
+    pub finish_reason: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct Delta {
+    pub role: Option<String>,
+    pub content: Option<String>,
+}
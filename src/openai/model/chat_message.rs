@@ -1,43 +1,15 @@
-use crate::output::message;
+use crate::openai::model::content_part::ContentPart;
 use serde::Serialize;
 
-use super::role::Role;
-
 #[derive(Serialize, Clone)]
 pub struct ChatMessage {
     pub role: String,
-    pub content: String,
+    pub content: ChatContent,
 }
 
-impl ChatMessage {
-    pub fn new(role: &str, content: &str) -> Self {
-        Self {
-            role: role.to_string(),
-            content: content.to_string(),
-        }
-    }
-
-    pub fn to_output_message(&self) -> message::Message {
-        message::Message {
-            role: Role::from_str(&self.role),
-            message: self.content.to_string(),
-        }
-    }
-
-    pub fn prepend_content(&self, text: &str) -> Self {
-        let new_content = format!("{}\n\n{}", text, self.content);
-        Self {
-            role: self.role.to_string(),
-            content: new_content,
-        }
-    }
-
-    pub fn remove_from_content(&self, text: &str) -> Self {
-        let new_content = self.content.replace(text, "");
-        let new_content = new_content.trim();
-        Self {
-            role: self.role.to_string(),
-            content: new_content.to_string(),
-        }
-    }
+#[derive(Serialize, Clone)]
+#[serde(untagged)]
+pub enum ChatContent {
+    Text(String),
+    Parts(Vec<ContentPart>),
 }
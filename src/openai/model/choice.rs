@@ -2,6 +2,7 @@ use crate::openai::model::message_content::MessageContent;
 use serde::Deserialize;
 
 #[derive(Deserialize, Debug)]
+#[allow(dead_code)]
 pub struct Choice {
     pub index: u32,
     pub message: MessageContent,
@@ -0,0 +1,62 @@
+use serde::Serialize;
+
+#[derive(Serialize, Clone)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ContentPart {
+    Text { text: String },
+    ImageUrl { image_url: ImageUrl },
+    File { file: FileReference },
+}
+
+#[derive(Serialize, Clone)]
+pub struct ImageUrl {
+    pub url: String,
+}
+
+// A PDF, either uploaded before and referenced by its id, or sent inline as
+// a data URL with its file name.
+#[derive(Serialize, Clone)]
+#[serde(untagged)]
+pub enum FileReference {
+    Uploaded { file_id: String },
+    Inline { filename: String, file_data: String },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn uploaded_file_is_referenced_by_id() {
+        let part = ContentPart::File {
+            file: FileReference::Uploaded {
+                file_id: "file-abc".to_string(),
+            },
+        };
+        assert_eq!(
+            serde_json::to_value(part).unwrap(),
+            json!({"type": "file", "file": {"file_id": "file-abc"}})
+        );
+    }
+
+    #[test]
+    fn inline_file_carries_its_name_and_data() {
+        let part = ContentPart::File {
+            file: FileReference::Inline {
+                filename: "spec.pdf".to_string(),
+                file_data: "data:application/pdf;base64,JVBERi0=".to_string(),
+            },
+        };
+        assert_eq!(
+            serde_json::to_value(part).unwrap(),
+            json!({
+                "type": "file",
+                "file": {
+                    "filename": "spec.pdf",
+                    "file_data": "data:application/pdf;base64,JVBERi0="
+                }
+            })
+        );
+    }
+}
@@ -0,0 +1,7 @@
+use serde::Deserialize;
+
+#[derive(Deserialize, Debug)]
+pub struct FileObject {
+    pub id: String,
+    pub bytes: Option<u64>,
+}
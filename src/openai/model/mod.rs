@@ -1,9 +1,16 @@
+pub(crate) mod api_error;
+pub(crate) mod chat_completion_chunk;
 pub(crate) mod chat_completion_request;
 pub(crate) mod chat_completion_response;
+pub(crate) mod chat_message;
 pub(crate) mod choice;
 pub(crate) mod completion_token_details;
-pub(crate) mod chat_message;
+pub(crate) mod content_part;
+pub(crate) mod file_object;
 pub(crate) mod message_content;
+#[allow(clippy::module_inception)]
 pub(crate) mod model;
+pub(crate) mod models_response;
 pub(crate) mod role;
+pub(crate) mod sampling;
 pub(crate) mod usage;
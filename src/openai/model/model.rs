@@ -1,4 +1,5 @@
 use serde::Serialize;
+use std::fmt;
 
 #[derive(Serialize)]
 #[serde(rename_all = "lowercase")]
@@ -42,45 +43,46 @@ pub enum Model {
     Davinci002,
 }
 
-impl Model {
-    pub fn to_string(&self) -> String {
-        match self {
-            Model::Gpt4o => "gpt-4o".to_owned(),
-            Model::Gpt4oMini => "gpt-4o-mini".to_owned(),
-            Model::Gpt4Turbo => "gpt-4-turbo".to_owned(),
-            Model::Gpt4 => "gpt-4".to_owned(),
-            Model::Gpt35Turbo => "gpt-3.5-turbo".to_owned(),
-            Model::DallE2 => "dall-e-2".to_owned(),
-            Model::DallE3 => "dall-e-3".to_owned(),
-            Model::Tts1 => "tts-1".to_owned(),
-            Model::Tts1Hd => "tts-1-hd".to_owned(),
-            Model::Whisper1 => "whisper-1".to_owned(),
-            Model::WhisperV2Large => "whisper-v2-large".to_owned(),
-            Model::TextEmbedding3Large => "text-embedding-3-large".to_owned(),
-            Model::TextEmbedding3Small => "text-embedding-3-small".to_owned(),
-            Model::TextEmbeddingAda002 => "text-embedding-ada-002".to_owned(),
-            Model::OmniModerationLatest => "omni-moderation-latest".to_owned(),
-            Model::TextModerationLatest => "text-moderation-latest".to_owned(),
-            Model::TextModerationStable => "text-moderation-stable".to_owned(),
-            Model::TextModeration007 => "text-moderation-007".to_owned(),
-            Model::O1Preview => "o1-preview".to_owned(),
-            Model::O1Mini => "o1-mini".to_owned(),
-            Model::O1 => "o1".to_owned(),
-            Model::O3Mini => "o3-mini".to_owned(),
-            Model::Gpt4oRealtimePreview => "gpt-4o-realtime-preview".to_owned(),
-            Model::Gpt4o20240806 => "gpt-4o-2024-08-06".to_owned(),
-            Model::Gpt4o20240513 => "gpt-4o-2024-05-13".to_owned(),
-            Model::Gpt4oMini20240718 => "gpt-4o-mini-2024-07-18".to_owned(),
-            Model::Gpt4Turbo20240409 => "gpt-4-turbo-2024-04-09".to_owned(),
-            Model::Gpt4TurboPreview => "gpt-4-turbo-preview".to_owned(),
-            Model::Gpt4Preview => "gpt-4-0125-preview".to_owned(),
-            Model::Gpt4_0613 => "gpt-4-0613".to_owned(),
-            Model::Gpt4_0314 => "gpt-4-0314".to_owned(),
-            Model::Gpt35Turbo0125 => "gpt-3.5-turbo-0125".to_owned(),
-            Model::Gpt35Turbo1106 => "gpt-3.5-turbo-1106".to_owned(),
-            Model::Gpt35TurboInstruct => "gpt-3.5-turbo-instruct".to_owned(),
-            Model::Babbage002 => "babbage-002".to_owned(),
-            Model::Davinci002 => "davinci-002".to_owned(),
-        }
+impl fmt::Display for Model {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let model = match self {
+            Model::Gpt4o => "gpt-4o",
+            Model::Gpt4oMini => "gpt-4o-mini",
+            Model::Gpt4Turbo => "gpt-4-turbo",
+            Model::Gpt4 => "gpt-4",
+            Model::Gpt35Turbo => "gpt-3.5-turbo",
+            Model::DallE2 => "dall-e-2",
+            Model::DallE3 => "dall-e-3",
+            Model::Tts1 => "tts-1",
+            Model::Tts1Hd => "tts-1-hd",
+            Model::Whisper1 => "whisper-1",
+            Model::WhisperV2Large => "whisper-v2-large",
+            Model::TextEmbedding3Large => "text-embedding-3-large",
+            Model::TextEmbedding3Small => "text-embedding-3-small",
+            Model::TextEmbeddingAda002 => "text-embedding-ada-002",
+            Model::OmniModerationLatest => "omni-moderation-latest",
+            Model::TextModerationLatest => "text-moderation-latest",
+            Model::TextModerationStable => "text-moderation-stable",
+            Model::TextModeration007 => "text-moderation-007",
+            Model::O1Preview => "o1-preview",
+            Model::O1Mini => "o1-mini",
+            Model::O1 => "o1",
+            Model::O3Mini => "o3-mini",
+            Model::Gpt4oRealtimePreview => "gpt-4o-realtime-preview",
+            Model::Gpt4o20240806 => "gpt-4o-2024-08-06",
+            Model::Gpt4o20240513 => "gpt-4o-2024-05-13",
+            Model::Gpt4oMini20240718 => "gpt-4o-mini-2024-07-18",
+            Model::Gpt4Turbo20240409 => "gpt-4-turbo-2024-04-09",
+            Model::Gpt4TurboPreview => "gpt-4-turbo-preview",
+            Model::Gpt4Preview => "gpt-4-0125-preview",
+            Model::Gpt4_0613 => "gpt-4-0613",
+            Model::Gpt4_0314 => "gpt-4-0314",
+            Model::Gpt35Turbo0125 => "gpt-3.5-turbo-0125",
+            Model::Gpt35Turbo1106 => "gpt-3.5-turbo-1106",
+            Model::Gpt35TurboInstruct => "gpt-3.5-turbo-instruct",
+            Model::Babbage002 => "babbage-002",
+            Model::Davinci002 => "davinci-002",
+        };
+        write!(f, "{}", model)
     }
 }
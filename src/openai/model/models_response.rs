@@ -0,0 +1,14 @@
+use serde::Deserialize;
+
+#[derive(Deserialize, Debug)]
+pub struct ModelsResponse {
+    pub data: Vec<ModelObject>,
+}
+
+#[derive(Deserialize, Debug)]
+#[allow(dead_code)]
+pub struct ModelObject {
+    pub id: String,
+    pub created: Option<u64>,
+    pub owned_by: Option<String>,
+}
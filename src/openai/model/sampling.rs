@@ -0,0 +1,52 @@
+use serde::Deserialize;
+
+const MAX_STOP_SEQUENCES: usize = 4;
+
+#[derive(Deserialize, Debug, Default, Clone)]
+#[serde(default)]
+pub struct Sampling {
+    pub temperature: Option<f32>,
+    pub top_p: Option<f32>,
+    pub max_output_tokens: Option<u32>,
+    pub stop: Vec<String>,
+}
+
+impl Sampling {
+    pub fn or(self, fallback: Self) -> Self {
+        Self {
+            temperature: self.temperature.or(fallback.temperature),
+            top_p: self.top_p.or(fallback.top_p),
+            max_output_tokens: self.max_output_tokens.or(fallback.max_output_tokens),
+            stop: match self.stop.is_empty() {
+                true => fallback.stop,
+                false => self.stop,
+            },
+        }
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if let Some(temperature) = self.temperature.filter(|t| !(0.0..=2.0).contains(t)) {
+            return Err(format!(
+                "the temperature must be between 0 and 2, got {}",
+                temperature
+            ));
+        }
+        if let Some(top_p) = self.top_p.filter(|p| !(0.0..=1.0).contains(p)) {
+            return Err(format!("top_p must be between 0 and 1, got {}", top_p));
+        }
+        if self.max_output_tokens == Some(0) {
+            return Err("max_output_tokens must be at least 1".to_string());
+        }
+        if self.stop.len() > MAX_STOP_SEQUENCES {
+            return Err(format!(
+                "at most {} stop sequences are allowed, got {}",
+                MAX_STOP_SEQUENCES,
+                self.stop.len()
+            ));
+        }
+        if self.stop.iter().any(String::is_empty) {
+            return Err("stop sequences must not be empty".to_string());
+        }
+        Ok(())
+    }
+}
@@ -2,9 +2,10 @@ use crate::openai::model::completion_token_details::CompletionTokensDetails;
 use serde::Deserialize;
 
 #[derive(Deserialize, Debug)]
+#[allow(dead_code)]
 pub struct Usage {
-    prompt_tokens: u32,
-    completion_tokens: u32,
-    total_tokens: u32,
-    completion_tokens_details: CompletionTokensDetails,
+    pub prompt_tokens: u32,
+    pub completion_tokens: u32,
+    pub total_tokens: u32,
+    pub completion_tokens_details: CompletionTokensDetails,
 }
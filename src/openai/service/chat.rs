@@ -1,13 +1,24 @@
+use crate::guard::model::{data_blocks, DATA_INSTRUCTION};
+use crate::models::model::capabilities::accepts_sampling;
 use crate::openai::{
     adapter::open_ai_adapter,
     model::{
-        chat_completion_request::ChatCompletionRequest, chat_message::ChatMessage, model::Model,
+        chat_completion_request::{ChatCompletionRequest, StreamOptions},
+        chat_completion_response::ChatCompletionResponse,
+        chat_message::{ChatContent, ChatMessage},
+        content_part::{ContentPart, FileReference, ImageUrl},
+        model::Model,
         role::Role,
+        sampling::Sampling,
     },
 };
+use crate::output::progress::status;
+use crate::provider::model::Endpoint;
 use crate::session::model::message::Message;
 use crate::session::model::session::Session;
-use anyhow::Result;
+use crate::upload::model::Uploads;
+use crate::usage::model::token_usage::TokenUsage;
+use anyhow::{anyhow, Result};
 
 pub const SYSTEM_PROMPT: &str = "
 You're an assistant in the terminal.
@@ -16,24 +27,109 @@ You will never output markdown, only ASCII text or ASCII art.
 You will limit your line length to 80 characters.
 You will not replace any UUIDs that you find in the text, these are required by the application for replacements later.";
 
-pub async fn chat(api_key: &str, session: &mut Session) -> Result<()> {
-    let model = Model::O3Mini;
+pub const CODE_ONLY_INSTRUCTION: &str = "
+Answer with fenced code blocks only.
+Do not write any text before, between or after the code blocks, put explanations in code comments instead.";
 
-    let chat_messages = session
+pub const CODE_ONLY_RETRY: &str =
+    "Your answer had text outside the code blocks. Answer again with the fenced code blocks only.";
+
+pub const CODE_ONLY_RETRIES: u32 = 2;
+
+pub const MODEL: Model = Model::O3Mini;
+
+pub async fn chat(
+    endpoint: &Endpoint,
+    model: &str,
+    sampling: &Sampling,
+    session: &mut Session,
+    uploads: &Uploads,
+) -> Result<Option<TokenUsage>> {
+    let request = chat_request(model, sampling, session, uploads)?;
+    let response = open_ai_adapter::chat(&request, endpoint).await?;
+    Ok(record_answer(model, session, response))
+}
+
+pub async fn chat_stream(
+    endpoint: &Endpoint,
+    model: &str,
+    sampling: &Sampling,
+    session: &mut Session,
+    uploads: &Uploads,
+    on_delta: &mut impl FnMut(&str),
+) -> Result<Option<TokenUsage>> {
+    let mut request = chat_request(model, sampling, session, uploads)?;
+    request.stream = Some(true);
+    request.stream_options = Some(StreamOptions {
+        include_usage: true,
+    });
+    let response = open_ai_adapter::chat_stream(&request, endpoint, on_delta).await?;
+    Ok(record_answer(model, session, response))
+}
+
+fn chat_request(
+    model: &str,
+    sampling: &Sampling,
+    session: &Session,
+    uploads: &Uploads,
+) -> Result<ChatCompletionRequest> {
+    sampling.validate().map_err(|err| anyhow!(err))?;
+    let (temperature, top_p, stop) = match accepts_sampling(model) {
+        true => (
+            sampling.temperature,
+            sampling.top_p,
+            Some(sampling.stop.clone()).filter(|stop| !stop.is_empty()),
+        ),
+        false => {
+            if sampling.temperature.is_some()
+                || sampling.top_p.is_some()
+                || !sampling.stop.is_empty()
+            {
+                status(&format!(
+                    "{} does not accept temperature, top_p or stop sequences, sending without them",
+                    model
+                ));
+            }
+            (None, None, None)
+        }
+    };
+    let mut chat_messages = session
         .messages
         .iter()
-        .map(|m| ChatMessage {
-            role: m.role.to_string(),
-            content: m.content.to_string(),
-        })
+        .map(|message| to_chat_message(message, uploads))
         .collect::<Vec<ChatMessage>>();
+    if session
+        .messages
+        .iter()
+        .any(|message| !data_blocks(&message.content_with_context()).is_empty())
+    {
+        chat_messages.insert(
+            0,
+            ChatMessage {
+                role: Role::System.to_string(),
+                content: ChatContent::Text(DATA_INSTRUCTION.to_string()),
+            },
+        );
+    }
 
-    let request = ChatCompletionRequest {
+    Ok(ChatCompletionRequest {
         model: model.to_string(),
         messages: chat_messages,
-    };
-    let response = open_ai_adapter::chat(&request, api_key).await?;
+        temperature,
+        top_p,
+        max_completion_tokens: sampling.max_output_tokens,
+        stop,
+        stream: None,
+        stream_options: None,
+    })
+}
 
+fn record_answer(
+    model: &str,
+    session: &mut Session,
+    response: ChatCompletionResponse,
+) -> Option<TokenUsage> {
+    let answered_by = response.model.unwrap_or_else(|| model.to_string());
     if let Some(choices) = response.choices {
         for choice in choices {
             let role = choice.message.role;
@@ -42,10 +138,50 @@ pub async fn chat(api_key: &str, session: &mut Session) -> Result<()> {
                 id: "".to_string(),
                 role: Role::from_str(&role),
                 content: message,
+                attachments: vec![],
                 redaction_mapping: None,
+                model: Some(answered_by.clone()),
             });
         }
     }
 
-    Ok(())
+    response.usage.map(|usage| TokenUsage {
+        model: answered_by,
+        prompt_tokens: usage.prompt_tokens,
+        completion_tokens: usage.completion_tokens,
+    })
+}
+
+fn to_chat_message(message: &Message, uploads: &Uploads) -> ChatMessage {
+    let images = message.image_attachments();
+    let documents = message.document_attachments();
+    let content = if images.is_empty() && documents.is_empty() {
+        ChatContent::Text(message.content_with_context())
+    } else {
+        let mut parts = vec![ContentPart::Text {
+            text: message.content_with_context(),
+        }];
+        parts.extend(images.iter().filter_map(|image| {
+            image.image_data_url().map(|url| ContentPart::ImageUrl {
+                image_url: ImageUrl { url },
+            })
+        }));
+        parts.extend(documents.iter().filter_map(|document| {
+            let file = match uploads.get(&document.hash()) {
+                Some(file_id) => FileReference::Uploaded {
+                    file_id: file_id.clone(),
+                },
+                None => FileReference::Inline {
+                    filename: document.file_name(),
+                    file_data: document.document_data_url()?,
+                },
+            };
+            Some(ContentPart::File { file })
+        }));
+        ChatContent::Parts(parts)
+    };
+    ChatMessage {
+        role: message.role.to_string(),
+        content,
+    }
 }
@@ -0,0 +1,79 @@
+use crate::config::repository::ConfigRepository;
+use crate::guard::model::wrap_data;
+use crate::guard::service::guard_service::check_injection;
+use crate::models::service::models_service::configured_model;
+use crate::openai::model::role::Role;
+use crate::openai::service::chat::chat;
+use crate::output::progress::Progress;
+use crate::project::config::load_project_config;
+use crate::provider::service::provider_service::endpoint;
+use crate::session::model::session::Session;
+use crate::upload::model::Uploads;
+use crate::usage::model::pricing::price_for_model;
+use crate::usage::model::token_usage::estimate_tokens;
+use crate::usage::repository::UsageRepository;
+use crate::usage::service::{budget_service, usage_service};
+use anyhow::{anyhow, Result};
+use std::path::Path;
+
+pub async fn prompt<R: ConfigRepository, UR: UsageRepository>(
+    repo: &R,
+    usage_repo: &UR,
+    command: &str,
+    directory: &Path,
+    system_prompt: &str,
+    input: &str,
+) -> Result<String> {
+    let mut session = Session::new_temporary();
+    session.add_raw_message(system_prompt.to_string(), Role::System);
+    follow_up(
+        repo,
+        usage_repo,
+        command,
+        directory,
+        &mut session,
+        &wrap_data("input", input),
+    )
+    .await
+}
+
+pub async fn follow_up<R: ConfigRepository, UR: UsageRepository>(
+    repo: &R,
+    usage_repo: &UR,
+    command: &str,
+    directory: &Path,
+    session: &mut Session,
+    input: &str,
+) -> Result<String> {
+    let endpoint = endpoint(repo)?;
+    let model = configured_model(repo);
+
+    session.add_raw_message(input.to_string(), Role::User);
+    check_injection(repo, session)?;
+
+    let estimated_prompt_tokens = session
+        .messages
+        .iter()
+        .map(|message| estimate_tokens(&message.content_with_context()))
+        .sum();
+    let estimated_cost = price_for_model(&model).cost(estimated_prompt_tokens, 0);
+    budget_service::check_budget(repo, usage_repo, &session.id, estimated_cost, false)?;
+
+    session.redact(repo);
+
+    let sampling = load_project_config(directory).sampling_for(command);
+    let waiting = Progress::spinner("waiting for the answer");
+    let usage = chat(&endpoint, &model, &sampling, session, &Uploads::new()).await;
+    waiting.finish();
+    if let Some(usage) = usage? {
+        usage_service::record_usage(usage_repo, &session.id, &usage)?;
+    }
+    session.unredact();
+
+    session
+        .messages
+        .last()
+        .filter(|message| message.role == Role::Assistant)
+        .map(|message| message.content.clone())
+        .ok_or_else(|| anyhow!("no answer received"))
+}
@@ -0,0 +1,34 @@
+use colored::*;
+
+const OPENAI_ICON: &str = "◆";
+const UNKNOWN_ICON: &str = "○";
+
+const OPENAI_PREFIXES: [&str; 6] = ["gpt-", "o1", "o3", "o4", "chatgpt-", "davinci"];
+
+const FAMILY_COLORS: [(&str, Color); 6] = [
+    ("gpt-4o-mini", Color::BrightBlue),
+    ("gpt-4o", Color::Blue),
+    ("gpt-4", Color::Magenta),
+    ("gpt-3.5", Color::Yellow),
+    ("o1", Color::Cyan),
+    ("o3", Color::BrightCyan),
+];
+
+pub fn model_badge(model: &str) -> String {
+    let icon = if OPENAI_PREFIXES
+        .iter()
+        .any(|prefix| model.starts_with(prefix))
+    {
+        OPENAI_ICON
+    } else {
+        UNKNOWN_ICON
+    };
+    let badge = format!("{} {}", icon, model);
+    match FAMILY_COLORS
+        .iter()
+        .find(|(prefix, _)| model.starts_with(prefix))
+    {
+        Some((_, color)) => badge.color(*color).to_string(),
+        None => badge.dimmed().to_string(),
+    }
+}
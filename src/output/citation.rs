@@ -0,0 +1,42 @@
+use crate::output::hyperlink::{file_url, hyperlink, linkify, supports_hyperlinks};
+use colored::*;
+use regex::Regex;
+
+pub fn render_citations(line: &str, sources: &[String]) -> String {
+    let re = Regex::new(r"【([^】:]+)(?::(\d+(?:-\d+)?))?】").unwrap();
+    let links = supports_hyperlinks();
+    let mut rendered = String::new();
+    let mut last = 0;
+    for caps in re.captures_iter(line) {
+        let found = caps.get(0).unwrap();
+        rendered.push_str(&plain(&line[last..found.start()], links));
+        last = found.end();
+
+        let path = caps[1].trim();
+        let label = match caps.get(2) {
+            Some(lines) => format!("[{}:{}]", path, lines.as_str()),
+            None => format!("[{}]", path),
+        };
+        let citation = if !sources.iter().any(|source| source == path) {
+            format!("{} (not in context)", label)
+                .yellow()
+                .dimmed()
+                .to_string()
+        } else if links {
+            hyperlink(&file_url(path), &label.dimmed().to_string())
+        } else {
+            label.dimmed().to_string()
+        };
+        rendered.push_str(&citation);
+    }
+    rendered.push_str(&plain(&line[last..], links));
+    rendered
+}
+
+fn plain(text: &str, links: bool) -> String {
+    if links {
+        linkify(text).white().to_string()
+    } else {
+        text.white().to_string()
+    }
+}
@@ -0,0 +1,108 @@
+use crate::output::markdown::{terminal_width, truncate};
+use colored::*;
+use similar::{ChangeTag, TextDiff};
+
+const MIN_SIDE_WIDTH: usize = 20;
+
+pub fn print_word_diff(old: &str, new: &str) {
+    let diff = TextDiff::from_words(old, new);
+    let mut output = String::new();
+    for change in diff.iter_all_changes() {
+        let value = change.value();
+        let rendered = match change.tag() {
+            ChangeTag::Equal => value.normal(),
+            ChangeTag::Delete => value.red().strikethrough(),
+            ChangeTag::Insert => value.green().underline(),
+        };
+        output.push_str(&rendered.to_string());
+    }
+    println!("{}", output);
+
+    let ratio = diff.ratio();
+    println!();
+    println!(
+        "{}",
+        format!("similarity: {:.0}%", ratio * 100.0)
+            .white()
+            .dimmed()
+    );
+}
+
+pub fn render_diff(lines: &[&str], side_by_side: bool) -> Vec<String> {
+    if side_by_side {
+        render_side_by_side(lines)
+    } else {
+        lines.iter().map(|line| colorize(line)).collect()
+    }
+}
+
+fn colorize(line: &str) -> String {
+    if is_header(line) {
+        line.bold().to_string()
+    } else if line.starts_with("@@") {
+        line.cyan().to_string()
+    } else if line.starts_with('+') {
+        line.green().to_string()
+    } else if line.starts_with('-') {
+        line.red().to_string()
+    } else {
+        line.to_string()
+    }
+}
+
+fn is_header(line: &str) -> bool {
+    ["--- ", "+++ ", "diff ", "index "]
+        .iter()
+        .any(|prefix| line.starts_with(prefix))
+}
+
+fn render_side_by_side(lines: &[&str]) -> Vec<String> {
+    let column = (terminal_width().saturating_sub(3) / 2).max(MIN_SIDE_WIDTH);
+    let mut rendered = Vec::new();
+    let mut removed: Vec<&str> = Vec::new();
+    let mut added: Vec<&str> = Vec::new();
+
+    let row = |left: Option<&str>, right: Option<&str>, changed: bool| {
+        let cell =
+            |text: Option<&str>| format!("{:<column$}", truncate(text.unwrap_or(""), column));
+        let (left_cell, right_cell) = (cell(left), cell(right));
+        if changed {
+            format!(
+                "{} {} {}",
+                left_cell.red(),
+                "│".dimmed(),
+                right_cell.green()
+            )
+        } else {
+            format!("{} {} {}", left_cell, "│".dimmed(), right_cell)
+        }
+    };
+    let flush = |removed: &mut Vec<&str>, added: &mut Vec<&str>, rendered: &mut Vec<String>| {
+        for index in 0..removed.len().max(added.len()) {
+            rendered.push(row(
+                removed.get(index).copied(),
+                added.get(index).copied(),
+                true,
+            ));
+        }
+        removed.clear();
+        added.clear();
+    };
+
+    for line in lines {
+        if is_header(line) || line.starts_with("@@") {
+            flush(&mut removed, &mut added, &mut rendered);
+            rendered.push(colorize(line));
+        } else if let Some(line) = line.strip_prefix('-') {
+            removed.push(line);
+        } else if let Some(line) = line.strip_prefix('+') {
+            added.push(line);
+        } else {
+            flush(&mut removed, &mut added, &mut rendered);
+            let context = line.strip_prefix(' ').unwrap_or(line);
+            rendered.push(row(Some(context), Some(context), false));
+        }
+    }
+    flush(&mut removed, &mut added, &mut rendered);
+    rendered
+}
@@ -0,0 +1,108 @@
+use std::sync::OnceLock;
+use syntect::easy::HighlightLines;
+use syntect::highlighting::{Style, Theme, ThemeSet};
+use syntect::parsing::{SyntaxDefinition, SyntaxReference, SyntaxSet};
+use syntect::util::as_24_bit_terminal_escaped;
+
+pub const DEFAULT_THEME: &str = "base16-ocean.dark";
+pub const THEMES: [&str; 7] = [
+    "base16-ocean.dark",
+    "base16-eighties.dark",
+    "base16-mocha.dark",
+    "base16-ocean.light",
+    "InspiredGitHub",
+    "Solarized (dark)",
+    "Solarized (light)",
+];
+
+const EXTRA_SYNTAXES: [(&str, &str); 4] = [
+    ("toml", include_str!("syntaxes/toml.sublime-syntax")),
+    (
+        "dockerfile",
+        include_str!("syntaxes/dockerfile.sublime-syntax"),
+    ),
+    ("hcl", include_str!("syntaxes/hcl.sublime-syntax")),
+    ("proto", include_str!("syntaxes/protobuf.sublime-syntax")),
+];
+
+const ALIASES: [(&str, &str); 14] = [
+    ("docker", "dockerfile"),
+    ("containerfile", "dockerfile"),
+    ("terraform", "hcl"),
+    ("tf", "hcl"),
+    ("protobuf", "proto"),
+    ("postgres", "sql"),
+    ("postgresql", "sql"),
+    ("psql", "sql"),
+    ("mysql", "sql"),
+    ("sqlite", "sql"),
+    ("plsql", "sql"),
+    ("tsql", "sql"),
+    ("shell", "sh"),
+    ("zsh", "sh"),
+];
+
+static SYNTAXES: OnceLock<SyntaxSet> = OnceLock::new();
+static THEME_SET: OnceLock<ThemeSet> = OnceLock::new();
+
+fn syntaxes() -> &'static SyntaxSet {
+    SYNTAXES.get_or_init(|| {
+        let mut builder = SyntaxSet::load_defaults_newlines().into_builder();
+        for (name, definition) in EXTRA_SYNTAXES {
+            match SyntaxDefinition::load_from_str(definition, true, Some(name)) {
+                Ok(syntax) => builder.add(syntax),
+                Err(err) => eprintln!("could not load the {} syntax: {:?}", name, err),
+            }
+        }
+        builder.build()
+    })
+}
+
+fn theme(name: &str) -> &'static Theme {
+    let themes = &THEME_SET.get_or_init(ThemeSet::load_defaults).themes;
+    themes.get(name).unwrap_or_else(|| &themes[DEFAULT_THEME])
+}
+
+fn syntax_for(language: &str) -> &'static SyntaxReference {
+    let syntaxes = syntaxes();
+    let language = language.to_lowercase();
+    let language = ALIASES
+        .iter()
+        .find(|(alias, _)| *alias == language)
+        .map(|(_, target)| *target)
+        .unwrap_or(&language);
+    syntaxes
+        .find_syntax_by_token(language)
+        .or_else(|| syntaxes.find_syntax_by_name(language))
+        .unwrap_or_else(|| syntaxes.find_syntax_plain_text())
+}
+
+pub struct CodeHighlighter {
+    lines: HighlightLines<'static>,
+}
+
+impl CodeHighlighter {
+    pub fn new(language: Option<&str>, theme_name: &str) -> Self {
+        let syntax = match language {
+            Some(language) if !language.is_empty() => syntax_for(language),
+            _ => syntaxes().find_syntax_plain_text(),
+        };
+        Self {
+            lines: HighlightLines::new(syntax, theme(theme_name)),
+        }
+    }
+
+    pub fn highlight(&mut self, line: &str) -> String {
+        let line = format!("{}\n", line);
+        match self.lines.highlight_line(&line, syntaxes()) {
+            Ok(ranges) => {
+                let ranges: Vec<(Style, &str)> = ranges;
+                format!(
+                    "{}\x1b[0m",
+                    as_24_bit_terminal_escaped(&ranges, false).trim_end_matches('\n')
+                )
+            }
+            Err(_) => line.trim_end_matches('\n').to_string(),
+        }
+    }
+}
@@ -0,0 +1,77 @@
+use regex::Regex;
+use std::io::{self, IsTerminal};
+use std::path::Path;
+
+const HYPERLINK_OVERRIDE: &str = "TERMAI_HYPERLINKS";
+const SUPPORTED_TERM_PROGRAMS: [&str; 6] =
+    ["iTerm.app", "WezTerm", "vscode", "Hyper", "ghostty", "rio"];
+const SUPPORTED_TERMS: [&str; 5] = [
+    "xterm-kitty",
+    "alacritty",
+    "foot",
+    "xterm-ghostty",
+    "wezterm",
+];
+const TRAILING_PUNCTUATION: &[char] = &[',', '.', ';', ':', ')', '?', '!', '"', '\''];
+
+pub fn supports_hyperlinks() -> bool {
+    if let Ok(value) = std::env::var(HYPERLINK_OVERRIDE) {
+        return value != "0" && value != "false";
+    }
+    if !io::stdout().is_terminal() {
+        return false;
+    }
+    let env = |key: &str| std::env::var(key).unwrap_or_default();
+    let term = env("TERM");
+    if term == "dumb" || term.starts_with("screen") || std::env::var("TMUX").is_ok() {
+        return false;
+    }
+    SUPPORTED_TERM_PROGRAMS.contains(&env("TERM_PROGRAM").as_str())
+        || SUPPORTED_TERMS
+            .iter()
+            .any(|supported| term.starts_with(supported))
+        || env("VTE_VERSION")
+            .parse::<u32>()
+            .is_ok_and(|version| version >= 5000)
+        || std::env::var("WT_SESSION").is_ok()
+        || std::env::var("KONSOLE_VERSION").is_ok()
+        || std::env::var("KITTY_WINDOW_ID").is_ok()
+}
+
+pub fn hyperlink(target: &str, text: &str) -> String {
+    format!("\x1b]8;;{}\x1b\\{}\x1b]8;;\x1b\\", target, text)
+}
+
+pub fn file_url(path: &str) -> String {
+    let path = Path::new(path);
+    let absolute = path
+        .canonicalize()
+        .or_else(|_| std::env::current_dir().map(|dir| dir.join(path)))
+        .map(|path| path.to_string_lossy().to_string())
+        .unwrap_or_else(|_| path.to_string_lossy().to_string());
+    format!("file://{}", absolute)
+}
+
+pub fn linkify(text: &str) -> String {
+    let re =
+        Regex::new(r"https?://\S+|(?:\.{0,2}/)?(?:[\w.-]+/)*[\w-][\w.-]*\.\w+(?::\d+)?").unwrap();
+    let mut linked = String::new();
+    let mut last = 0;
+    for found in re.find_iter(text) {
+        let candidate = found.as_str().trim_end_matches(TRAILING_PUNCTUATION);
+        let end = found.start() + candidate.len();
+        let target = if candidate.starts_with("http://") || candidate.starts_with("https://") {
+            Some(candidate.to_string())
+        } else {
+            let path = candidate.split(':').next().unwrap_or(candidate);
+            Path::new(path).is_file().then(|| file_url(path))
+        };
+        if let Some(target) = target {
+            linked.push_str(&text[last..found.start()]);
+            linked.push_str(&hyperlink(&target, candidate));
+            last = end;
+        }
+    }
+    linked.push_str(&text[last..]);
+    linked
+}
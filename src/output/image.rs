@@ -0,0 +1,92 @@
+use colored::*;
+use std::io::{self, IsTerminal};
+use std::path::Path;
+
+const KITTY_CHUNK_SIZE: usize = 4096;
+const ITERM_TERM_PROGRAMS: [&str; 2] = ["iTerm.app", "WezTerm"];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ImageProtocol {
+    Kitty,
+    Iterm,
+}
+
+pub fn detect_image_protocol() -> Option<ImageProtocol> {
+    if !io::stdout().is_terminal() || std::env::var("TMUX").is_ok() {
+        return None;
+    }
+    let env = |key: &str| std::env::var(key).unwrap_or_default();
+    if env("TERM") == "xterm-kitty"
+        || env("TERM") == "xterm-ghostty"
+        || std::env::var("KITTY_WINDOW_ID").is_ok()
+    {
+        return Some(ImageProtocol::Kitty);
+    }
+    if ITERM_TERM_PROGRAMS.contains(&env("TERM_PROGRAM").as_str()) {
+        return Some(ImageProtocol::Iterm);
+    }
+    None
+}
+
+pub fn render_image(protocol: Option<ImageProtocol>, path: &str, base64: &str) -> String {
+    let png = Path::new(path)
+        .extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| e.eq_ignore_ascii_case("png"));
+    match protocol {
+        Some(ImageProtocol::Kitty) if png => kitty(base64),
+        Some(ImageProtocol::Iterm) => iterm(base64),
+        _ => placeholder(base64),
+    }
+}
+
+fn kitty(base64: &str) -> String {
+    let chunks = base64
+        .as_bytes()
+        .chunks(KITTY_CHUNK_SIZE)
+        .collect::<Vec<_>>();
+    let mut rendered = String::new();
+    for (index, chunk) in chunks.iter().enumerate() {
+        let more = if index + 1 < chunks.len() { 1 } else { 0 };
+        let control = if index == 0 {
+            format!("a=T,f=100,m={}", more)
+        } else {
+            format!("m={}", more)
+        };
+        rendered.push_str(&format!(
+            "\x1b_G{};{}\x1b\\",
+            control,
+            String::from_utf8_lossy(chunk)
+        ));
+    }
+    rendered
+}
+
+fn iterm(base64: &str) -> String {
+    format!(
+        "\x1b]1337;File=inline=1;size={};preserveAspectRatio=1:{}\x07",
+        decoded_size(base64),
+        base64
+    )
+}
+
+pub fn render_document(base64: &str) -> String {
+    format!("[pdf, {} KB]", decoded_size(base64).div_ceil(1024))
+        .dimmed()
+        .to_string()
+}
+
+fn placeholder(base64: &str) -> String {
+    format!("[image, {} KB]", decoded_size(base64).div_ceil(1024))
+        .dimmed()
+        .to_string()
+}
+
+fn decoded_size(base64: &str) -> usize {
+    let padding = base64
+        .bytes()
+        .rev()
+        .take_while(|byte| *byte == b'=')
+        .count();
+    (base64.len() * 3 / 4).saturating_sub(padding)
+}
@@ -0,0 +1,246 @@
+use crate::output::citation::render_citations;
+use crate::output::math;
+use colored::*;
+use regex::Regex;
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+const DEFAULT_WIDTH: usize = 100;
+const MIN_COLUMN_WIDTH: usize = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Alignment {
+    Left,
+    Center,
+    Right,
+}
+
+pub fn render(lines: &[&str], sources: &[String]) -> Vec<String> {
+    let footnote_definition = Regex::new(r"^\[\^([^\]]+)\]:\s*(.*)$").unwrap();
+    let mut rendered = Vec::new();
+    let mut footnotes: Vec<(String, String)> = Vec::new();
+    let mut index = 0;
+
+    while index < lines.len() {
+        let line = lines[index];
+        if is_table_row(line) && lines.get(index + 1).is_some_and(|next| is_separator(next)) {
+            let mut end = index + 2;
+            while end < lines.len() && is_table_row(lines[end]) {
+                end += 1;
+            }
+            rendered.extend(render_table(line, lines[index + 1], &lines[index + 2..end]));
+            index = end;
+            continue;
+        }
+        if line.trim() == "$$" {
+            let mut end = index + 1;
+            while end < lines.len() && lines[end].trim() != "$$" {
+                end += 1;
+            }
+            for math_line in &lines[index + 1..end] {
+                rendered.push(format!("  {}", math::render_block_line(math_line)));
+            }
+            index = end + 1;
+            continue;
+        }
+        if let Some(caps) = footnote_definition.captures(line) {
+            footnotes.push((caps[1].to_string(), caps[2].to_string()));
+            index += 1;
+            continue;
+        }
+        rendered.push(render_line(&math::render_inline(line), sources));
+        index += 1;
+    }
+
+    if !footnotes.is_empty() {
+        while rendered.last().is_some_and(|line| line.trim().is_empty()) {
+            rendered.pop();
+        }
+        rendered.push(String::new());
+        for (id, text) in footnotes {
+            rendered.push(format!(
+                "{} {}",
+                format!("[{}]", id).dimmed(),
+                render_citations(&render_footnote_refs(&text), sources).dimmed()
+            ));
+        }
+    }
+    rendered
+}
+
+fn render_line(line: &str, sources: &[String]) -> String {
+    let trimmed = line.trim_start();
+    if trimmed.starts_with('>') {
+        let mut depth = 0;
+        let mut rest = trimmed;
+        while let Some(stripped) = rest.strip_prefix('>') {
+            depth += 1;
+            rest = stripped.trim_start();
+        }
+        return format!(
+            "{}{}",
+            "│ ".repeat(depth).dimmed(),
+            render_citations(&render_footnote_refs(rest), sources).italic()
+        );
+    }
+
+    let indent = &line[..line.len() - trimmed.len()];
+    for (marker, checked) in [
+        ("- [ ] ", false),
+        ("* [ ] ", false),
+        ("- [x] ", true),
+        ("- [X] ", true),
+        ("* [x] ", true),
+        ("* [X] ", true),
+    ] {
+        if let Some(task) = trimmed.strip_prefix(marker) {
+            let box_char = if checked {
+                "☑".green()
+            } else {
+                "☐".normal()
+            };
+            return format!(
+                "{}{} {}",
+                indent,
+                box_char,
+                render_citations(&render_footnote_refs(task), sources)
+            );
+        }
+    }
+    render_citations(&render_footnote_refs(line), sources)
+}
+
+fn render_footnote_refs(text: &str) -> String {
+    let reference = Regex::new(r"\[\^([^\]]+)\]").unwrap();
+    reference.replace_all(text, "[$1]").to_string()
+}
+
+fn is_table_row(line: &str) -> bool {
+    let trimmed = line.trim();
+    trimmed.starts_with('|') && trimmed.len() > 1
+}
+
+fn is_separator(line: &str) -> bool {
+    let cells = split_row(line);
+    !cells.is_empty()
+        && cells.iter().all(|cell| {
+            let cell = cell.trim();
+            cell.len() >= 3 && cell.contains("---") && cell.chars().all(|c| c == '-' || c == ':')
+        })
+}
+
+fn split_row(line: &str) -> Vec<String> {
+    let trimmed = line.trim().trim_start_matches('|');
+    let trimmed = trimmed.strip_suffix('|').unwrap_or(trimmed);
+    trimmed
+        .split('|')
+        .map(|cell| cell.trim().to_string())
+        .collect()
+}
+
+fn alignment(cell: &str) -> Alignment {
+    let cell = cell.trim();
+    match (cell.starts_with(':'), cell.ends_with(':')) {
+        (true, true) => Alignment::Center,
+        (false, true) => Alignment::Right,
+        _ => Alignment::Left,
+    }
+}
+
+pub fn terminal_width() -> usize {
+    std::env::var("COLUMNS")
+        .ok()
+        .and_then(|columns| columns.parse::<usize>().ok())
+        .unwrap_or(DEFAULT_WIDTH)
+}
+
+fn render_table(header: &str, separator: &str, rows: &[&str]) -> Vec<String> {
+    let header = split_row(header);
+    let alignments = split_row(separator)
+        .iter()
+        .map(|cell| alignment(cell))
+        .collect::<Vec<Alignment>>();
+    let rows = rows.iter().map(|row| split_row(row)).collect::<Vec<_>>();
+    let columns = header.len();
+
+    let mut widths = vec![MIN_COLUMN_WIDTH; columns];
+    for row in std::iter::once(&header).chain(rows.iter()) {
+        for (column, cell) in row.iter().take(columns).enumerate() {
+            widths[column] = widths[column].max(cell.width());
+        }
+    }
+    let available = terminal_width().saturating_sub(3 * columns + 1);
+    while widths.iter().sum::<usize>() > available {
+        let Some((widest, _)) = widths
+            .iter()
+            .enumerate()
+            .filter(|(_, width)| **width > MIN_COLUMN_WIDTH)
+            .max_by_key(|(_, width)| **width)
+        else {
+            break;
+        };
+        widths[widest] -= 1;
+    }
+
+    let format_row = |row: &[String]| {
+        let cells = (0..columns)
+            .map(|column| {
+                let cell = row.get(column).map(String::as_str).unwrap_or("");
+                let align = alignments.get(column).copied().unwrap_or(Alignment::Left);
+                pad(&truncate(cell, widths[column]), widths[column], align)
+            })
+            .collect::<Vec<String>>();
+        format!("│ {} │", cells.join(" │ "))
+    };
+    let border = |left: &str, middle: &str, right: &str| {
+        let segments = widths
+            .iter()
+            .map(|width| "─".repeat(width + 2))
+            .collect::<Vec<String>>();
+        format!("{}{}{}", left, segments.join(middle), right)
+            .dimmed()
+            .to_string()
+    };
+
+    let mut rendered = vec![
+        border("┌", "┬", "┐"),
+        format_row(&header).bold().to_string(),
+    ];
+    rendered.push(border("├", "┼", "┤"));
+    for row in &rows {
+        rendered.push(format_row(row));
+    }
+    rendered.push(border("└", "┴", "┘"));
+    rendered
+}
+
+pub fn truncate(cell: &str, width: usize) -> String {
+    if cell.width() <= width {
+        return cell.to_string();
+    }
+    let mut truncated = String::new();
+    let mut used = 0;
+    for c in cell.chars() {
+        let char_width = c.width().unwrap_or(0);
+        if used + char_width > width - 1 {
+            break;
+        }
+        used += char_width;
+        truncated.push(c);
+    }
+    truncated.push('…');
+    truncated
+}
+
+fn pad(cell: &str, width: usize, align: Alignment) -> String {
+    let padding = width.saturating_sub(cell.width());
+    match align {
+        Alignment::Left => format!("{}{}", cell, " ".repeat(padding)),
+        Alignment::Right => format!("{}{}", " ".repeat(padding), cell),
+        Alignment::Center => format!(
+            "{}{}{}",
+            " ".repeat(padding / 2),
+            cell,
+            " ".repeat(padding - padding / 2)
+        ),
+    }
+}
@@ -0,0 +1,353 @@
+use regex::Regex;
+
+const SYMBOLS: [(&str, &str); 98] = [
+    ("alpha", "α"),
+    ("beta", "β"),
+    ("gamma", "γ"),
+    ("delta", "δ"),
+    ("epsilon", "ε"),
+    ("varepsilon", "ε"),
+    ("zeta", "ζ"),
+    ("eta", "η"),
+    ("theta", "θ"),
+    ("iota", "ι"),
+    ("kappa", "κ"),
+    ("lambda", "λ"),
+    ("mu", "μ"),
+    ("nu", "ν"),
+    ("xi", "ξ"),
+    ("pi", "π"),
+    ("rho", "ρ"),
+    ("sigma", "σ"),
+    ("tau", "τ"),
+    ("upsilon", "υ"),
+    ("phi", "φ"),
+    ("varphi", "φ"),
+    ("chi", "χ"),
+    ("psi", "ψ"),
+    ("omega", "ω"),
+    ("Gamma", "Γ"),
+    ("Delta", "Δ"),
+    ("Theta", "Θ"),
+    ("Lambda", "Λ"),
+    ("Xi", "Ξ"),
+    ("Pi", "Π"),
+    ("Sigma", "Σ"),
+    ("Phi", "Φ"),
+    ("Psi", "Ψ"),
+    ("Omega", "Ω"),
+    ("times", "×"),
+    ("cdot", "·"),
+    ("div", "÷"),
+    ("pm", "±"),
+    ("mp", "∓"),
+    ("le", "≤"),
+    ("leq", "≤"),
+    ("ge", "≥"),
+    ("geq", "≥"),
+    ("ne", "≠"),
+    ("neq", "≠"),
+    ("approx", "≈"),
+    ("sim", "∼"),
+    ("equiv", "≡"),
+    ("propto", "∝"),
+    ("ll", "≪"),
+    ("gg", "≫"),
+    ("infty", "∞"),
+    ("sum", "∑"),
+    ("prod", "∏"),
+    ("int", "∫"),
+    ("partial", "∂"),
+    ("nabla", "∇"),
+    ("forall", "∀"),
+    ("exists", "∃"),
+    ("in", "∈"),
+    ("notin", "∉"),
+    ("subset", "⊂"),
+    ("subseteq", "⊆"),
+    ("supset", "⊃"),
+    ("cup", "∪"),
+    ("cap", "∩"),
+    ("emptyset", "∅"),
+    ("land", "∧"),
+    ("wedge", "∧"),
+    ("lor", "∨"),
+    ("vee", "∨"),
+    ("neg", "¬"),
+    ("oplus", "⊕"),
+    ("to", "→"),
+    ("rightarrow", "→"),
+    ("leftarrow", "←"),
+    ("Rightarrow", "⇒"),
+    ("implies", "⇒"),
+    ("Leftrightarrow", "⇔"),
+    ("iff", "⇔"),
+    ("mapsto", "↦"),
+    ("lfloor", "⌊"),
+    ("rfloor", "⌋"),
+    ("lceil", "⌈"),
+    ("rceil", "⌉"),
+    ("langle", "⟨"),
+    ("rangle", "⟩"),
+    ("ldots", "…"),
+    ("dots", "…"),
+    ("cdots", "⋯"),
+    ("mid", "|"),
+    ("log", "log"),
+    ("ln", "ln"),
+    ("exp", "exp"),
+    ("max", "max"),
+    ("min", "min"),
+    ("quad", "  "),
+];
+
+const SUPERSCRIPTS: [(char, char); 27] = [
+    ('0', '⁰'),
+    ('1', '¹'),
+    ('2', '²'),
+    ('3', '³'),
+    ('4', '⁴'),
+    ('5', '⁵'),
+    ('6', '⁶'),
+    ('7', '⁷'),
+    ('8', '⁸'),
+    ('9', '⁹'),
+    ('+', '⁺'),
+    ('-', '⁻'),
+    ('=', '⁼'),
+    ('(', '⁽'),
+    (')', '⁾'),
+    ('a', 'ᵃ'),
+    ('b', 'ᵇ'),
+    ('c', 'ᶜ'),
+    ('d', 'ᵈ'),
+    ('e', 'ᵉ'),
+    ('i', 'ⁱ'),
+    ('k', 'ᵏ'),
+    ('m', 'ᵐ'),
+    ('n', 'ⁿ'),
+    ('t', 'ᵗ'),
+    ('x', 'ˣ'),
+    ('y', 'ʸ'),
+];
+
+const SUBSCRIPTS: [(char, char); 26] = [
+    ('0', '₀'),
+    ('1', '₁'),
+    ('2', '₂'),
+    ('3', '₃'),
+    ('4', '₄'),
+    ('5', '₅'),
+    ('6', '₆'),
+    ('7', '₇'),
+    ('8', '₈'),
+    ('9', '₉'),
+    ('+', '₊'),
+    ('-', '₋'),
+    ('=', '₌'),
+    ('(', '₍'),
+    (')', '₎'),
+    ('a', 'ₐ'),
+    ('e', 'ₑ'),
+    ('i', 'ᵢ'),
+    ('j', 'ⱼ'),
+    ('k', 'ₖ'),
+    ('m', 'ₘ'),
+    ('n', 'ₙ'),
+    ('o', 'ₒ'),
+    ('r', 'ᵣ'),
+    ('t', 'ₜ'),
+    ('x', 'ₓ'),
+];
+
+const FRACTIONS: [(&str, &str, &str); 9] = [
+    ("1", "2", "½"),
+    ("1", "3", "⅓"),
+    ("2", "3", "⅔"),
+    ("1", "4", "¼"),
+    ("3", "4", "¾"),
+    ("1", "5", "⅕"),
+    ("1", "6", "⅙"),
+    ("1", "8", "⅛"),
+    ("1", "10", "⅒"),
+];
+
+const DOUBLE_STRUCK: [(char, char); 5] =
+    [('R', 'ℝ'), ('N', 'ℕ'), ('Z', 'ℤ'), ('Q', 'ℚ'), ('C', 'ℂ')];
+
+pub fn render_inline(line: &str) -> String {
+    if !line.contains('$') {
+        return line.to_string();
+    }
+    let re = Regex::new(r"\$\$([^$]+)\$\$|\$([^\s$](?:[^$]*[^\s$])?)\$").unwrap();
+    let mut rendered = String::new();
+    let mut last = 0;
+    for caps in re.captures_iter(line) {
+        let found = caps.get(0).unwrap();
+        if caps.get(2).is_some()
+            && line[found.end()..]
+                .chars()
+                .next()
+                .is_some_and(|next| next.is_ascii_digit())
+        {
+            continue;
+        }
+        let source = caps.get(1).or_else(|| caps.get(2)).unwrap().as_str();
+        rendered.push_str(&line[last..found.start()]);
+        match to_unicode(source) {
+            Some(math) => rendered.push_str(&math),
+            None => rendered.push_str(found.as_str()),
+        }
+        last = found.end();
+    }
+    rendered.push_str(&line[last..]);
+    rendered
+}
+
+pub fn render_block_line(line: &str) -> String {
+    to_unicode(line.trim()).unwrap_or_else(|| line.to_string())
+}
+
+pub fn to_unicode(source: &str) -> Option<String> {
+    let mut parser = Parser {
+        chars: source.chars().collect(),
+        pos: 0,
+    };
+    let converted = parser.parse(false)?;
+    Some(converted.split_whitespace().collect::<Vec<_>>().join(" "))
+}
+
+struct Parser {
+    chars: Vec<char>,
+    pos: usize,
+}
+
+impl Parser {
+    fn next(&mut self) -> Option<char> {
+        let c = self.chars.get(self.pos).copied();
+        self.pos += 1;
+        c
+    }
+
+    fn parse(&mut self, in_group: bool) -> Option<String> {
+        let mut out = String::new();
+        loop {
+            match self.next() {
+                None if in_group => return None,
+                None => return Some(out),
+                Some('}') if in_group => return Some(out),
+                Some('}') => return None,
+                Some('{') => out.push_str(&self.parse(true)?),
+                Some('\\') => out.push_str(&self.command()?),
+                Some('^') => out.push_str(&script(&self.argument()?, &SUPERSCRIPTS, '^')),
+                Some('_') => out.push_str(&script(&self.argument()?, &SUBSCRIPTS, '_')),
+                Some('&') => {}
+                Some(c) => out.push(c),
+            }
+        }
+    }
+
+    fn argument(&mut self) -> Option<String> {
+        while self.chars.get(self.pos).is_some_and(|c| c.is_whitespace()) {
+            self.pos += 1;
+        }
+        match self.next()? {
+            '{' => self.parse(true),
+            '\\' => self.command(),
+            c => Some(c.to_string()),
+        }
+    }
+
+    fn command(&mut self) -> Option<String> {
+        let start = self.pos;
+        while self
+            .chars
+            .get(self.pos)
+            .is_some_and(|c| c.is_ascii_alphabetic())
+        {
+            self.pos += 1;
+        }
+        let name: String = self.chars[start..self.pos].iter().collect();
+        if name.is_empty() {
+            return match self.next()? {
+                '\\' => Some(String::new()),
+                ',' | ';' | ':' | '!' | ' ' => Some(" ".to_string()),
+                c => Some(c.to_string()),
+            };
+        }
+        let converted = self.control_word(&name)?;
+        let mut next = self.pos;
+        while self.chars.get(next).is_some_and(|c| *c == ' ') {
+            next += 1;
+        }
+        if converted
+            .chars()
+            .all(|c| c.is_alphabetic() && !c.is_ascii())
+            && self.chars.get(next).is_some_and(|c| c.is_alphanumeric())
+        {
+            self.pos = next;
+        }
+        Some(converted)
+    }
+
+    fn control_word(&mut self, name: &str) -> Option<String> {
+        match name {
+            "frac" | "dfrac" | "tfrac" => {
+                let numerator = self.argument()?;
+                let denominator = self.argument()?;
+                Some(fraction(&numerator, &denominator))
+            }
+            "sqrt" => Some(format!("√{}", wrap(&self.argument()?))),
+            "text" | "mathrm" | "mathit" | "mathbf" | "operatorname" | "textbf" => self.argument(),
+            "mathbb" => Some(
+                self.argument()?
+                    .chars()
+                    .map(|c| {
+                        DOUBLE_STRUCK
+                            .iter()
+                            .find(|(plain, _)| *plain == c)
+                            .map_or(c, |(_, struck)| *struck)
+                    })
+                    .collect(),
+            ),
+            "left" | "right" | "displaystyle" => Some(String::new()),
+            "begin" | "end" => self.argument().map(|_| String::new()),
+            _ => SYMBOLS
+                .iter()
+                .find(|(symbol, _)| *symbol == name)
+                .map(|(_, unicode)| unicode.to_string()),
+        }
+    }
+}
+
+fn script(text: &str, table: &[(char, char)], marker: char) -> String {
+    let mapped: Option<String> = text
+        .chars()
+        .map(|c| table.iter().find(|(plain, _)| *plain == c).map(|(_, s)| *s))
+        .collect();
+    match mapped {
+        Some(mapped) if !text.is_empty() => mapped,
+        _ if text.chars().count() == 1 => format!("{}{}", marker, text),
+        _ => format!("{}({})", marker, text),
+    }
+}
+
+fn fraction(numerator: &str, denominator: &str) -> String {
+    let numerator = numerator.trim();
+    let denominator = denominator.trim();
+    match FRACTIONS
+        .iter()
+        .find(|(n, d, _)| *n == numerator && *d == denominator)
+    {
+        Some((_, _, vulgar)) => vulgar.to_string(),
+        None => format!("{}/{}", wrap(numerator), wrap(denominator)),
+    }
+}
+
+fn wrap(text: &str) -> String {
+    if text.chars().all(|c| c.is_alphanumeric() || c == '.') {
+        text.to_string()
+    } else {
+        format!("({})", text)
+    }
+}
@@ -1,15 +1,10 @@
 use crate::openai::model::role::Role;
+use crate::session::model::attachment::Attachment;
 
 pub struct Message {
+    pub id: String,
     pub role: Role,
     pub message: String,
-}
-
-impl Message {
-    pub fn copy_with_message(&self, message: String) -> Self {
-        Self {
-            role: self.role.clone(),
-            message,
-        }
-    }
+    pub attachments: Vec<Attachment>,
+    pub model: Option<String>,
 }
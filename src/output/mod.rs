@@ -1,2 +1,13 @@
+pub(crate) mod badge;
+pub(crate) mod citation;
+pub(crate) mod diff;
+pub(crate) mod highlighting;
+pub(crate) mod hyperlink;
+pub(crate) mod image;
+pub(crate) mod markdown;
+pub(crate) mod math;
 pub(crate) mod message;
 pub(crate) mod outputter;
+pub(crate) mod progress;
+pub(crate) mod report;
+pub(crate) mod stream;
@@ -1,55 +1,143 @@
+use crate::openai::model::role::Role;
+use crate::output::badge::model_badge;
+use crate::output::diff::render_diff;
+use crate::output::highlighting::CodeHighlighter;
+use crate::output::image::{render_document, render_image, ImageProtocol};
+use crate::output::markdown;
+use crate::output::math;
 use crate::output::message::Message;
+use crate::patch::model::is_diff_language;
+use crate::session::model::attachment::AttachmentKind;
 use colored::*;
-use syntect::easy::HighlightLines;
-use syntect::highlighting::{Style, ThemeSet};
-use syntect::parsing::SyntaxSet;
-use syntect::util::as_24_bit_terminal_escaped;
 
-pub fn print(messages: Vec<Message>) {
-    println!();
+pub struct OutputOptions {
+    pub theme: String,
+    pub side_by_side: bool,
+    pub session: String,
+    pub images: Option<ImageProtocol>,
+}
 
-    let ps = SyntaxSet::load_defaults_newlines();
-    let ts = ThemeSet::load_defaults();
-    let theme = &ts.themes["base16-ocean.dark"];
+pub fn print(messages: Vec<Message>, sources: &[String], options: &OutputOptions) {
+    println!();
 
     for message in messages {
-        match message.role.to_string().as_str() {
-            "user" => print!("{}", "user".green().bold()),
-            "system" => print!("{}", "system".cyan().bold()),
-            "assistant" => print!("{}", "assistant".magenta().bold()),
-            _ => print!("{}", message.role.to_string().yellow().bold()),
-        }
-        println!(":");
+        print_header(&message.role, message.model.as_deref());
 
         let lines = message.message.split('\n');
-        let mut in_code = false;
-        let mut h = HighlightLines::new(ps.find_syntax_by_extension("rs").unwrap(), theme);
+        let mut highlighter: Option<CodeHighlighter> = None;
+        let mut in_math = false;
+        let mut diff: Option<Vec<&str>> = None;
+        let mut diff_count = 0;
+        let mut prose: Vec<&str> = Vec::new();
 
         for line in lines {
-            if line.trim_start().starts_with("```") {
-                in_code = !in_code;
-                if in_code {
-                    println!(
-                        "{}",
-                        "────────────────────────────────────".white().dimmed()
-                    );
-                } else {
-                    println!(
-                        "{}",
-                        "────────────────────────────────────".white().dimmed()
-                    );
+            if let Some(info) = line.trim_start().strip_prefix("```") {
+                print_prose(&mut prose, sources);
+                let language = info.split_whitespace().next();
+                if in_math || (highlighter.is_none() && language == Some("math")) {
+                    in_math = !in_math;
+                    continue;
+                }
+                if let Some(diff_lines) = diff.take() {
+                    for rendered in render_diff(&diff_lines, options.side_by_side) {
+                        println!("{}", rendered);
+                    }
+                    print_separator();
+                    continue;
+                }
+                if highlighter.is_none() && is_diff_language(language) {
+                    diff_count += 1;
+                    diff = Some(Vec::new());
+                    if message.role == Role::Assistant {
+                        println!(
+                            "{}",
+                            format!(
+                                "── diff {} · apply with `termai apply-diff {} {} --block {}`",
+                                diff_count,
+                                options.session,
+                                &message.id[..message.id.len().min(8)],
+                                diff_count
+                            )
+                            .white()
+                            .dimmed()
+                        );
+                    } else {
+                        print_separator();
+                    }
+                    continue;
                 }
+                highlighter = match highlighter {
+                    Some(_) => None,
+                    None => Some(CodeHighlighter::new(language, &options.theme)),
+                };
+                print_separator();
                 continue;
             }
 
-            if in_code {
-                let ranges: Vec<(Style, &str)> = h.highlight_line(line, &ps).unwrap();
-                let escaped = as_24_bit_terminal_escaped(&ranges, false);
-                println!("{}", escaped);
+            if let Some(diff_lines) = diff.as_mut() {
+                diff_lines.push(line);
+            } else if in_math {
+                println!("  {}", math::render_block_line(line));
+            } else if let Some(highlighter) = highlighter.as_mut() {
+                println!("{}", highlighter.highlight(line));
+            } else {
+                prose.push(line);
+            }
+        }
+        if let Some(diff_lines) = diff.take() {
+            for rendered in render_diff(&diff_lines, options.side_by_side) {
+                println!("{}", rendered);
+            }
+        }
+        print_prose(&mut prose, sources);
+
+        for attachment in &message.attachments {
+            println!();
+            println!(
+                "{} {}",
+                format!("{}:", attachment.kind).cyan().bold(),
+                attachment.path.cyan()
+            );
+            if attachment.is_text() {
+                for line in attachment.content.lines() {
+                    println!("{}", line.white().dimmed());
+                }
+            } else if attachment.kind == AttachmentKind::Document {
+                println!("{}", render_document(&attachment.content));
             } else {
-                println!("{}", line.white());
+                println!(
+                    "{}",
+                    render_image(options.images, &attachment.path, &attachment.content)
+                );
             }
         }
         println!();
     }
 }
+
+pub fn print_header(role: &Role, model: Option<&str>) {
+    match role.to_string().as_str() {
+        "user" => print!("{}", "user".green().bold()),
+        "system" => print!("{}", "system".cyan().bold()),
+        "assistant" => print!("{}", "assistant".magenta().bold()),
+        _ => print!("{}", role.to_string().yellow().bold()),
+    }
+    if let Some(model) = model {
+        print!(" {}", model_badge(model));
+    }
+    println!(":");
+}
+
+fn print_prose(prose: &mut Vec<&str>, sources: &[String]) {
+    for line in markdown::render(prose, sources) {
+        println!("{}", line);
+    }
+    prose.clear();
+}
+
+fn print_separator() {
+    println!(
+        "{}",
+        "────────────────────────────────────".white().dimmed()
+    );
+}
@@ -0,0 +1,77 @@
+use indicatif::{ProgressBar, ProgressStyle};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+static QUIET: AtomicBool = AtomicBool::new(false);
+
+pub fn set_quiet(quiet: bool) {
+    QUIET.store(quiet, Ordering::Relaxed);
+}
+
+pub fn is_quiet() -> bool {
+    QUIET.load(Ordering::Relaxed)
+}
+
+pub fn status(message: &str) {
+    if !is_quiet() {
+        eprintln!("{}", message);
+    }
+}
+
+pub struct Progress {
+    bar: Option<ProgressBar>,
+}
+
+impl Progress {
+    pub fn steps(total: usize, label: &str) -> Self {
+        Self::start(label, || {
+            ProgressBar::new(total as u64).with_style(
+                ProgressStyle::with_template("{prefix} [{bar:24}] {pos}/{len} {msg}")
+                    .unwrap()
+                    .progress_chars("=> "),
+            )
+        })
+    }
+
+    pub fn spinner(label: &str) -> Self {
+        Self::start(label, || {
+            let bar = ProgressBar::new_spinner()
+                .with_style(ProgressStyle::with_template("{spinner} {prefix} {msg}").unwrap());
+            bar.enable_steady_tick(Duration::from_millis(100));
+            bar
+        })
+    }
+
+    fn start(label: &str, bar: impl FnOnce() -> ProgressBar) -> Self {
+        if is_quiet() {
+            return Self { bar: None };
+        }
+        let bar = bar();
+        bar.set_prefix(label.to_string());
+        Self { bar: Some(bar) }
+    }
+
+    pub fn message(&self, message: &str) {
+        if let Some(bar) = &self.bar {
+            bar.set_message(message.to_string());
+        }
+    }
+
+    pub fn advance(&self) {
+        if let Some(bar) = &self.bar {
+            bar.inc(1);
+        }
+    }
+
+    pub fn finish(&self) {
+        if let Some(bar) = &self.bar {
+            bar.finish_and_clear();
+        }
+    }
+}
+
+impl Drop for Progress {
+    fn drop(&mut self) {
+        self.finish();
+    }
+}
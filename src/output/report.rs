@@ -0,0 +1,160 @@
+use crate::output::markdown::truncate;
+use anyhow::{anyhow, Result};
+use colored::*;
+use serde::Serialize;
+use std::cmp::Reverse;
+use std::fs;
+use std::path::Path;
+use std::time::Duration;
+
+const TOP_FILES: usize = 10;
+const FILE_COLUMN_WIDTH: usize = 48;
+
+#[derive(Serialize)]
+pub struct Report {
+    pub title: String,
+    pub counts: Vec<StatusCount>,
+    pub files: Vec<ReportFile>,
+    pub tokens: u32,
+    pub cost: f64,
+    pub duration_secs: u64,
+}
+
+#[derive(Serialize)]
+pub struct StatusCount {
+    pub status: String,
+    pub count: usize,
+}
+
+#[derive(Serialize)]
+pub struct ReportFile {
+    pub path: String,
+    pub status: String,
+    pub detail: String,
+    pub lines_changed: u32,
+}
+
+impl Report {
+    pub fn new(
+        title: String,
+        files: Vec<ReportFile>,
+        tokens: u32,
+        cost: f64,
+        duration: Duration,
+    ) -> Self {
+        let mut counts: Vec<StatusCount> = Vec::new();
+        for file in &files {
+            match counts.iter_mut().find(|count| count.status == file.status) {
+                Some(count) => count.count += 1,
+                None => counts.push(StatusCount {
+                    status: file.status.clone(),
+                    count: 1,
+                }),
+            }
+        }
+        Self {
+            title,
+            counts,
+            files,
+            tokens,
+            cost,
+            duration_secs: duration.as_secs(),
+        }
+    }
+
+    pub fn print(&self) {
+        println!("{}", self.title.bold());
+        let counts = self
+            .counts
+            .iter()
+            .map(|count| format!("{} {}", colored_status(&count.status), count.count))
+            .collect::<Vec<String>>();
+        if !counts.is_empty() {
+            println!("  {}", counts.join("   "));
+        }
+        println!(
+            "  {} files, {} tokens, ${:.4}, {}",
+            self.files.len(),
+            self.tokens,
+            self.cost,
+            self.duration()
+        );
+
+        let mut top = self.files.iter().collect::<Vec<&ReportFile>>();
+        top.sort_by_key(|file| (file.status != "failed", Reverse(file.lines_changed)));
+        if top.is_empty() {
+            return;
+        }
+        println!("  top files:");
+        for file in top.iter().take(TOP_FILES) {
+            println!(
+                "    {:<width$}  {:<9}  {}",
+                truncate(&file.path, FILE_COLUMN_WIDTH),
+                colored_status(&file.status),
+                file.detail,
+                width = FILE_COLUMN_WIDTH
+            );
+        }
+        if top.len() > TOP_FILES {
+            println!(
+                "    {}",
+                format!(
+                    "... and {} more, --report lists all of them",
+                    top.len() - TOP_FILES
+                )
+                .dimmed()
+            );
+        }
+    }
+
+    pub fn write(&self, path: &str) -> Result<()> {
+        let content = match Path::new(path).extension().and_then(|ext| ext.to_str()) {
+            Some("json") => serde_json::to_string_pretty(self)?,
+            _ => self.to_markdown(),
+        };
+        fs::write(path, content).map_err(|err| anyhow!("could not write {}: {:?}", path, err))?;
+        println!("report written to {}", path);
+        Ok(())
+    }
+
+    fn to_markdown(&self) -> String {
+        let mut markdown = format!("# {}\n\n", self.title);
+        for count in &self.counts {
+            markdown.push_str(&format!("- {}: {}\n", count.status, count.count));
+        }
+        markdown.push_str(&format!(
+            "- tokens: {}\n- cost: ${:.4}\n- duration: {}\n\n",
+            self.tokens,
+            self.cost,
+            self.duration()
+        ));
+        if !self.files.is_empty() {
+            markdown.push_str("| file | status | detail |\n| --- | --- | --- |\n");
+            for file in &self.files {
+                markdown.push_str(&format!(
+                    "| {} | {} | {} |\n",
+                    file.path,
+                    file.status,
+                    file.detail.replace('|', "\\|")
+                ));
+            }
+        }
+        markdown
+    }
+
+    fn duration(&self) -> String {
+        format!(
+            "{}m{:02}s",
+            self.duration_secs / 60,
+            self.duration_secs % 60
+        )
+    }
+}
+
+fn colored_status(status: &str) -> ColoredString {
+    match status {
+        "failed" => status.red(),
+        "changed" | "passed" | "new" => status.green(),
+        _ => status.dimmed(),
+    }
+}
@@ -0,0 +1,49 @@
+use crate::redactions::revert::unredact;
+use std::collections::HashMap;
+use std::io::{self, Write};
+
+pub struct StreamPrinter {
+    redactions: Vec<HashMap<String, String>>,
+    pending: String,
+}
+
+impl StreamPrinter {
+    pub fn new(redactions: Vec<HashMap<String, String>>) -> Self {
+        Self {
+            redactions,
+            pending: String::new(),
+        }
+    }
+
+    // Text is held back up to the last whitespace so a redaction id split across
+    // two deltas is restored before it reaches the terminal.
+    pub fn push(&mut self, delta: &str) {
+        self.pending.push_str(delta);
+        if let Some((start, whitespace)) = self
+            .pending
+            .char_indices()
+            .rfind(|(_, c)| c.is_whitespace())
+        {
+            let ready = self
+                .pending
+                .drain(..start + whitespace.len_utf8())
+                .collect::<String>();
+            self.print(&ready);
+        }
+    }
+
+    pub fn finish(&mut self) {
+        let rest = std::mem::take(&mut self.pending);
+        self.print(&rest);
+        println!();
+    }
+
+    fn print(&self, text: &str) {
+        let text = self
+            .redactions
+            .iter()
+            .fold(text.to_string(), |text, mapping| unredact(mapping, &text));
+        print!("{}", text);
+        let _ = io::stdout().flush();
+    }
+}
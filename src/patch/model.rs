@@ -0,0 +1,84 @@
+use crate::snippet::model::code_block::extract_code_blocks;
+
+const DIFF_LANGUAGES: [&str; 2] = ["diff", "patch"];
+const RENAME_HEADERS: [&str; 4] = ["rename from ", "rename to ", "copy from ", "copy to "];
+
+pub fn is_diff_language(language: Option<&str>) -> bool {
+    language.is_some_and(|language| DIFF_LANGUAGES.contains(&language.to_lowercase().as_str()))
+}
+
+pub fn diff_blocks(content: &str) -> Vec<String> {
+    extract_code_blocks(content)
+        .into_iter()
+        .filter(|block| is_diff_language(block.language.as_deref()))
+        .map(|block| block.content)
+        .collect()
+}
+
+pub fn strip_level(diff: &str) -> usize {
+    let prefixed = diff
+        .lines()
+        .filter_map(|line| {
+            line.strip_prefix("--- ")
+                .or_else(|| line.strip_prefix("+++ "))
+        })
+        .map(|path| path.split('\t').next().unwrap_or(path).trim())
+        .filter(|path| *path != "/dev/null")
+        .all(|path| path.starts_with("a/") || path.starts_with("b/"));
+    if prefixed {
+        1
+    } else {
+        0
+    }
+}
+
+// Both sides of every file header, a deletion only names its file after `---`
+// and a rename or copy names the files on its own lines, without a/ and b/.
+pub fn patched_files(diff: &str) -> Vec<String> {
+    let strip = strip_level(diff);
+    let mut files: Vec<String> = Vec::new();
+    for line in diff.lines() {
+        let path = if let Some(path) = line
+            .strip_prefix("--- ")
+            .or_else(|| line.strip_prefix("+++ "))
+        {
+            let path = path.split('\t').next().unwrap_or(path).trim();
+            if path == "/dev/null" {
+                continue;
+            }
+            path.splitn(strip + 1, '/').last().unwrap_or(path)
+        } else if let Some(path) = RENAME_HEADERS
+            .iter()
+            .find_map(|header| line.strip_prefix(header))
+        {
+            path.trim()
+        } else {
+            continue;
+        };
+        if !files.iter().any(|file| file == path) {
+            files.push(path.to_string());
+        }
+    }
+    files
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn both_sides_of_every_header_are_patched() {
+        let edit = "--- a/src/main.rs\n+++ b/src/main.rs\n@@ -1 +1 @@\n-a\n+b\n";
+        assert_eq!(patched_files(edit), vec!["src/main.rs"]);
+
+        let deletion = "--- a/../outside.rs\n+++ /dev/null\n@@ -1 +0,0 @@\n-a\n";
+        assert_eq!(patched_files(deletion), vec!["../outside.rs"]);
+
+        let creation = "--- /dev/null\n+++ new.rs\n@@ -0,0 +1 @@\n+a\n";
+        assert_eq!(patched_files(creation), vec!["new.rs"]);
+
+        let rename =
+            "diff --git a/x b/../y\nsimilarity index 100%\nrename from x\nrename to ../y\n";
+        assert_eq!(patched_files(rename), vec!["x", "../y"]);
+    }
+}
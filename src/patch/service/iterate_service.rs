@@ -0,0 +1,285 @@
+use crate::changes::model::ChangeOrigin;
+use crate::changes::service::changes_service::{record_change, snapshot_files};
+use crate::common::terminal::{ask, is_interactive};
+use crate::config::repository::ConfigRepository;
+use crate::git::repository::{repo_root, run_git};
+use crate::openai::model::role::Role;
+use crate::openai::service::prompt::follow_up;
+use crate::output::markdown::truncate;
+use crate::output::progress::status;
+use crate::output::report::{Report, ReportFile};
+use crate::patch::model::{diff_blocks, patched_files};
+use crate::patch::service::patch_service::apply_patch;
+use crate::path::reference::expand_file_references;
+use crate::project::config::{load_project_config, SandboxConfig};
+use crate::sandbox::model::policy::escapes_directory;
+use crate::sandbox::repository::AuditRepository;
+use crate::sandbox::service::sandbox_service::run_sandboxed;
+use crate::session::model::session::Session;
+use crate::usage::model::token_usage::{total_cost, total_tokens};
+use crate::usage::model::watchdog::Watchdog;
+use crate::usage::repository::UsageRepository;
+use crate::usage::service::usage_service::session_usage;
+use anyhow::{anyhow, Result};
+use colored::*;
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::Path;
+
+const MAX_OUTPUT_CHARS: usize = 6000;
+const SOURCE_TASK_WIDTH: usize = 40;
+
+const ITERATE_PROMPT: &str = "
+You are a developer working in a git repository until a verification command passes.
+Reply with the changes as unified diffs in ```diff code blocks, relative to the repository directory, with enough context lines for `git apply`.
+Before the diffs, explain in one or two sentences what you change and why.
+When the command output shows a failure, fix the cause instead of the test.";
+
+pub struct IterateInput<'a> {
+    pub task: &'a str,
+    pub run: &'a str,
+    pub max_iterations: u32,
+    pub max_tokens: u32,
+    pub max_minutes: u64,
+    pub confirm_every: Option<u32>,
+    pub report: Option<&'a str>,
+    pub directory: &'a Path,
+}
+
+pub async fn iterate<R: ConfigRepository, UR: UsageRepository, AR: AuditRepository>(
+    repo: &R,
+    usage_repo: &UR,
+    audit_repo: &AR,
+    input: &IterateInput<'_>,
+) -> Result<()> {
+    repo_root(input.directory)?;
+    let watchdog = Watchdog::new(input.max_tokens, input.max_minutes, input.confirm_every);
+    let sandbox = load_project_config(input.directory).sandbox;
+    let (task, files) = expand_file_references(input.task);
+    let mut message = format!("Task: {}\nVerification command: {}", task, input.run);
+    for file in files.iter().filter(|file| !file.is_binary()) {
+        message = format!("{}\n\n{}\n```\n{}\n```", message, file.path, file.content);
+    }
+
+    let mut session = Session::new_temporary();
+    session.add_raw_message(ITERATE_PROMPT.to_string(), Role::System);
+    let mut changed = BTreeSet::new();
+    let mut passed = false;
+    let mut stopped = None;
+    let mut iterations = 0;
+    while iterations < input.max_iterations {
+        let usage = session_usage(usage_repo, &session.id);
+        if let Some(reason) = watchdog.exceeded(&usage) {
+            stopped = Some(reason);
+            break;
+        }
+        if watchdog.checkpoint_due(iterations)
+            && !confirm_continue(iterations, &watchdog.summary(&usage))?
+        {
+            stopped = Some(format!("stopped after {} iteration(s)", iterations));
+            break;
+        }
+        iterations += 1;
+        status(&format!(
+            "iteration {}/{}, spent {}",
+            iterations,
+            input.max_iterations,
+            watchdog.summary(&usage)
+        ));
+        let answer = follow_up(
+            repo,
+            usage_repo,
+            "iterate",
+            input.directory,
+            &mut session,
+            &message,
+        )
+        .await?;
+        let blocks = diff_blocks(&answer);
+        if blocks.is_empty() {
+            message = "Your answer had no ```diff block, reply with the changes as a unified diff."
+                .to_string();
+            continue;
+        }
+
+        let source = format!(
+            "iterate round {}/{}: {}",
+            iterations,
+            input.max_iterations,
+            truncate(input.task, SOURCE_TASK_WIDTH)
+        );
+        if let Some(failed) =
+            apply_blocks(&blocks, input.directory, &sandbox, &source, &mut changed)
+        {
+            println!("{}", "the proposed diff does not apply".yellow());
+            message = failed;
+            continue;
+        }
+        if let Some(explanation) = answer.split("```").next() {
+            println!("{}", explanation.trim());
+        }
+
+        let (success, output) =
+            run_command(audit_repo, &sandbox, input.run, input.directory, &source)?;
+        if success {
+            passed = true;
+            break;
+        }
+        println!("{}", format!("`{}` failed", input.run).red());
+        message = format!(
+            "`{}` still fails:\n```\n{}\n```\nFix it with another diff.",
+            input.run, output
+        );
+    }
+
+    println!();
+    let summary = match passed {
+        true => format!("`{}` passes after {} iteration(s)", input.run, iterations).green(),
+        false => format!(
+            "`{}` did not pass after {} iteration(s)",
+            input.run, iterations
+        )
+        .red(),
+    };
+    println!("{}", summary.bold());
+    let usage = session_usage(usage_repo, &session.id);
+    let report = Report::new(
+        format!("iterate: {}", truncate(input.task, SOURCE_TASK_WIDTH)),
+        changed_rows(input.directory, &changed),
+        total_tokens(&usage),
+        total_cost(&usage),
+        watchdog.elapsed(),
+    );
+    report.print();
+    if let Some(path) = input.report {
+        report.write(path)?;
+    }
+    if !passed {
+        return Err(anyhow!(stopped.unwrap_or_else(|| format!(
+            "the iteration cap of {} was hit",
+            input.max_iterations
+        ))));
+    }
+    Ok(())
+}
+
+fn changed_rows(directory: &Path, changed: &BTreeSet<String>) -> Vec<ReportFile> {
+    changed
+        .iter()
+        .map(|path| {
+            let numstat =
+                run_git(directory, &["diff", "--numstat", "--", path]).unwrap_or_default();
+            let counts = numstat
+                .split_whitespace()
+                .take(2)
+                .filter_map(|count| count.parse::<u32>().ok())
+                .collect::<Vec<u32>>();
+            match counts[..] {
+                [added, removed] => ReportFile {
+                    path: path.clone(),
+                    status: "changed".to_string(),
+                    detail: format!("+{} -{}", added, removed),
+                    lines_changed: added + removed,
+                },
+                _ => {
+                    let lines = fs::read_to_string(directory.join(path))
+                        .map(|content| content.lines().count() as u32)
+                        .unwrap_or_default();
+                    ReportFile {
+                        path: path.clone(),
+                        status: "new".to_string(),
+                        detail: format!("+{}", lines),
+                        lines_changed: lines,
+                    }
+                }
+            }
+        })
+        .collect()
+}
+
+fn confirm_continue(iterations: u32, spent: &str) -> Result<bool> {
+    if !is_interactive() {
+        return Err(anyhow!(
+            "not running in a terminal, leave out --confirm-every to run without checkpoints"
+        ));
+    }
+    ask(
+        &format!(
+            "{} iteration(s) done, spent {}. continue?",
+            iterations, spent
+        ),
+        false,
+    )
+}
+
+fn apply_blocks(
+    blocks: &[String],
+    directory: &Path,
+    sandbox: &SandboxConfig,
+    source: &str,
+    changed: &mut BTreeSet<String>,
+) -> Option<String> {
+    for diff in blocks {
+        if sandbox.jail {
+            if let Some(path) = patched_files(diff)
+                .into_iter()
+                .find(|path| escapes_directory(directory, path))
+            {
+                return Some(format!(
+                    "{} is outside the directory you may change, only change files under it.",
+                    path
+                ));
+            }
+        }
+        let snapshots = match snapshot_files(directory, diff) {
+            Ok(snapshots) => snapshots,
+            Err(err) => {
+                return Some(format!(
+                    "{}, so this diff and the ones after it were not applied.",
+                    err
+                ))
+            }
+        };
+        if let Err(err) = apply_patch(diff, false, directory) {
+            let current = patched_files(diff)
+                .iter()
+                .filter_map(|path| {
+                    let content = fs::read_to_string(directory.join(path)).ok()?;
+                    Some(format!("{}\n```\n{}\n```", path, content))
+                })
+                .collect::<Vec<String>>()
+                .join("\n\n");
+            return Some(format!(
+                "{}\nThe diffs before this one were applied, this one and the ones after it were not. Current content of its files:\n\n{}",
+                err, current
+            ));
+        }
+        let origin = ChangeOrigin {
+            source: source.to_string(),
+            session: None,
+            message_id: None,
+        };
+        record_change(directory, diff, origin, snapshots);
+        changed.extend(patched_files(diff));
+    }
+    None
+}
+
+fn run_command<AR: AuditRepository>(
+    audit_repo: &AR,
+    sandbox: &SandboxConfig,
+    command: &str,
+    directory: &Path,
+    source: &str,
+) -> Result<(bool, String)> {
+    status(&format!("running `{}`", command));
+    let output = run_sandboxed(audit_repo, sandbox, command, directory, source)?;
+    let combined = format!(
+        "{}{}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let skip = combined.chars().count().saturating_sub(MAX_OUTPUT_CHARS);
+    let tail = combined.chars().skip(skip).collect::<String>();
+    Ok((output.status.success(), tail))
+}
@@ -0,0 +1,2 @@
+pub(crate) mod iterate_service;
+pub(crate) mod patch_service;
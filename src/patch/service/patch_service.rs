@@ -0,0 +1,86 @@
+use crate::changes::model::ChangeOrigin;
+use crate::changes::service::changes_service::{record_change, snapshot_files};
+use crate::git::repository::run_git;
+use crate::patch::model::{diff_blocks, strip_level};
+use crate::session::model::session::Session;
+use crate::session::service::sessions_service::assistant_message;
+use anyhow::{anyhow, Result};
+use colored::*;
+use std::io::Write;
+use std::path::Path;
+use tempfile::NamedTempFile;
+
+pub fn apply_diff(
+    session: &Session,
+    answer: &str,
+    block: usize,
+    check: bool,
+    directory: &Path,
+) -> Result<()> {
+    let message = assistant_message(session, answer)?;
+    let blocks = diff_blocks(&message.content);
+    let diff = block
+        .checked_sub(1)
+        .and_then(|index| blocks.get(index))
+        .ok_or_else(|| {
+            anyhow!(
+                "answer '{}' has {} diff block(s), there is no block {}",
+                answer,
+                blocks.len(),
+                block
+            )
+        })?;
+
+    let snapshots = snapshot_files(directory, diff)?;
+    apply_patch(diff, check, directory)?;
+    if check {
+        println!(
+            "{} to {}",
+            "diff applies cleanly".green(),
+            directory.display()
+        );
+        return Ok(());
+    }
+
+    let origin = ChangeOrigin {
+        source: "apply-diff".to_string(),
+        session: Some(session.name.clone()),
+        message_id: Some(message.id.clone()),
+    };
+    println!("{} to {}", "diff applied".green(), directory.display());
+    if record_change(directory, diff, origin, snapshots).is_some() {
+        println!("undo it with `termai undo`");
+    }
+    Ok(())
+}
+
+pub fn apply_patch(diff: &str, check: bool, directory: &Path) -> Result<()> {
+    git_apply(diff, check, false, directory)
+        .map_err(|err| anyhow!("the diff does not apply cleanly: {}", err))
+}
+
+pub fn reverse_patch(diff: &str, check: bool, directory: &Path) -> Result<()> {
+    git_apply(diff, check, true, directory)
+        .map_err(|err| anyhow!("the change can not be reverted cleanly: {}", err))
+}
+
+fn git_apply(diff: &str, check: bool, reverse: bool, directory: &Path) -> Result<()> {
+    // a fresh file that only this user can read, removed when it goes out of scope
+    let patch_file = NamedTempFile::with_prefix("termai-")
+        .and_then(|mut file| file.write_all(diff.as_bytes()).map(|_| file))
+        .map_err(|err| anyhow!("could not write the patch file: {:?}", err))?;
+    let strip = format!("-p{}", strip_level(diff));
+    let patch_path = patch_file.path().to_string_lossy().to_string();
+    let mut args = vec!["apply", "--recount", &strip];
+    if reverse {
+        args.push("--reverse");
+    }
+    args.push(&patch_path);
+    let mut check_args = args.clone();
+    check_args.insert(1, "--check");
+    match (run_git(directory, &check_args), check) {
+        (Err(err), _) => Err(err),
+        (Ok(_), true) => Ok(()),
+        (Ok(_), false) => run_git(directory, &args).map(|_| ()),
+    }
+}
@@ -0,0 +1,134 @@
+use crate::path::model::Files;
+use crate::project::config::ContextConfig;
+use crate::usage::model::token_usage::estimate_tokens;
+use std::path::Path;
+
+const DOC_EXTENSIONS: [&str; 6] = ["md", "markdown", "txt", "rst", "adoc", "org"];
+const DIFF_EXTENSIONS: [&str; 2] = ["diff", "patch"];
+const MIN_TRUNCATED_TOKENS: u32 = 64;
+pub const PIPED_INPUT_NAME: &str = "stdin";
+pub const TRUNCATED_MARKER: &str = "... [truncated to fit the context budget]";
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ContextCategory {
+    Code,
+    Diff,
+    Docs,
+}
+
+struct ContextItem {
+    category: ContextCategory,
+    path: String,
+    content: String,
+}
+
+pub fn apply_budget(
+    config: &ContextConfig,
+    files: Vec<Files>,
+    piped_input: Option<String>,
+) -> (Vec<Files>, Option<String>) {
+    let max_tokens = match config.max_tokens {
+        Some(max_tokens) => max_tokens,
+        None => return (files, piped_input),
+    };
+
+    let (binaries, files): (Vec<Files>, Vec<Files>) =
+        files.into_iter().partition(|file| file.is_binary());
+    let mut items = files
+        .into_iter()
+        .map(|file| ContextItem {
+            category: category_for_path(&file.path),
+            path: file.path,
+            content: file.content,
+        })
+        .collect::<Vec<ContextItem>>();
+
+    let mut prompt_input = None;
+    match piped_input {
+        Some(piped) if looks_like_diff(&piped) => items.push(ContextItem {
+            category: ContextCategory::Diff,
+            path: PIPED_INPUT_NAME.to_string(),
+            content: piped,
+        }),
+        piped => prompt_input = piped,
+    }
+
+    let shares = [
+        (ContextCategory::Code, config.budget.code),
+        (ContextCategory::Diff, config.budget.diff),
+        (ContextCategory::Docs, config.budget.docs),
+    ];
+    let present_share: u32 = shares
+        .iter()
+        .filter(|(category, _)| items.iter().any(|item| item.category == *category))
+        .map(|(_, share)| share)
+        .sum();
+
+    let mut kept = Vec::new();
+    let mut omitted = Vec::new();
+    for (category, share) in shares {
+        if present_share == 0 {
+            break;
+        }
+        let mut remaining = (max_tokens as u64 * share as u64 / present_share as u64) as u32;
+        for item in items.iter().filter(|item| item.category == category) {
+            let tokens = estimate_tokens(&item.content);
+            if tokens <= remaining {
+                remaining -= tokens;
+                kept.push((item.path.clone(), item.content.clone()));
+            } else if remaining >= MIN_TRUNCATED_TOKENS || item.path == PIPED_INPUT_NAME {
+                let tokens = remaining.max(MIN_TRUNCATED_TOKENS);
+                kept.push((item.path.clone(), truncate(&item.content, tokens)));
+                remaining = 0;
+            } else {
+                omitted.push(item.path.clone());
+            }
+        }
+    }
+
+    if !omitted.is_empty() {
+        eprintln!(
+            "context budget of {} tokens exceeded, left out: {}",
+            max_tokens,
+            omitted.join(", ")
+        );
+    }
+
+    let mut files = Vec::new();
+    for (path, content) in kept {
+        if path == PIPED_INPUT_NAME && prompt_input.is_none() {
+            prompt_input = Some(content);
+        } else {
+            files.push(Files { path, content });
+        }
+    }
+    files.extend(binaries);
+    (files, prompt_input)
+}
+
+fn category_for_path(path: &str) -> ContextCategory {
+    let extension = Path::new(path)
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(|e| e.to_lowercase())
+        .unwrap_or_default();
+    if DIFF_EXTENSIONS.contains(&extension.as_str()) {
+        ContextCategory::Diff
+    } else if DOC_EXTENSIONS.contains(&extension.as_str()) {
+        ContextCategory::Docs
+    } else {
+        ContextCategory::Code
+    }
+}
+
+fn looks_like_diff(content: &str) -> bool {
+    content.starts_with("diff ") || content.lines().any(|line| line.starts_with("@@ "))
+}
+
+fn truncate(content: &str, tokens: u32) -> String {
+    let truncated = content
+        .chars()
+        .take(tokens as usize * 4)
+        .collect::<String>();
+    format!("{}\n{}\n", truncated, TRUNCATED_MARKER)
+}
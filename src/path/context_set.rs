@@ -0,0 +1,88 @@
+use crate::path::model::Files;
+use crate::project::config::{find_project_config, ProjectConfig};
+use anyhow::{anyhow, Result};
+use glob::Pattern;
+use std::fs;
+use std::path::Path;
+
+pub fn context_set_files(
+    start: &Path,
+    config: &ProjectConfig,
+    name: &str,
+    exclude: &[String],
+) -> Result<Vec<Files>> {
+    let set = config.context.sets.get(name).ok_or_else(|| {
+        let names = config
+            .context
+            .sets
+            .keys()
+            .map(String::as_str)
+            .collect::<Vec<&str>>();
+        match names.is_empty() {
+            true => anyhow!(
+                "unknown context set '{}', define one under [context.sets.{}] in .termai.toml",
+                name,
+                name
+            ),
+            false => anyhow!(
+                "unknown context set '{}', available: {}",
+                name,
+                names.join(", ")
+            ),
+        }
+    })?;
+    let root = find_project_config(start)
+        .and_then(|path| path.parent().map(Path::to_path_buf))
+        .ok_or_else(|| anyhow!("no .termai.toml found for context set '{}'", name))?;
+    let excluded = set
+        .exclude
+        .iter()
+        .map(|pattern| Pattern::new(pattern))
+        .collect::<Result<Vec<Pattern>, _>>()
+        .map_err(|err| anyhow!("invalid exclude pattern in context set '{}': {}", name, err))?;
+    let current_dir = std::env::current_dir()?;
+
+    let mut files: Vec<Files> = Vec::new();
+    for pattern in &set.include {
+        let full = match pattern.ends_with("**") {
+            true => root.join(format!("{}/*", pattern)),
+            false => root.join(pattern),
+        };
+        let paths = glob::glob(&full.to_string_lossy())
+            .map_err(|err| anyhow!("invalid include pattern '{}': {}", pattern, err))?;
+        for path in paths.flatten().filter(|path| path.is_file()) {
+            let relative = path.strip_prefix(&root).unwrap_or(&path);
+            if excluded
+                .iter()
+                .any(|pattern| pattern.matches_path(relative))
+                || is_excluded(relative, exclude)
+            {
+                continue;
+            }
+            let display = display_path(&path, &current_dir);
+            if files.iter().any(|file| file.path == display) {
+                continue;
+            }
+            if let Ok(content) = fs::read_to_string(&path) {
+                files.push(Files {
+                    path: display,
+                    content,
+                });
+            }
+        }
+    }
+    Ok(files)
+}
+
+fn is_excluded(relative: &Path, exclude: &[String]) -> bool {
+    exclude
+        .iter()
+        .any(|excluded| relative.starts_with(excluded))
+}
+
+fn display_path(path: &Path, current_dir: &Path) -> String {
+    path.strip_prefix(current_dir)
+        .unwrap_or(path)
+        .to_string_lossy()
+        .to_string()
+}
@@ -53,10 +53,7 @@ fn collect_files(path: &Path, files: &mut Vec<Files>, exclude: &[String]) {
 }
 
 fn must_exclude(exclude: &[String], path: &str) -> bool {
-    if exclude.contains(&path.to_string()) {
-        return true;
-    }
-    return false;
+    exclude.contains(&path.to_string())
 }
 
 fn remove_dot_slash(path: &str) -> &str {
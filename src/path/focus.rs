@@ -0,0 +1,77 @@
+use crate::path::model::Files;
+use anyhow::{anyhow, Result};
+use std::fs;
+
+pub struct Focus {
+    pub path: String,
+    pub start: usize,
+    pub end: usize,
+}
+
+impl Focus {
+    pub fn parse(value: &str) -> Result<Self> {
+        let invalid = || anyhow!("'{}' is not in the form file:line or file:start-end", value);
+        let (path, lines) = value.rsplit_once(':').ok_or_else(invalid)?;
+        let (start, end) = match lines.split_once('-') {
+            Some((start, end)) => (start, end),
+            None => (lines, lines),
+        };
+        let start = start.trim().parse::<usize>().map_err(|_| invalid())?;
+        let end = end.trim().parse::<usize>().map_err(|_| invalid())?;
+        if path.is_empty() || start == 0 || end < start {
+            return Err(invalid());
+        }
+        Ok(Self {
+            path: path.strip_prefix("./").unwrap_or(path).to_string(),
+            start,
+            end,
+        })
+    }
+
+    pub fn read(&self) -> Result<Files> {
+        let content = fs::read_to_string(&self.path)
+            .map_err(|err| anyhow!("could not read {}: {:?}", self.path, err))?;
+        let line_count = content.lines().count();
+        if self.start > line_count {
+            return Err(anyhow!(
+                "{} has {} lines, line {} is past the end",
+                self.path,
+                line_count,
+                self.start
+            ));
+        }
+        Ok(Files {
+            path: self.path.clone(),
+            content,
+        })
+    }
+
+    pub fn excerpt(&self, content: &str) -> String {
+        let lines = content
+            .lines()
+            .enumerate()
+            .skip(self.start - 1)
+            .take(self.end + 1 - self.start)
+            .map(|(index, line)| format!("{:>4} | {}", index + 1, line))
+            .collect::<Vec<String>>();
+        let range = match self.start == self.end {
+            true => format!("line {}", self.start),
+            false => format!("lines {}-{}", self.start, self.end),
+        };
+        format!(
+            "I am looking at {} {} in my editor, answer about this code:\n```\n{}\n```",
+            self.path,
+            range,
+            lines.join("\n")
+        )
+    }
+}
+
+pub fn with_focused_file(files: Vec<Files>, focused: Files) -> Vec<Files> {
+    let mut files = files
+        .into_iter()
+        .filter(|file| file.path != focused.path)
+        .collect::<Vec<Files>>();
+    files.insert(0, focused);
+    files
+}
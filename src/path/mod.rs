@@ -1,2 +1,6 @@
+pub(crate) mod budget;
+pub(crate) mod context_set;
 pub(crate) mod extract;
-pub(crate) mod model;
\ No newline at end of file
+pub(crate) mod focus;
+pub(crate) mod model;
+pub(crate) mod reference;pub(crate) mod sample;
@@ -1,5 +1,46 @@
-#[derive(Debug)]
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+pub const IMAGE_EXTENSIONS: [&str; 5] = ["png", "jpg", "jpeg", "gif", "webp"];
+pub const DOCUMENT_EXTENSIONS: [&str; 1] = ["pdf"];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Files {
     pub path: String,
     pub content: String
-}
\ No newline at end of file
+}
+
+impl Files {
+    pub fn is_image(&self) -> bool {
+        is_image_path(&self.path)
+    }
+
+    pub fn is_document(&self) -> bool {
+        is_document_path(&self.path)
+    }
+
+    // Images and documents hold base64 encoded bytes, not text.
+    pub fn is_binary(&self) -> bool {
+        self.is_image() || self.is_document()
+    }
+}
+
+pub fn is_image_path(path: &str) -> bool {
+    has_extension(path, &IMAGE_EXTENSIONS)
+}
+
+pub fn is_document_path(path: &str) -> bool {
+    has_extension(path, &DOCUMENT_EXTENSIONS)
+}
+
+pub fn is_binary_path(path: &str) -> bool {
+    is_image_path(path) || is_document_path(path)
+}
+
+fn has_extension(path: &str, extensions: &[&str]) -> bool {
+    Path::new(path)
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(|e| extensions.contains(&e.to_lowercase().as_str()))
+        .unwrap_or(false)
+}
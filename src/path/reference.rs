@@ -0,0 +1,74 @@
+use crate::path::model::{is_binary_path, Files};
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use regex::Regex;
+use std::fs;
+use std::path::Path;
+
+const TRAILING_PUNCTUATION: &[char] = &[',', '.', ';', ':', ')', '?', '!', '"', '\''];
+
+pub fn expand_file_references(input: &str) -> (String, Vec<Files>) {
+    let re = Regex::new(r"(^|\s)@(\S+)").unwrap();
+    let mut files: Vec<Files> = Vec::new();
+
+    let expanded = re.replace_all(input, |caps: &regex::Captures| {
+        let prefix = &caps[1];
+        let reference = caps[2].trim_end_matches(TRAILING_PUNCTUATION);
+        let suffix = &caps[2][reference.len()..];
+
+        let referenced = resolve_reference(reference);
+        if referenced.is_empty() {
+            return caps[0].to_string();
+        }
+        for file in referenced {
+            if !files.iter().any(|f| f.path == file.path) {
+                files.push(file);
+            }
+        }
+        format!("{}{}{}", prefix, reference, suffix)
+    });
+
+    (expanded.to_string(), files)
+}
+
+fn resolve_reference(reference: &str) -> Vec<Files> {
+    let paths = match glob::glob(reference) {
+        Ok(paths) => paths.flatten().collect::<Vec<_>>(),
+        Err(_) => return vec![],
+    };
+
+    paths
+        .iter()
+        .filter(|path| path.is_file())
+        .filter_map(|path| read_file(path))
+        .collect()
+}
+
+fn read_file(path: &Path) -> Option<Files> {
+    let path = path.to_str()?;
+    let content = if is_binary_path(path) {
+        STANDARD.encode(fs::read(path).ok()?)
+    } else {
+        fs::read_to_string(path).ok()?
+    };
+    Some(Files {
+        path: path.strip_prefix("./").unwrap_or(path).to_string(),
+        content,
+    })
+}
+
+pub fn with_referenced_files(
+    local_context: Option<Vec<Files>>,
+    referenced_files: Vec<Files>,
+) -> Option<Vec<Files>> {
+    if referenced_files.is_empty() {
+        return local_context;
+    }
+    let mut files = local_context.unwrap_or_default();
+    for file in referenced_files {
+        if !files.iter().any(|f| f.path == file.path) {
+            files.push(file);
+        }
+    }
+    Some(files)
+}
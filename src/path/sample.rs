@@ -0,0 +1,120 @@
+use crate::usage::model::token_usage::estimate_tokens;
+
+const HEAD_SHARE: usize = 20;
+const TAIL_SHARE: usize = 20;
+const WINDOW_RADIUS: usize = 3;
+
+pub struct Sample {
+    pub text: String,
+    pub total_lines: usize,
+    pub kept_lines: usize,
+}
+
+pub fn sample_lines<F: Fn(&str) -> bool>(text: &str, max_tokens: u32, is_interesting: F) -> Sample {
+    let lines = text.lines().collect::<Vec<&str>>();
+    if estimate_tokens(text) <= max_tokens {
+        return Sample {
+            text: text.to_string(),
+            total_lines: lines.len(),
+            kept_lines: lines.len(),
+        };
+    }
+
+    let budget = max_tokens as usize * 4;
+    let mut keep = vec![false; lines.len()];
+    let mut used = 0;
+
+    used = fill(&lines, &mut keep, used, budget * HEAD_SHARE / 100, false);
+    used = fill(
+        &lines,
+        &mut keep,
+        used,
+        used + budget * TAIL_SHARE / 100,
+        true,
+    );
+
+    for (start, end) in interesting_windows(&lines, &is_interesting) {
+        let cost = (start..=end)
+            .filter(|index| !keep[*index])
+            .map(|index| lines[index].len() + 1)
+            .sum::<usize>();
+        if used + cost > budget {
+            continue;
+        }
+        (start..=end).for_each(|index| keep[index] = true);
+        used += cost;
+    }
+
+    used = fill(&lines, &mut keep, used, used + (budget - used) / 2, false);
+    fill(&lines, &mut keep, used, budget, true);
+
+    let mut sampled = String::new();
+    let mut omitted = 0;
+    for (index, line) in lines.iter().enumerate() {
+        if !keep[index] {
+            omitted += 1;
+            continue;
+        }
+        if omitted > 0 {
+            sampled.push_str(&format!("... [{} lines omitted] ...\n", omitted));
+            omitted = 0;
+        }
+        sampled.push_str(line);
+        sampled.push('\n');
+    }
+    if omitted > 0 {
+        sampled.push_str(&format!("... [{} lines omitted] ...\n", omitted));
+    }
+
+    Sample {
+        text: sampled,
+        total_lines: lines.len(),
+        kept_lines: keep.iter().filter(|kept| **kept).count(),
+    }
+}
+
+fn fill(lines: &[&str], keep: &mut [bool], mut used: usize, limit: usize, from_end: bool) -> usize {
+    let mut indices = (0..lines.len()).collect::<Vec<usize>>();
+    if from_end {
+        indices.reverse();
+    }
+    let first_open = indices.iter().position(|index| !keep[*index]);
+    for index in indices.into_iter().skip(first_open.unwrap_or(lines.len())) {
+        if keep[index] || used + lines[index].len() + 1 > limit {
+            break;
+        }
+        keep[index] = true;
+        used += lines[index].len() + 1;
+    }
+    used
+}
+
+fn interesting_windows<F: Fn(&str) -> bool>(
+    lines: &[&str],
+    is_interesting: &F,
+) -> Vec<(usize, usize)> {
+    let mut windows: Vec<(usize, usize, usize)> = Vec::new();
+    for (index, line) in lines.iter().enumerate() {
+        if !is_interesting(line) {
+            continue;
+        }
+        let start = index.saturating_sub(WINDOW_RADIUS);
+        let end = (index + WINDOW_RADIUS).min(lines.len() - 1);
+        match windows.last_mut() {
+            Some((_, last_end, hits)) if start <= *last_end + 1 => {
+                *last_end = end;
+                *hits += 1;
+            }
+            _ => windows.push((start, end, 1)),
+        }
+    }
+    windows.sort_by(|a, b| {
+        let density_a = a.2 as f64 / (a.1 - a.0 + 1) as f64;
+        let density_b = b.2 as f64 / (b.1 - b.0 + 1) as f64;
+        density_b.total_cmp(&density_a).then(a.0.cmp(&b.0))
+    });
+    windows
+        .into_iter()
+        .map(|(start, end, _)| (start, end))
+        .collect()
+}
@@ -0,0 +1,120 @@
+use anyhow::{anyhow, Result};
+use serde_json::Value;
+use std::path::Path;
+
+#[derive(Debug, Clone)]
+pub struct BenchResult {
+    pub name: String,
+    pub mean_seconds: f64,
+    pub stddev_seconds: Option<f64>,
+}
+
+pub struct BenchComparison {
+    pub name: String,
+    pub before: f64,
+    pub after: f64,
+    pub stddev: Option<f64>,
+}
+
+impl BenchComparison {
+    pub fn change_percent(&self) -> f64 {
+        if self.before == 0.0 {
+            return 0.0;
+        }
+        (self.after - self.before) * 100.0 / self.before
+    }
+
+    pub fn describe(&self) -> String {
+        let noise = self
+            .stddev
+            .map(|stddev| format!(", stddev {}", format_seconds(stddev)))
+            .unwrap_or_default();
+        format!(
+            "{}: {} -> {} ({:+.1}%{})",
+            self.name,
+            format_seconds(self.before),
+            format_seconds(self.after),
+            self.change_percent(),
+            noise
+        )
+    }
+}
+
+pub fn parse_results(path: &Path, content: &str) -> Result<Vec<BenchResult>> {
+    let json: Value = serde_json::from_str(content)
+        .map_err(|err| anyhow!("{} is not a JSON result file: {:?}", path.display(), err))?;
+
+    if let Some(results) = json.get("results").and_then(|r| r.as_array()) {
+        return Ok(results
+            .iter()
+            .filter_map(|result| {
+                Some(BenchResult {
+                    name: result.get("command")?.as_str()?.to_string(),
+                    mean_seconds: result.get("mean")?.as_f64()?,
+                    stddev_seconds: result.get("stddev").and_then(|s| s.as_f64()),
+                })
+            })
+            .collect());
+    }
+
+    if let Some(mean) = json
+        .pointer("/mean/point_estimate")
+        .and_then(|m| m.as_f64())
+    {
+        return Ok(vec![BenchResult {
+            name: criterion_bench_name(path),
+            mean_seconds: mean / 1e9,
+            stddev_seconds: json
+                .pointer("/std_dev/point_estimate")
+                .and_then(|s| s.as_f64())
+                .map(|s| s / 1e9),
+        }]);
+    }
+
+    Err(anyhow!(
+        "{} is neither a hyperfine --export-json file nor a criterion estimates.json",
+        path.display()
+    ))
+}
+
+fn criterion_bench_name(path: &Path) -> String {
+    path.ancestors()
+        .filter_map(|ancestor| ancestor.file_name().and_then(|name| name.to_str()))
+        .find(|name| !["estimates.json", "new", "base", "change"].contains(name))
+        .unwrap_or("benchmark")
+        .to_string()
+}
+
+pub fn compare(before: &[BenchResult], after: &[BenchResult]) -> Vec<BenchComparison> {
+    before
+        .iter()
+        .enumerate()
+        .filter_map(|(index, result)| {
+            let matching = after
+                .iter()
+                .find(|other| other.name == result.name)
+                .or_else(|| after.get(index))?;
+            Some(BenchComparison {
+                name: result.name.clone(),
+                before: result.mean_seconds,
+                after: matching.mean_seconds,
+                stddev: match (result.stddev_seconds, matching.stddev_seconds) {
+                    (Some(a), Some(b)) => Some(a.max(b)),
+                    (a, b) => a.or(b),
+                },
+            })
+        })
+        .collect()
+}
+
+pub fn format_seconds(seconds: f64) -> String {
+    if seconds >= 1.0 {
+        format!("{:.3} s", seconds)
+    } else if seconds >= 1e-3 {
+        format!("{:.3} ms", seconds * 1e3)
+    } else if seconds >= 1e-6 {
+        format!("{:.3} µs", seconds * 1e6)
+    } else {
+        format!("{:.1} ns", seconds * 1e9)
+    }
+}
@@ -0,0 +1 @@
+pub(crate) mod perf_service;
@@ -0,0 +1,145 @@
+use crate::args::PerfAction;
+use crate::config::repository::ConfigRepository;
+use crate::git::repository::{repo_root, run_git};
+use crate::openai::service::prompt::prompt;
+use crate::output::progress::{status, Progress};
+use crate::perf::model::{compare, format_seconds, parse_results, BenchComparison, BenchResult};
+use crate::usage::model::token_usage::estimate_tokens;
+use crate::usage::repository::UsageRepository;
+use anyhow::{anyhow, Result};
+use std::fs;
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::time::Instant;
+
+const MAX_DIFF_TOKENS: u32 = 12000;
+
+const PERF_PROMPT: &str = "
+You help developers understand performance regressions.
+You get benchmark numbers before and after a change and the code diff between the two.
+For each significant regression, point at the changes that most likely caused it and explain why.
+Finish with concrete profiling steps to confirm the hypothesis. Ignore changes within the noise.";
+
+pub struct PerfInput<'a> {
+    pub files: &'a [String],
+    pub before: Option<&'a str>,
+    pub after: Option<&'a str>,
+    pub runs: u32,
+    pub base: &'a str,
+}
+
+pub async fn perf<R: ConfigRepository, UR: UsageRepository>(
+    repo: &R,
+    usage_repo: &UR,
+    action: &PerfAction,
+) -> Result<()> {
+    match action {
+        PerfAction::Explain {
+            files,
+            before,
+            after,
+            runs,
+            base,
+        } => {
+            let input = PerfInput {
+                files,
+                before: before.as_deref(),
+                after: after.as_deref(),
+                runs: *runs,
+                base,
+            };
+            explain(repo, usage_repo, &input).await
+        }
+    }
+}
+
+async fn explain<R: ConfigRepository, UR: UsageRepository>(
+    repo: &R,
+    usage_repo: &UR,
+    input: &PerfInput<'_>,
+) -> Result<()> {
+    let (before, after) = match (input.files, input.before, input.after) {
+        ([before, after], None, None) => (read_results(before)?, read_results(after)?),
+        ([], Some(before), Some(after)) => (
+            run_benchmark(before, input.runs)?,
+            run_benchmark(after, input.runs)?,
+        ),
+        _ => {
+            return Err(anyhow!(
+                "pass either two result files or both --before and --after commands"
+            ))
+        }
+    };
+
+    let comparisons = compare(&before, &after);
+    if comparisons.is_empty() {
+        return Err(anyhow!("no benchmark appears in both results"));
+    }
+    let numbers = comparisons
+        .iter()
+        .map(BenchComparison::describe)
+        .collect::<Vec<String>>()
+        .join("\n");
+    println!("{}\n", numbers);
+
+    let dir = repo_root(Path::new("."))?;
+    let diff = run_git(&dir, &["diff", "-M", input.base])?;
+    let diff = if estimate_tokens(&diff) > MAX_DIFF_TOKENS {
+        let truncated = diff
+            .chars()
+            .take(MAX_DIFF_TOKENS as usize * 4)
+            .collect::<String>();
+        format!("{}\n... [diff truncated]\n", truncated)
+    } else {
+        diff
+    };
+
+    let question = format!(
+        "benchmarks (before -> after):\n{}\n\nchanges since {}:\n{}",
+        numbers, input.base, diff
+    );
+    let answer = prompt(repo, usage_repo, "perf", &dir, PERF_PROMPT, &question).await?;
+    println!("{}", answer);
+    Ok(())
+}
+
+fn read_results(path: &str) -> Result<Vec<BenchResult>> {
+    let content =
+        fs::read_to_string(path).map_err(|err| anyhow!("could not read {}: {:?}", path, err))?;
+    parse_results(Path::new(path), &content)
+}
+
+fn run_benchmark(command: &str, runs: u32) -> Result<Vec<BenchResult>> {
+    let mut timings = Vec::new();
+    let progress = Progress::steps(runs.max(1) as usize, command);
+    for _ in 0..runs.max(1) {
+        let start = Instant::now();
+        let status = Command::new("sh")
+            .arg("-c")
+            .arg(command)
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status()
+            .map_err(|err| anyhow!("could not run '{}': {:?}", command, err))?;
+        if !status.success() {
+            return Err(anyhow!("'{}' failed with {}", command, status));
+        }
+        timings.push(start.elapsed().as_secs_f64());
+        progress.advance();
+    }
+    progress.finish();
+    let mean = timings.iter().sum::<f64>() / timings.len() as f64;
+    let variance = timings.iter().map(|t| (t - mean).powi(2)).sum::<f64>() / timings.len() as f64;
+    status(&format!(
+        "{}: {} ± {} over {} runs",
+        command,
+        format_seconds(mean),
+        format_seconds(variance.sqrt()),
+        timings.len()
+    ));
+    Ok(vec![BenchResult {
+        name: command.to_string(),
+        mean_seconds: mean,
+        stddev_seconds: Some(variance.sqrt()),
+    }])
+}
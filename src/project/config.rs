@@ -0,0 +1,330 @@
+use crate::config::model::diagnostic::{line_of_key, line_of_offset, suggestion, Diagnostic};
+use crate::openai::model::sampling::Sampling;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub const PROJECT_CONFIG_FILE: &str = ".termai.toml";
+
+const ANY_KEY: &str = "*";
+
+const KNOWN_KEYS: [(&str, &[&str]); 11] = [
+    ("", &["agent", "context", "prompts", "sandbox", "sampling"]),
+    ("agent", &["read_file", "write_file", "run"]),
+    (
+        "context",
+        &["max_tokens", "summarize_above_tokens", "budget", "sets"],
+    ),
+    ("context.budget", &["code", "diff", "docs"]),
+    ("context.sets", &[ANY_KEY]),
+    ("context.sets.*", &["include", "exclude"]),
+    ("prompts", &["notes"]),
+    ("prompts.notes", &["tone", "language", "instructions"]),
+    ("sandbox", &["allow", "deny", "network", "jail"]),
+    ("sampling", &[ANY_KEY]),
+    (
+        "sampling.*",
+        &["temperature", "top_p", "max_output_tokens", "stop"],
+    ),
+];
+
+#[derive(Deserialize, Debug, Default)]
+#[serde(default)]
+pub struct ProjectConfig {
+    pub agent: AgentConfig,
+    pub context: ContextConfig,
+    pub prompts: PromptsConfig,
+    pub sandbox: SandboxConfig,
+    pub sampling: BTreeMap<String, Sampling>,
+}
+
+impl ProjectConfig {
+    pub fn sampling_for(&self, command: &str) -> Sampling {
+        let default = self.sampling.get("default").cloned().unwrap_or_default();
+        self.sampling
+            .get(command)
+            .cloned()
+            .unwrap_or_default()
+            .or(default)
+    }
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(default)]
+pub struct ContextConfig {
+    pub max_tokens: Option<u32>,
+    pub summarize_above_tokens: u32,
+    pub budget: CategoryBudget,
+    pub sets: BTreeMap<String, ContextSet>,
+}
+
+impl Default for ContextConfig {
+    fn default() -> Self {
+        Self {
+            max_tokens: None,
+            summarize_above_tokens: 8000,
+            budget: CategoryBudget::default(),
+            sets: BTreeMap::new(),
+        }
+    }
+}
+
+#[derive(Deserialize, Debug, Default)]
+#[serde(default)]
+pub struct ContextSet {
+    pub include: Vec<String>,
+    pub exclude: Vec<String>,
+}
+
+#[derive(Deserialize, Debug, Default)]
+#[serde(default)]
+pub struct PromptsConfig {
+    pub notes: PromptLayer,
+}
+
+#[derive(Deserialize, Debug, Default)]
+#[serde(default)]
+pub struct PromptLayer {
+    pub tone: Option<String>,
+    pub language: Option<String>,
+    pub instructions: Vec<String>,
+}
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum Permission {
+    Auto,
+    Ask,
+    Deny,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(default)]
+pub struct AgentConfig {
+    pub read_file: Permission,
+    pub write_file: Permission,
+    pub run: Permission,
+}
+
+impl Default for AgentConfig {
+    fn default() -> Self {
+        Self {
+            read_file: Permission::Auto,
+            write_file: Permission::Ask,
+            run: Permission::Ask,
+        }
+    }
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(default)]
+pub struct SandboxConfig {
+    pub allow: Vec<String>,
+    pub deny: Vec<String>,
+    pub network: bool,
+    pub jail: bool,
+}
+
+impl Default for SandboxConfig {
+    fn default() -> Self {
+        Self {
+            allow: Vec::new(),
+            deny: Vec::new(),
+            network: true,
+            jail: true,
+        }
+    }
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(default)]
+pub struct CategoryBudget {
+    pub code: u32,
+    pub diff: u32,
+    pub docs: u32,
+}
+
+impl Default for CategoryBudget {
+    fn default() -> Self {
+        Self {
+            code: 60,
+            diff: 25,
+            docs: 15,
+        }
+    }
+}
+
+pub fn find_project_config(start: &Path) -> Option<PathBuf> {
+    let start = start.canonicalize().ok()?;
+    start
+        .ancestors()
+        .map(|dir| dir.join(PROJECT_CONFIG_FILE))
+        .find(|path| path.is_file())
+}
+
+pub fn load_project_config(start: &Path) -> ProjectConfig {
+    let path = match find_project_config(start) {
+        Some(path) => path,
+        None => return ProjectConfig::default(),
+    };
+
+    let content = match fs::read_to_string(&path) {
+        Ok(content) => content,
+        Err(_) => return ProjectConfig::default(),
+    };
+    match toml::from_str(&content) {
+        Ok(config) => config,
+        Err(err) => {
+            eprintln!("ignoring {}: {}", path.display(), err);
+            ProjectConfig::default()
+        }
+    }
+}
+
+pub fn check_project_config(path: &Path, content: &str) -> Vec<Diagnostic> {
+    let table: toml::Table = match toml::from_str(content) {
+        Ok(table) => table,
+        Err(err) => {
+            let line = err.span().map(|span| line_of_offset(content, span.start));
+            return vec![Diagnostic::error(path, line, err.message().to_string())];
+        }
+    };
+    let mut diagnostics = Vec::new();
+    check_known_keys(path, content, "", "", &table, &mut diagnostics);
+
+    let config: ProjectConfig = match toml::from_str(content) {
+        Ok(config) => config,
+        Err(err) => {
+            let line = err.span().map(|span| line_of_offset(content, span.start));
+            diagnostics.push(Diagnostic::error(path, line, err.message().to_string()));
+            return diagnostics;
+        }
+    };
+    let context = &config.context;
+    let budget = &context.budget;
+    match context.max_tokens {
+        Some(0) => diagnostics.push(Diagnostic::error(
+            path,
+            line_of_key(content, "context.max_tokens"),
+            "max_tokens of 0 leaves no room for any file".to_string(),
+        )),
+        Some(max_tokens) => {
+            if budget.code + budget.diff + budget.docs == 0 {
+                diagnostics.push(Diagnostic::error(
+                    path,
+                    line_of_key(content, "context.budget"),
+                    "all budget shares are 0, every file would be left out".to_string(),
+                ));
+            }
+            if context.summarize_above_tokens > max_tokens {
+                diagnostics.push(Diagnostic::warning(
+                    path,
+                    line_of_key(content, "context.summarize_above_tokens"),
+                    format!(
+                        "summarize_above_tokens ({}) is above max_tokens ({}), large files are truncated before they would be summarized",
+                        context.summarize_above_tokens, max_tokens
+                    ),
+                ));
+            }
+        }
+        None => {
+            if table
+                .get("context")
+                .and_then(|context| context.get("budget"))
+                .is_some()
+            {
+                diagnostics.push(Diagnostic::warning(
+                    path,
+                    line_of_key(content, "context.budget"),
+                    "budget shares only apply when context.max_tokens is set".to_string(),
+                ));
+            }
+        }
+    }
+    for (name, set) in &context.sets {
+        let key = format!("context.sets.{}", name);
+        if set.include.is_empty() {
+            diagnostics.push(Diagnostic::warning(
+                path,
+                line_of_key(content, &key),
+                format!("context set '{}' has no include patterns", name),
+            ));
+        }
+        for pattern in set.include.iter().chain(&set.exclude) {
+            if let Err(err) = glob::Pattern::new(pattern) {
+                diagnostics.push(Diagnostic::error(
+                    path,
+                    line_of_key(content, &key),
+                    format!(
+                        "invalid pattern '{}' in context set '{}': {}",
+                        pattern, name, err
+                    ),
+                ));
+            }
+        }
+    }
+    let sandbox = &config.sandbox;
+    for binary in sandbox
+        .allow
+        .iter()
+        .filter(|binary| sandbox.deny.contains(binary))
+    {
+        diagnostics.push(Diagnostic::warning(
+            path,
+            line_of_key(content, "sandbox.deny"),
+            format!(
+                "'{}' is both allowed and denied, the deny list wins",
+                binary
+            ),
+        ));
+    }
+    for (command, sampling) in &config.sampling {
+        if let Err(err) = sampling.validate() {
+            diagnostics.push(Diagnostic::error(
+                path,
+                line_of_key(content, &format!("sampling.{}", command)),
+                err,
+            ));
+        }
+    }
+    diagnostics
+}
+
+fn check_known_keys(
+    path: &Path,
+    content: &str,
+    prefix: &str,
+    pattern: &str,
+    table: &toml::Table,
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    let known = KNOWN_KEYS
+        .iter()
+        .find(|(table, _)| *table == pattern)
+        .map(|(_, keys)| *keys)
+        .unwrap_or_default();
+    let any = known == [ANY_KEY];
+    for (key, value) in table {
+        let full = match prefix.is_empty() {
+            true => key.clone(),
+            false => format!("{}.{}", prefix, key),
+        };
+        if !any && !known.contains(&key.as_str()) {
+            diagnostics.push(Diagnostic::error(
+                path,
+                line_of_key(content, &full),
+                format!("unknown key '{}'{}", full, suggestion(key, known)),
+            ));
+            continue;
+        }
+        let nested_pattern = match (pattern.is_empty(), any) {
+            (true, _) => key.clone(),
+            (false, true) => format!("{}.{}", pattern, ANY_KEY),
+            (false, false) => format!("{}.{}", pattern, key),
+        };
+        if let toml::Value::Table(nested) = value {
+            check_known_keys(path, content, &full, &nested_pattern, nested, diagnostics);
+        }
+    }
+}
@@ -0,0 +1,291 @@
+use crate::project::model::ProjectInfo;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const MANIFESTS: [&str; 5] = [
+    "Cargo.toml",
+    "package.json",
+    "pyproject.toml",
+    "requirements.txt",
+    "go.mod",
+];
+
+const RUST_FRAMEWORKS: [&str; 14] = [
+    "tokio",
+    "async-std",
+    "actix-web",
+    "axum",
+    "rocket",
+    "warp",
+    "serde",
+    "clap",
+    "diesel",
+    "sqlx",
+    "rusqlite",
+    "bevy",
+    "tauri",
+    "ratatui",
+];
+
+const JS_FRAMEWORKS: [&str; 11] = [
+    "react",
+    "next",
+    "vue",
+    "nuxt",
+    "svelte",
+    "@angular/core",
+    "express",
+    "@nestjs/core",
+    "jest",
+    "vitest",
+    "electron",
+];
+
+const PYTHON_FRAMEWORKS: [&str; 9] = [
+    "django",
+    "flask",
+    "fastapi",
+    "pytest",
+    "numpy",
+    "pandas",
+    "pydantic",
+    "sqlalchemy",
+    "torch",
+];
+
+const GO_FRAMEWORKS: [&str; 6] = [
+    "github.com/gin-gonic/gin",
+    "github.com/labstack/echo",
+    "github.com/spf13/cobra",
+    "github.com/gofiber/fiber",
+    "gorm.io/gorm",
+    "google.golang.org/grpc",
+];
+
+pub fn detect_project(start: &Path) -> Vec<ProjectInfo> {
+    let root = match find_project_root(start) {
+        Some(root) => root,
+        None => return vec![],
+    };
+
+    let mut projects = Vec::new();
+    if let Some(project) = detect_rust(&root) {
+        projects.push(project);
+    }
+    if let Some(project) = detect_javascript(&root) {
+        projects.push(project);
+    }
+    if let Some(project) = detect_python(&root) {
+        projects.push(project);
+    }
+    if let Some(project) = detect_go(&root) {
+        projects.push(project);
+    }
+    projects
+}
+
+pub fn project_preamble(start: &Path) -> Option<String> {
+    let projects = detect_project(start);
+    if projects.is_empty() {
+        return None;
+    }
+    let preamble = projects
+        .iter()
+        .map(ProjectInfo::to_preamble)
+        .collect::<Vec<String>>()
+        .join("\n");
+    Some(preamble)
+}
+
+fn find_project_root(start: &Path) -> Option<PathBuf> {
+    let start = if start.is_file() {
+        start.parent()?
+    } else {
+        start
+    };
+    let start = start.canonicalize().ok()?;
+    start
+        .ancestors()
+        .find(|dir| {
+            MANIFESTS
+                .iter()
+                .any(|manifest| dir.join(manifest).is_file())
+        })
+        .map(Path::to_path_buf)
+}
+
+fn read_toml(path: &Path) -> Option<toml::Table> {
+    fs::read_to_string(path).ok()?.parse::<toml::Table>().ok()
+}
+
+fn detect_rust(root: &Path) -> Option<ProjectInfo> {
+    let manifest = read_toml(&root.join("Cargo.toml"))?;
+    let mut project = ProjectInfo::new("Rust");
+
+    let package = manifest.get("package").and_then(|p| p.as_table());
+    if let Some(edition) = package
+        .and_then(|p| p.get("edition"))
+        .and_then(|e| e.as_str())
+    {
+        project.language_version = Some(format!("{} edition", edition));
+    }
+    if let Some(rust_version) = package
+        .and_then(|p| p.get("rust-version"))
+        .and_then(|v| v.as_str())
+    {
+        project.toolchain.push(format!("rustc >= {}", rust_version));
+    }
+
+    let dependencies = ["dependencies", "dev-dependencies"]
+        .iter()
+        .filter_map(|section| manifest.get(*section).and_then(|d| d.as_table()))
+        .flat_map(|deps| deps.keys().cloned())
+        .collect::<Vec<String>>();
+    project.frameworks = known(&RUST_FRAMEWORKS, &dependencies);
+
+    if let Some(channel) = rust_toolchain_channel(root) {
+        project
+            .toolchain
+            .push(format!("rust toolchain {}", channel));
+    }
+    Some(project)
+}
+
+fn rust_toolchain_channel(root: &Path) -> Option<String> {
+    if let Some(toolchain) = read_toml(&root.join("rust-toolchain.toml")) {
+        return toolchain
+            .get("toolchain")
+            .and_then(|t| t.get("channel"))
+            .and_then(|c| c.as_str())
+            .map(str::to_string);
+    }
+    fs::read_to_string(root.join("rust-toolchain"))
+        .ok()
+        .map(|channel| channel.trim().to_string())
+}
+
+fn detect_javascript(root: &Path) -> Option<ProjectInfo> {
+    let content = fs::read_to_string(root.join("package.json")).ok()?;
+    let manifest: serde_json::Value = serde_json::from_str(&content).ok()?;
+
+    let dependencies = ["dependencies", "devDependencies"]
+        .iter()
+        .filter_map(|section| manifest.get(*section).and_then(|d| d.as_object()))
+        .flat_map(|deps| deps.keys().cloned())
+        .collect::<Vec<String>>();
+
+    let is_typescript =
+        dependencies.iter().any(|d| d == "typescript") || root.join("tsconfig.json").is_file();
+    let mut project = ProjectInfo::new(if is_typescript {
+        "TypeScript"
+    } else {
+        "JavaScript"
+    });
+    project.frameworks = known(&JS_FRAMEWORKS, &dependencies);
+
+    if let Some(node) = manifest
+        .get("engines")
+        .and_then(|e| e.get("node"))
+        .and_then(|n| n.as_str())
+    {
+        project.toolchain.push(format!("node {}", node));
+    } else if let Ok(nvmrc) = fs::read_to_string(root.join(".nvmrc")) {
+        project.toolchain.push(format!("node {}", nvmrc.trim()));
+    }
+    if let Some(package_manager) = manifest.get("packageManager").and_then(|p| p.as_str()) {
+        project.toolchain.push(package_manager.to_string());
+    }
+    Some(project)
+}
+
+fn detect_python(root: &Path) -> Option<ProjectInfo> {
+    let pyproject = read_toml(&root.join("pyproject.toml"));
+    let requirements = fs::read_to_string(root.join("requirements.txt")).ok();
+    if pyproject.is_none() && requirements.is_none() {
+        return None;
+    }
+
+    let mut project = ProjectInfo::new("Python");
+    let mut dependencies = Vec::new();
+
+    if let Some(pyproject) = &pyproject {
+        let section = pyproject.get("project");
+        if let Some(requires) = section
+            .and_then(|p| p.get("requires-python"))
+            .and_then(|r| r.as_str())
+        {
+            project.language_version = Some(requires.to_string());
+        }
+        if let Some(deps) = section
+            .and_then(|p| p.get("dependencies"))
+            .and_then(|d| d.as_array())
+        {
+            dependencies.extend(deps.iter().filter_map(|d| d.as_str()).map(requirement_name));
+        }
+        if let Some(deps) = pyproject
+            .get("tool")
+            .and_then(|t| t.get("poetry"))
+            .and_then(|p| p.get("dependencies"))
+            .and_then(|d| d.as_table())
+        {
+            dependencies.extend(deps.keys().map(|d| d.to_lowercase()));
+        }
+    }
+    if let Some(requirements) = &requirements {
+        dependencies.extend(
+            requirements
+                .lines()
+                .map(str::trim)
+                .filter(|line| !line.is_empty() && !line.starts_with('#'))
+                .map(requirement_name),
+        );
+    }
+    project.frameworks = known(&PYTHON_FRAMEWORKS, &dependencies);
+
+    if let Ok(version) = fs::read_to_string(root.join(".python-version")) {
+        project.toolchain.push(format!("python {}", version.trim()));
+    }
+    Some(project)
+}
+
+fn requirement_name(requirement: &str) -> String {
+    requirement
+        .split(|c: char| !(c.is_alphanumeric() || c == '-' || c == '_' || c == '.'))
+        .next()
+        .unwrap_or_default()
+        .to_lowercase()
+}
+
+fn detect_go(root: &Path) -> Option<ProjectInfo> {
+    let content = fs::read_to_string(root.join("go.mod")).ok()?;
+    let mut project = ProjectInfo::new("Go");
+
+    let mut dependencies = Vec::new();
+    for line in content.lines().map(str::trim) {
+        if let Some(version) = line.strip_prefix("go ") {
+            project.language_version = Some(format!("go {}", version.trim()));
+        } else if let Some(toolchain) = line.strip_prefix("toolchain ") {
+            project.toolchain.push(toolchain.trim().to_string());
+        } else if let Some(module) = line
+            .trim_start_matches("require ")
+            .split_whitespace()
+            .next()
+        {
+            dependencies.push(module.to_string());
+        }
+    }
+    project.frameworks = GO_FRAMEWORKS
+        .iter()
+        .filter(|framework| dependencies.iter().any(|d| d.starts_with(*framework)))
+        .map(|framework| framework.to_string())
+        .collect();
+    Some(project)
+}
+
+fn known(frameworks: &[&str], dependencies: &[String]) -> Vec<String> {
+    frameworks
+        .iter()
+        .filter(|framework| dependencies.iter().any(|d| d == *framework))
+        .map(|framework| framework.to_string())
+        .collect()
+}
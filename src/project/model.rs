@@ -0,0 +1,32 @@
+#[derive(Debug, Default)]
+pub struct ProjectInfo {
+    pub language: String,
+    pub language_version: Option<String>,
+    pub frameworks: Vec<String>,
+    pub toolchain: Vec<String>,
+}
+
+impl ProjectInfo {
+    pub fn new(language: &str) -> Self {
+        Self {
+            language: language.to_string(),
+            ..Default::default()
+        }
+    }
+
+    pub fn to_preamble(&self) -> String {
+        let mut preamble = format!("The user is working on a {} project", self.language);
+        if let Some(version) = &self.language_version {
+            preamble.push_str(&format!(" ({})", version));
+        }
+        if !self.frameworks.is_empty() {
+            preamble.push_str(&format!(" using {}", self.frameworks.join(", ")));
+        }
+        preamble.push('.');
+        if !self.toolchain.is_empty() {
+            preamble.push_str(&format!(" Toolchain: {}.", self.toolchain.join(", ")));
+        }
+        preamble.push_str(" Default to the idioms and libraries of this project in your answers.");
+        preamble
+    }
+}
@@ -0,0 +1,38 @@
+pub const OPENAI_BASE_URL: &str = "https://api.openai.com/v1";
+pub const OLLAMA_BASE_URL: &str = "http://localhost:11434/v1";
+pub const OLLAMA_MODEL: &str = "llama3.2";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Provider {
+    OpenAi,
+    Ollama,
+}
+
+impl Provider {
+    pub fn from_key(key: &str) -> Option<Self> {
+        match key {
+            "openai" => Some(Self::OpenAi),
+            "ollama" => Some(Self::Ollama),
+            _ => None,
+        }
+    }
+
+    pub fn to_key(self) -> &'static str {
+        match self {
+            Self::OpenAi => "openai",
+            Self::Ollama => "ollama",
+        }
+    }
+}
+
+pub struct Endpoint {
+    pub provider: Provider,
+    pub base_url: String,
+    pub api_key: Option<String>,
+}
+
+impl Endpoint {
+    pub fn url(&self, path: &str) -> String {
+        format!("{}/{}", self.base_url.trim_end_matches('/'), path)
+    }
+}
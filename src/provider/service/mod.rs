@@ -0,0 +1 @@
+pub(crate) mod provider_service;
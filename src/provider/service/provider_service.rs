@@ -0,0 +1,52 @@
+use crate::config::model::keys::ConfigKeys;
+use crate::config::repository::ConfigRepository;
+use crate::config::service::config_service;
+use crate::provider::model::{Endpoint, Provider, OLLAMA_BASE_URL, OPENAI_BASE_URL};
+use anyhow::Result;
+use colored::*;
+
+pub fn configured_provider<R: ConfigRepository>(repo: &R) -> Provider {
+    config_service::fetch_by_key(repo, &ConfigKeys::Provider.to_key())
+        .ok()
+        .and_then(|config| Provider::from_key(&config.value))
+        .unwrap_or(Provider::OpenAi)
+}
+
+pub fn endpoint<R: ConfigRepository>(repo: &R) -> Result<Endpoint> {
+    match configured_provider(repo) {
+        Provider::OpenAi => {
+            let api_key = config_service::fetch_by_key(repo, &ConfigKeys::ChatGptApiKey.to_key())?;
+            Ok(Endpoint {
+                provider: Provider::OpenAi,
+                base_url: OPENAI_BASE_URL.to_string(),
+                api_key: Some(api_key.value),
+            })
+        }
+        Provider::Ollama => Ok(Endpoint {
+            provider: Provider::Ollama,
+            base_url: config_service::fetch_by_key(repo, &ConfigKeys::OllamaBaseUrl.to_key())
+                .map(|config| config.value)
+                .unwrap_or_else(|_| OLLAMA_BASE_URL.to_string()),
+            api_key: None,
+        }),
+    }
+}
+
+pub fn set_provider<R: ConfigRepository>(repo: &R, provider: &str) -> Result<()> {
+    config_service::set_config(repo, &ConfigKeys::Provider.to_key(), provider)?;
+    match Provider::from_key(provider) {
+        Some(Provider::Ollama) => {
+            let endpoint = endpoint(repo)?;
+            println!(
+                "{} answers now come from {}",
+                "saved".green(),
+                endpoint.base_url
+            );
+            println!(
+                "pick the model with `termai config set ollama_model <name>` and the server with `termai config set ollama_base_url <url>`"
+            );
+        }
+        _ => println!("{} answers now come from OpenAI", "saved".green()),
+    }
+    Ok(())
+}
@@ -0,0 +1,2 @@
+pub(crate) mod model_rating_entity;
+pub(crate) mod rating_entity;
@@ -0,0 +1,11 @@
+pub struct ModelRatingEntity {
+    pub model: Option<String>,
+    pub up: u32,
+    pub down: u32,
+}
+
+impl ModelRatingEntity {
+    pub fn new(model: Option<String>, up: u32, down: u32) -> Self {
+        Self { model, up, down }
+    }
+}
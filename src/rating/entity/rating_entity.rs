@@ -0,0 +1,27 @@
+use chrono::NaiveDateTime;
+
+pub struct RatingEntity {
+    pub message_id: String,
+    pub session_id: String,
+    pub rating: Option<String>,
+    pub note: Option<String>,
+    pub created_at: NaiveDateTime,
+}
+
+impl RatingEntity {
+    pub fn new(
+        message_id: String,
+        session_id: String,
+        rating: Option<String>,
+        note: Option<String>,
+        created_at: NaiveDateTime,
+    ) -> Self {
+        Self {
+            message_id,
+            session_id,
+            rating,
+            note,
+            created_at,
+        }
+    }
+}
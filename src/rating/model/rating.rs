@@ -0,0 +1,29 @@
+#[derive(Clone, Copy, PartialEq)]
+pub enum Rating {
+    Up,
+    Down,
+}
+
+impl Rating {
+    pub fn from_str(rating: &str) -> Option<Self> {
+        match rating {
+            "up" => Some(Self::Up),
+            "down" => Some(Self::Down),
+            _ => None,
+        }
+    }
+
+    pub fn to_key(self) -> &'static str {
+        match self {
+            Self::Up => "up",
+            Self::Down => "down",
+        }
+    }
+
+    pub fn symbol(self) -> &'static str {
+        match self {
+            Self::Up => "👍",
+            Self::Down => "👎",
+        }
+    }
+}
@@ -0,0 +1,19 @@
+use super::entity::model_rating_entity::ModelRatingEntity;
+use super::entity::rating_entity::RatingEntity;
+use std::fmt::Debug;
+
+pub(crate) mod rating_repository;
+
+pub trait RatingRepository
+where
+    Self::Error: Debug,
+{
+    type Error;
+
+    fn fetch_rating(&self, message_id: &str) -> Result<Option<RatingEntity>, Self::Error>;
+    fn fetch_ratings_for_session(&self, session_id: &str)
+        -> Result<Vec<RatingEntity>, Self::Error>;
+    fn fetch_ratings(&self, rating: &str, limit: u32) -> Result<Vec<RatingEntity>, Self::Error>;
+    fn fetch_model_ratings(&self) -> Result<Vec<ModelRatingEntity>, Self::Error>;
+    fn save_rating(&self, rating: &RatingEntity) -> Result<(), Self::Error>;
+}
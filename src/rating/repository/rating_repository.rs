@@ -0,0 +1,110 @@
+use super::RatingRepository;
+use crate::rating::entity::model_rating_entity::ModelRatingEntity;
+use crate::rating::entity::rating_entity::RatingEntity;
+use crate::repository::db::SqliteRepository;
+use chrono::NaiveDateTime;
+use rusqlite::{params, OptionalExtension, Result, Row};
+
+const DATE_TIME_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+
+impl RatingRepository for SqliteRepository {
+    type Error = rusqlite::Error;
+
+    fn fetch_rating(&self, message_id: &str) -> Result<Option<RatingEntity>, Self::Error> {
+        self.conn
+            .query_row(
+                "SELECT message_id, session_id, rating, note, created_at
+                 FROM ratings WHERE message_id = ?1",
+                params![message_id],
+                row_to_rating_entity(),
+            )
+            .optional()
+    }
+
+    fn fetch_ratings_for_session(
+        &self,
+        session_id: &str,
+    ) -> Result<Vec<RatingEntity>, Self::Error> {
+        let mut stmt = self.conn.prepare(
+            "SELECT message_id, session_id, rating, note, created_at
+             FROM ratings WHERE session_id = ?1",
+        )?;
+        let rows = stmt.query_map(params![session_id], row_to_rating_entity())?;
+
+        let mut ratings = Vec::new();
+        for rating in rows {
+            ratings.push(rating?);
+        }
+        Ok(ratings)
+    }
+
+    fn fetch_ratings(&self, rating: &str, limit: u32) -> Result<Vec<RatingEntity>, Self::Error> {
+        let mut stmt = self.conn.prepare(
+            "SELECT message_id, session_id, rating, note, created_at
+             FROM ratings WHERE rating = ?1 ORDER BY created_at DESC LIMIT ?2",
+        )?;
+        let rows = stmt.query_map(params![rating, limit], row_to_rating_entity())?;
+
+        let mut ratings = Vec::new();
+        for rating in rows {
+            ratings.push(rating?);
+        }
+        Ok(ratings)
+    }
+
+    fn fetch_model_ratings(&self) -> Result<Vec<ModelRatingEntity>, Self::Error> {
+        let mut stmt = self.conn.prepare(
+            "SELECT messages.model,
+                    SUM(CASE WHEN ratings.rating = 'up' THEN 1 ELSE 0 END),
+                    SUM(CASE WHEN ratings.rating = 'down' THEN 1 ELSE 0 END)
+             FROM ratings JOIN messages ON messages.id = ratings.message_id
+             WHERE ratings.rating IS NOT NULL
+             GROUP BY messages.model",
+        )?;
+        let rows = stmt.query_map([], |row| {
+            let model: Option<String> = row.get(0)?;
+            let up: u32 = row.get(1)?;
+            let down: u32 = row.get(2)?;
+
+            Ok(ModelRatingEntity::new(model, up, down))
+        })?;
+
+        let mut ratings = Vec::new();
+        for rating in rows {
+            ratings.push(rating?);
+        }
+        Ok(ratings)
+    }
+
+    fn save_rating(&self, rating: &RatingEntity) -> Result<(), Self::Error> {
+        let created_at_str = rating.created_at.format(DATE_TIME_FORMAT).to_string();
+        self.conn.execute(
+            "INSERT OR REPLACE INTO ratings (message_id, session_id, rating, note, created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![
+                rating.message_id,
+                rating.session_id,
+                rating.rating,
+                rating.note,
+                created_at_str
+            ],
+        )?;
+        Ok(())
+    }
+}
+
+fn row_to_rating_entity() -> fn(&Row) -> Result<RatingEntity> {
+    |row| {
+        let message_id: String = row.get(0)?;
+        let session_id: String = row.get(1)?;
+        let rating: Option<String> = row.get(2)?;
+        let note: Option<String> = row.get(3)?;
+        let created_at_str: String = row.get(4)?;
+        let created_at = NaiveDateTime::parse_from_str(&created_at_str, DATE_TIME_FORMAT)
+            .expect("Invalid DateTime format");
+
+        Ok(RatingEntity::new(
+            message_id, session_id, rating, note, created_at,
+        ))
+    }
+}
@@ -0,0 +1,2 @@
+pub(crate) mod rating_service;
+pub(crate) mod tune_service;
@@ -0,0 +1,132 @@
+use crate::openai::model::role::Role;
+use crate::rating::entity::rating_entity::RatingEntity;
+use crate::rating::model::rating::Rating;
+use crate::rating::repository::RatingRepository;
+use crate::session::model::session::Session;
+use crate::session::service::sessions_service::assistant_message;
+use anyhow::{anyhow, Result};
+use chrono::Utc;
+use colored::*;
+
+pub fn note<RR: RatingRepository>(
+    rating_repo: &RR,
+    session: &Session,
+    answer: &str,
+    rating: Option<Rating>,
+    text: Option<&str>,
+) -> Result<()> {
+    let message = assistant_message(session, answer)?;
+    let existing = rating_repo
+        .fetch_rating(&message.id)
+        .map_err(|err| anyhow!("could not read the note: {:?}", err))?;
+
+    if rating.is_none() && text.is_none() {
+        match existing {
+            Some(existing) => println!("{}", format_rating(&existing)),
+            None => println!("no note on this answer yet"),
+        }
+        return Ok(());
+    }
+
+    let (previous_rating, previous_note) = match existing {
+        Some(existing) => (existing.rating, existing.note),
+        None => (None, None),
+    };
+    let entity = RatingEntity::new(
+        message.id.clone(),
+        session.id.clone(),
+        rating
+            .map(|rating| rating.to_key().to_string())
+            .or(previous_rating),
+        text.map(str::to_string).or(previous_note),
+        Utc::now().naive_utc(),
+    );
+    rating_repo
+        .save_rating(&entity)
+        .map_err(|err| anyhow!("could not save the note: {:?}", err))?;
+    println!("{}", format_rating(&entity));
+    Ok(())
+}
+
+pub fn print_session_notes<RR: RatingRepository>(rating_repo: &RR, session: &Session) {
+    let Ok(ratings) = rating_repo.fetch_ratings_for_session(&session.id) else {
+        return;
+    };
+    if ratings.is_empty() {
+        return;
+    }
+    println!("{}", "notes".bold());
+    let answers = session
+        .messages
+        .iter()
+        .filter(|message| message.role == Role::Assistant);
+    for (number, answer) in answers.enumerate() {
+        if let Some(rating) = ratings.iter().find(|rating| rating.message_id == answer.id) {
+            println!("  answer {}: {}", number + 1, format_rating(rating));
+        }
+    }
+}
+
+pub fn print_model_ratings<RR: RatingRepository>(rating_repo: &RR) -> Result<()> {
+    let mut ratings = rating_repo
+        .fetch_model_ratings()
+        .map_err(|err| anyhow!("could not read the ratings: {:?}", err))?;
+    if ratings.is_empty() {
+        println!(
+            "no rated answers yet, rate one with `termai session note <session> <answer> --up`"
+        );
+        return Ok(());
+    }
+    ratings.sort_by(|a, b| score(b.up, b.down).total_cmp(&score(a.up, a.down)));
+
+    let models = ratings
+        .iter()
+        .map(|rating| {
+            rating
+                .model
+                .clone()
+                .unwrap_or_else(|| "unknown".to_string())
+        })
+        .collect::<Vec<String>>();
+    let width = models.iter().map(|model| model.len()).max().unwrap_or(0);
+    println!(
+        "{:<width$}  {:>5}  {:>5}  {:>6}",
+        "MODEL",
+        "UP",
+        "DOWN",
+        "SCORE",
+        width = width
+    );
+    for (model, rating) in models.iter().zip(&ratings) {
+        println!(
+            "{:<width$}  {:>5}  {:>5}  {:>5.0}%",
+            model,
+            rating.up,
+            rating.down,
+            score(rating.up, rating.down) * 100.0,
+            width = width
+        );
+    }
+    Ok(())
+}
+
+fn score(up: u32, down: u32) -> f64 {
+    match up + down {
+        0 => 0.0,
+        total => up as f64 / total as f64,
+    }
+}
+
+fn format_rating(rating: &RatingEntity) -> String {
+    let symbol = rating
+        .rating
+        .as_deref()
+        .and_then(Rating::from_str)
+        .map(Rating::symbol)
+        .unwrap_or("");
+    match &rating.note {
+        Some(note) if symbol.is_empty() => note.to_string(),
+        Some(note) => format!("{} {}", symbol, note),
+        None => symbol.to_string(),
+    }
+}
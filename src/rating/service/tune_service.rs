@@ -0,0 +1,104 @@
+use crate::common::terminal::{ask, is_interactive};
+use crate::config::model::keys::ConfigKeys;
+use crate::config::repository::ConfigRepository;
+use crate::config::service::{config_service, open_ai_config};
+use crate::openai::model::role::Role;
+use crate::openai::service::chat::SYSTEM_PROMPT;
+use crate::openai::service::prompt::prompt;
+use crate::output::diff;
+use crate::output::markdown::truncate;
+use crate::rating::repository::RatingRepository;
+use crate::session::entity::message_entity::MessageEntity;
+use crate::session::repository::MessageRepository;
+use crate::usage::repository::UsageRepository;
+use anyhow::{anyhow, Result};
+use colored::*;
+use std::path::Path;
+
+const MAX_EXCERPT_CHARS: usize = 1500;
+
+const TUNE_PROMPT: &str = "
+You improve system prompts for a terminal assistant.
+You get the current system prompt and answers the user rated as bad, with their notes.
+Change the prompt so these answers would have been better, keep what works and stay brief.
+Reply with the complete new system prompt only, no explanation and no code fences.";
+
+pub async fn tune<
+    R: ConfigRepository,
+    UR: UsageRepository,
+    RR: RatingRepository,
+    MR: MessageRepository,
+>(
+    repo: &R,
+    usage_repo: &UR,
+    rating_repo: &RR,
+    message_repo: &MR,
+    limit: u32,
+) -> Result<()> {
+    let ratings = rating_repo
+        .fetch_ratings("down", limit)
+        .map_err(|err| anyhow!("could not read the ratings: {:?}", err))?;
+    if ratings.is_empty() {
+        return Err(anyhow!(
+            "no answers are rated down yet, rate them with `termai session note <session> <answer> --down`"
+        ));
+    }
+
+    let mut examples = Vec::new();
+    for rating in &ratings {
+        let messages = message_repo
+            .fetch_messages_for_session(&rating.session_id)
+            .map_err(|err| anyhow!("could not read the session: {:?}", err))?;
+        if let Some(example) = low_rated_example(&messages, &rating.message_id) {
+            let note = rating.note.as_deref().unwrap_or("(no note)");
+            examples.push(format!("{}\nUser note: {}", example, note));
+        }
+    }
+
+    let current = open_ai_config::system_prompt(repo).unwrap_or_else(|| SYSTEM_PROMPT.to_string());
+    let input = format!(
+        "Current system prompt:\n{}\n\nLow-rated answers:\n\n{}",
+        current.trim(),
+        examples.join("\n\n---\n\n")
+    );
+    let suggested = prompt(
+        repo,
+        usage_repo,
+        "tune",
+        Path::new("."),
+        TUNE_PROMPT,
+        &input,
+    )
+    .await?;
+    let suggested = suggested.trim();
+
+    println!(
+        "{}",
+        format!("suggestion from {} low-rated answers", examples.len()).bold()
+    );
+    diff::print_word_diff(current.trim(), suggested);
+
+    if !is_interactive() {
+        println!("run in a terminal to accept the suggestion");
+        return Ok(());
+    }
+    if ask("use it as the system prompt?", false)? {
+        config_service::set_config(repo, &ConfigKeys::SystemPrompt.to_key(), suggested)?;
+        println!("saved, `termai config set system_prompt` changes it again");
+    }
+    Ok(())
+}
+
+fn low_rated_example(messages: &[MessageEntity], message_id: &str) -> Option<String> {
+    let position = messages
+        .iter()
+        .position(|message| message.id == message_id)?;
+    let question = messages[..position]
+        .iter()
+        .rev()
+        .find(|message| Role::from_str(&message.role) == Role::User)
+        .map(|message| truncate(&message.content, MAX_EXCERPT_CHARS))
+        .unwrap_or_default();
+    let answer = truncate(&messages[position].content, MAX_EXCERPT_CHARS);
+    Some(format!("Question: {}\nAnswer: {}", question, answer))
+}
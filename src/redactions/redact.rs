@@ -1,7 +1,7 @@
-use std::collections::HashMap;
-use regex::Regex;
 use crate::config::repository::ConfigRepository;
 use crate::config::service::redacted_config;
+use regex::Regex;
+use std::collections::HashMap;
 
 use super::common;
 
@@ -9,13 +9,16 @@ pub fn redact<R: ConfigRepository>(repo: &R, content: &str) -> (String, HashMap<
     let redactions = redacted_config::fetch_redactions(repo);
     let mapped_redactions = common::redaction_map(redactions);
 
-    let input_with_redactions =
-        mapped_redactions
-            .iter()
-            .fold(content.to_string(), |acc, (redaction, id)| {
-                let re = Regex::new(&format!("(?i){}", regex::escape(redaction))).unwrap();
-                re.replace_all(&acc, id).to_string()
-            });
+    let input_with_redactions = redact_with_mapping(&mapped_redactions, content);
+
+    (input_with_redactions, mapped_redactions)
+}
 
-    (input_with_redactions.to_string(), mapped_redactions)
+pub fn redact_with_mapping(mapped_redactions: &HashMap<String, String>, content: &str) -> String {
+    mapped_redactions
+        .iter()
+        .fold(content.to_string(), |acc, (redaction, id)| {
+            let re = Regex::new(&format!("(?i){}", regex::escape(redaction))).unwrap();
+            re.replace_all(&acc, id).to_string()
+        })
 }
@@ -1,4 +1,6 @@
-use rusqlite::{Connection, Result};
+use crate::output::progress::is_quiet;
+use rusqlite::{Connection, DatabaseName, Result};
+use std::path::Path;
 
 pub struct SqliteRepository {
     pub(crate) conn: Connection,
@@ -6,16 +8,48 @@ pub struct SqliteRepository {
 
 impl SqliteRepository {
     pub fn new(path: &str) -> Result<Self> {
-        let conn = Connection::open(path)?;
+        Self::with_connection(Connection::open(path)?)
+    }
+
+    pub fn new_in_memory_copy(path: &str) -> Result<Self> {
+        let mut conn = Connection::open_in_memory()?;
+        if Path::new(path).exists() {
+            conn.restore(
+                DatabaseName::Main,
+                path,
+                None::<fn(rusqlite::backup::Progress)>,
+            )?;
+        }
+        Self::with_connection(conn)
+    }
+
+    fn with_connection(conn: Connection) -> Result<Self> {
         create_table_messages(&conn)?;
         create_table_config(&conn)?;
         create_table_sessions(&conn)?;
+        create_table_snippets(&conn)?;
+        create_table_usage(&conn)?;
+        create_table_file_summaries(&conn)?;
+        create_table_attachments(&conn)?;
+        create_table_hints(&conn)?;
+        create_table_models(&conn)?;
+        create_table_ratings(&conn)?;
+        create_table_history(&conn)?;
+        create_table_session_digests(&conn)?;
+        create_table_session_pins(&conn)?;
+        create_table_command_audit(&conn)?;
+        create_table_trusted_directories(&conn)?;
+        create_table_response_cache(&conn)?;
+        create_table_uploads(&conn)?;
         migrate_messages_id_column(&conn)?;
         messages_add_session_id_column(&conn)?;
         messages_add_role_column(&conn)?;
+        messages_add_model_column(&conn)?;
         sessions_add_current_column(&conn)?;
         sessions_rename_column_key_to_name(&conn)?;
-        if cfg!(debug_assertions) {
+        messages_add_created_at_column(&conn)?;
+        create_indexes_for_timeline(&conn)?;
+        if cfg!(debug_assertions) && !is_quiet() {
             debug_print_tables(&conn)?;
         }
         Ok(Self { conn })
@@ -60,6 +94,206 @@ fn create_table_sessions(conn: &Connection) -> Result<()> {
     Ok(())
 }
 
+fn create_table_snippets(conn: &Connection) -> Result<()> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS snippets (
+                id TEXT NOT NULL PRIMARY KEY,
+                name TEXT NOT NULL UNIQUE,
+                language TEXT,
+                content TEXT NOT NULL,
+                session_id TEXT NOT NULL,
+                message_id TEXT NOT NULL,
+                created_at TEXT NOT NULL
+            )",
+        [],
+    )?;
+    Ok(())
+}
+
+fn create_table_usage(conn: &Connection) -> Result<()> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS usage (
+                id TEXT NOT NULL PRIMARY KEY,
+                session_id TEXT NOT NULL,
+                model TEXT NOT NULL,
+                prompt_tokens INTEGER NOT NULL,
+                completion_tokens INTEGER NOT NULL,
+                created_at TEXT NOT NULL
+            )",
+        [],
+    )?;
+    Ok(())
+}
+
+fn create_table_file_summaries(conn: &Connection) -> Result<()> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS file_summaries (
+                hash TEXT NOT NULL PRIMARY KEY,
+                path TEXT NOT NULL,
+                summary TEXT NOT NULL
+            )",
+        [],
+    )?;
+    Ok(())
+}
+
+fn create_table_hints(conn: &Connection) -> Result<()> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS hints (
+                name TEXT NOT NULL PRIMARY KEY,
+                count INTEGER NOT NULL,
+                shown INTEGER NOT NULL
+            )",
+        [],
+    )?;
+    Ok(())
+}
+
+fn create_table_models(conn: &Connection) -> Result<()> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS models (
+                id TEXT NOT NULL,
+                provider TEXT NOT NULL,
+                fetched_at TEXT NOT NULL,
+                PRIMARY KEY (provider, id)
+            )",
+        [],
+    )?;
+    Ok(())
+}
+
+fn create_table_ratings(conn: &Connection) -> Result<()> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS ratings (
+                message_id TEXT NOT NULL PRIMARY KEY,
+                session_id TEXT NOT NULL,
+                rating TEXT,
+                note TEXT,
+                created_at TEXT NOT NULL
+            )",
+        [],
+    )?;
+    Ok(())
+}
+
+fn create_table_history(conn: &Connection) -> Result<()> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS history (
+                id TEXT NOT NULL PRIMARY KEY,
+                prompt TEXT NOT NULL,
+                arguments TEXT NOT NULL,
+                working_directory TEXT NOT NULL,
+                created_at TEXT NOT NULL
+            )",
+        [],
+    )?;
+    Ok(())
+}
+
+fn create_table_session_digests(conn: &Connection) -> Result<()> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS session_digests (
+                session_id TEXT NOT NULL PRIMARY KEY,
+                depth TEXT NOT NULL,
+                content TEXT NOT NULL,
+                message_count INTEGER NOT NULL,
+                created_at TEXT NOT NULL
+            )",
+        [],
+    )?;
+    Ok(())
+}
+
+fn create_table_session_pins(conn: &Connection) -> Result<()> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS session_pins (
+                session_id TEXT NOT NULL PRIMARY KEY,
+                model TEXT NOT NULL,
+                pinned_at TEXT NOT NULL
+            )",
+        [],
+    )?;
+    Ok(())
+}
+
+fn create_table_trusted_directories(conn: &Connection) -> Result<()> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS trusted_directories (
+                path TEXT NOT NULL PRIMARY KEY,
+                trusted INTEGER NOT NULL,
+                decided_at TEXT NOT NULL
+            )",
+        [],
+    )?;
+    Ok(())
+}
+
+fn create_table_response_cache(conn: &Connection) -> Result<()> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS response_cache (
+                key TEXT NOT NULL PRIMARY KEY,
+                provider TEXT NOT NULL,
+                model TEXT NOT NULL,
+                answer TEXT NOT NULL,
+                created_at TEXT NOT NULL
+            )",
+        [],
+    )?;
+    Ok(())
+}
+
+fn create_table_uploads(conn: &Connection) -> Result<()> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS uploads (
+                file_id TEXT NOT NULL PRIMARY KEY,
+                base_url TEXT NOT NULL,
+                hash TEXT NOT NULL,
+                path TEXT NOT NULL,
+                bytes INTEGER NOT NULL,
+                created_at TEXT NOT NULL
+            )",
+        [],
+    )?;
+    Ok(())
+}
+
+fn create_table_command_audit(conn: &Connection) -> Result<()> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS command_audit (
+                id TEXT NOT NULL PRIMARY KEY,
+                command TEXT NOT NULL,
+                directory TEXT NOT NULL,
+                source TEXT NOT NULL,
+                exit_code INTEGER,
+                blocked TEXT,
+                created_at TEXT NOT NULL
+            )",
+        [],
+    )?;
+    Ok(())
+}
+
+fn create_table_attachments(conn: &Connection) -> Result<()> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS attachments (
+                id TEXT NOT NULL PRIMARY KEY,
+                message_id TEXT NOT NULL,
+                kind TEXT NOT NULL,
+                path TEXT NOT NULL,
+                hash TEXT NOT NULL
+            )",
+        [],
+    )?;
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS attachment_contents (
+                hash TEXT NOT NULL PRIMARY KEY,
+                content TEXT NOT NULL
+            )",
+        [],
+    )?;
+    Ok(())
+}
+
 fn migrate_messages_id_column(conn: &Connection) -> Result<()> {
     let mut stmt = conn.prepare("PRAGMA table_info(messages)")?;
     let mut old_id_schema = false;
@@ -128,6 +362,52 @@ fn messages_add_role_column(conn: &Connection) -> Result<()> {
     Ok(())
 }
 
+fn messages_add_model_column(conn: &Connection) -> Result<()> {
+    let mut stmt = conn.prepare("PRAGMA table_info(messages)")?;
+    let mut has_model = false;
+    let rows = stmt.query_map([], |row| row.get::<_, String>(1))?;
+    for col in rows {
+        if col? == "model" {
+            has_model = true;
+            break;
+        }
+    }
+    if !has_model {
+        conn.execute("ALTER TABLE messages ADD COLUMN model TEXT", [])?;
+    }
+    drop(stmt);
+    Ok(())
+}
+
+fn messages_add_created_at_column(conn: &Connection) -> Result<()> {
+    let mut stmt = conn.prepare("PRAGMA table_info(messages)")?;
+    let mut has_created_at = false;
+    let rows = stmt.query_map([], |row| row.get::<_, String>(1))?;
+    for col in rows {
+        if col? == "created_at" {
+            has_created_at = true;
+            break;
+        }
+    }
+    if !has_created_at {
+        conn.execute("ALTER TABLE messages ADD COLUMN created_at TEXT", [])?;
+    }
+    drop(stmt);
+    Ok(())
+}
+
+fn create_indexes_for_timeline(conn: &Connection) -> Result<()> {
+    conn.execute(
+        "CREATE INDEX IF NOT EXISTS messages_session_created_at ON messages (session_id, created_at)",
+        [],
+    )?;
+    conn.execute(
+        "CREATE INDEX IF NOT EXISTS usage_session_created_at ON usage (session_id, created_at)",
+        [],
+    )?;
+    Ok(())
+}
+
 fn sessions_add_current_column(conn: &Connection) -> Result<()> {
     let mut stmt = conn.prepare("PRAGMA table_info(sessions)")?;
     let mut has_current = false;
@@ -0,0 +1,33 @@
+use chrono::NaiveDateTime;
+
+pub struct AuditEntity {
+    pub id: String,
+    pub command: String,
+    pub directory: String,
+    pub source: String,
+    pub exit_code: Option<i32>,
+    pub blocked: Option<String>,
+    pub created_at: NaiveDateTime,
+}
+
+impl AuditEntity {
+    pub fn new(
+        id: String,
+        command: String,
+        directory: String,
+        source: String,
+        exit_code: Option<i32>,
+        blocked: Option<String>,
+        created_at: NaiveDateTime,
+    ) -> Self {
+        Self {
+            id,
+            command,
+            directory,
+            source,
+            exit_code,
+            blocked,
+            created_at,
+        }
+    }
+}
@@ -0,0 +1,523 @@
+use crate::project::config::SandboxConfig;
+use std::fs;
+use std::path::{Component, Path};
+
+// Commands that run another command: (name, options that take a value, leading
+// arguments that are not the command). The wrapper and the command it runs are
+// both checked.
+const WRAPPERS: [(&str, &[&str], usize); 10] = [
+    ("env", &["-u", "-C", "-S"], 0),
+    (
+        "sudo",
+        &["-u", "-g", "-C", "-D", "-h", "-p", "-r", "-t", "-U"],
+        0,
+    ),
+    ("nice", &["-n"], 0),
+    ("time", &["-f", "-o"], 0),
+    ("exec", &["-a"], 0),
+    ("nohup", &[], 0),
+    ("command", &[], 0),
+    ("builtin", &[], 0),
+    ("timeout", &["-s", "-k"], 1),
+    (
+        "xargs",
+        &["-a", "-d", "-E", "-I", "-L", "-n", "-P", "-s"],
+        0,
+    ),
+];
+
+// Words that start or close a compound command, the command follows them.
+const RESERVED_WORDS: [&str; 13] = [
+    "if", "then", "else", "elif", "fi", "do", "done", "while", "until", "esac", "{", "}", "!",
+];
+
+// Loop and case headers only list words, the commands are in the next segments.
+const HEADER_WORDS: [&str; 4] = ["for", "case", "select", "function"];
+
+// Shells run their -c argument as a command line.
+const SHELLS: [&str; 6] = ["sh", "bash", "zsh", "dash", "ksh", "fish"];
+
+const FIND_ACTIONS: [&str; 4] = ["-exec", "-execdir", "-ok", "-okdir"];
+
+pub fn command_binaries(command: &str) -> Vec<String> {
+    shell_segments(command)
+        .iter()
+        .flat_map(|words| segment_binaries(words))
+        .collect()
+}
+
+fn segment_binaries(words: &[String]) -> Vec<String> {
+    let mut binaries = Vec::new();
+    let mut wrapper: Option<(&[&str], usize)> = None;
+    let mut index = 0;
+    while index < words.len() {
+        let word = &words[index];
+        index += 1;
+        if word.is_empty() || is_assignment(word) {
+            continue;
+        }
+        let name = binary_name(word);
+        if let Some((options, positional)) = wrapper.as_mut() {
+            if name.starts_with('-') {
+                if options.contains(&name.as_str()) {
+                    index += 1;
+                }
+                continue;
+            }
+            if *positional > 0 {
+                *positional -= 1;
+                continue;
+            }
+        } else if binaries.is_empty() {
+            if RESERVED_WORDS.contains(&name.as_str()) {
+                continue;
+            }
+            if HEADER_WORDS.contains(&name.as_str()) {
+                break;
+            }
+        }
+        binaries.push(name.clone());
+        let rest = &words[index..];
+        if SHELLS.contains(&name.as_str()) {
+            let script = rest
+                .iter()
+                .position(|word| is_command_option(word))
+                .and_then(|position| rest.get(position + 1));
+            binaries.extend(
+                script
+                    .map(|script| command_binaries(script))
+                    .unwrap_or_default(),
+            );
+            break;
+        }
+        match name.as_str() {
+            "eval" => {
+                binaries.extend(command_binaries(&rest.join(" ")));
+                break;
+            }
+            "find" => {
+                binaries.extend(find_binaries(rest));
+                break;
+            }
+            _ => {}
+        }
+        wrapper = WRAPPERS
+            .iter()
+            .find(|(wrapper, _, _)| *wrapper == name)
+            .map(|(_, options, positional)| (*options, *positional));
+        if wrapper.is_none() {
+            break;
+        }
+    }
+    binaries
+}
+
+// The commands of `find -exec ... ;` and its variants, up to the ; or +.
+fn find_binaries(arguments: &[String]) -> Vec<String> {
+    let mut binaries = Vec::new();
+    let mut arguments = arguments.iter();
+    while let Some(argument) = arguments.next() {
+        if FIND_ACTIONS.contains(&argument.as_str()) {
+            let command = arguments
+                .by_ref()
+                .take_while(|word| *word != ";" && *word != "+")
+                .cloned()
+                .collect::<Vec<String>>();
+            binaries.extend(segment_binaries(&command));
+        }
+    }
+    binaries
+}
+
+// -c, or -c combined with other single letter options such as -lc.
+fn is_command_option(word: &str) -> bool {
+    word.strip_prefix('-')
+        .is_some_and(|flags| !flags.starts_with('-') && flags.contains('c'))
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Nesting {
+    Parenthesis,
+    SubstitutionInQuotes,
+    BacktickInQuotes,
+}
+
+// Splits a command line into the words of each simple command, with quotes and
+// escapes removed. Lists, pipes, subshells and command substitutions, also the
+// ones inside double quotes, all start a new command.
+fn shell_segments(command: &str) -> Vec<Vec<String>> {
+    let mut segments = vec![Vec::new()];
+    let mut word: Option<String> = None;
+    let mut nesting = Vec::new();
+    let mut in_double_quotes = false;
+    let mut chars = command.chars().peekable();
+
+    fn end_word(segments: &mut [Vec<String>], word: &mut Option<String>) {
+        if let Some(word) = word.take() {
+            segments.last_mut().unwrap().push(word);
+        }
+    }
+    fn end_segment(segments: &mut Vec<Vec<String>>, word: &mut Option<String>) {
+        end_word(segments, word);
+        segments.push(Vec::new());
+    }
+
+    while let Some(c) = chars.next() {
+        if in_double_quotes {
+            match c {
+                '"' => in_double_quotes = false,
+                '\\' => match chars.peek() {
+                    Some(&next) if matches!(next, '$' | '`' | '"' | '\\' | '\n') => {
+                        chars.next();
+                        if next != '\n' {
+                            word.get_or_insert_with(String::new).push(next);
+                        }
+                    }
+                    _ => word.get_or_insert_with(String::new).push(c),
+                },
+                '$' if chars.peek() == Some(&'(') => {
+                    chars.next();
+                    nesting.push(Nesting::SubstitutionInQuotes);
+                    in_double_quotes = false;
+                    end_segment(&mut segments, &mut word);
+                }
+                '`' => {
+                    nesting.push(Nesting::BacktickInQuotes);
+                    in_double_quotes = false;
+                    end_segment(&mut segments, &mut word);
+                }
+                _ => word.get_or_insert_with(String::new).push(c),
+            }
+            continue;
+        }
+        match c {
+            '\'' => {
+                let quoted = word.get_or_insert_with(String::new);
+                for c in chars.by_ref() {
+                    if c == '\'' {
+                        break;
+                    }
+                    quoted.push(c);
+                }
+            }
+            '"' => {
+                word.get_or_insert_with(String::new);
+                in_double_quotes = true;
+            }
+            '\\' => match chars.next() {
+                Some('\n') | None => {}
+                Some(next) => word.get_or_insert_with(String::new).push(next),
+            },
+            '#' if word.is_none() => {
+                for c in chars.by_ref() {
+                    if c == '\n' {
+                        break;
+                    }
+                }
+                end_segment(&mut segments, &mut word);
+            }
+            // redirections such as 2>&1 and &> file are not separators
+            '&' if word
+                .as_deref()
+                .is_some_and(|word| word.ends_with(['>', '<']))
+                || chars.peek() == Some(&'>') =>
+            {
+                word.get_or_insert_with(String::new).push(c)
+            }
+            '$' if chars.peek() == Some(&'(') => {
+                chars.next();
+                nesting.push(Nesting::Parenthesis);
+                end_segment(&mut segments, &mut word);
+            }
+            '(' => {
+                nesting.push(Nesting::Parenthesis);
+                end_segment(&mut segments, &mut word);
+            }
+            ')' => {
+                if nesting.pop() == Some(Nesting::SubstitutionInQuotes) {
+                    in_double_quotes = true;
+                }
+                end_segment(&mut segments, &mut word);
+            }
+            '`' => {
+                if nesting.last() == Some(&Nesting::BacktickInQuotes) {
+                    nesting.pop();
+                    in_double_quotes = true;
+                }
+                end_segment(&mut segments, &mut word);
+            }
+            ';' | '&' | '|' | '\n' => end_segment(&mut segments, &mut word),
+            c if c.is_whitespace() => end_word(&mut segments, &mut word),
+            c => word.get_or_insert_with(String::new).push(c),
+        }
+    }
+    end_word(&mut segments, &mut word);
+    segments.retain(|segment| !segment.is_empty());
+    segments
+}
+
+// Takes the directory off, so rm and /bin/rm both read as rm.
+fn binary_name(word: &str) -> String {
+    Path::new(word)
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_else(|| word.to_string())
+}
+
+fn is_assignment(word: &str) -> bool {
+    word.split_once('=').is_some_and(|(name, _)| {
+        !name.is_empty() && name.chars().all(|c| c.is_alphanumeric() || c == '_')
+    })
+}
+
+pub fn check_command(config: &SandboxConfig, command: &str) -> Result<(), String> {
+    for binary in command_binaries(command) {
+        if config.deny.contains(&binary) {
+            return Err(format!("'{}' is on the sandbox deny list", binary));
+        }
+        if !config.allow.is_empty() && !config.allow.contains(&binary) {
+            return Err(format!("'{}' is not on the sandbox allow list", binary));
+        }
+    }
+    Ok(())
+}
+
+// True when path, relative to directory, points outside of it, either by its
+// components or through a symlink on the way.
+pub fn escapes_directory(directory: &Path, path: &str) -> bool {
+    let mut depth = 0;
+    for component in Path::new(path).components() {
+        match component {
+            Component::Normal(_) => depth += 1,
+            Component::ParentDir if depth == 0 => return true,
+            Component::ParentDir => depth -= 1,
+            Component::CurDir => {}
+            Component::RootDir | Component::Prefix(_) => return true,
+        }
+    }
+
+    let Ok(root) = directory.canonicalize() else {
+        return true;
+    };
+    let target = directory.join(path);
+    let existing = target
+        .ancestors()
+        .find(|ancestor| fs::symlink_metadata(ancestor).is_ok());
+    match existing.map(Path::canonicalize) {
+        Some(Ok(resolved)) => !resolved.starts_with(&root),
+        // a dangling symlink, its target cannot be checked
+        _ => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn config(allow: &[&str], deny: &[&str]) -> SandboxConfig {
+        SandboxConfig {
+            allow: allow.iter().map(|binary| binary.to_string()).collect(),
+            deny: deny.iter().map(|binary| binary.to_string()).collect(),
+            ..SandboxConfig::default()
+        }
+    }
+
+    #[test]
+    fn finds_the_binary_of_each_segment() {
+        assert_eq!(
+            command_binaries("RUST_LOG=debug cargo test && grep -r x . | wc -l; echo $(date)"),
+            vec!["cargo", "grep", "wc", "echo", "date"]
+        );
+    }
+
+    #[test]
+    fn wrappers_and_the_wrapped_command_are_both_binaries() {
+        assert_eq!(command_binaries("sudo rm -rf /"), vec!["sudo", "rm"]);
+        assert_eq!(command_binaries("sudo -u root rm x"), vec!["sudo", "rm"]);
+        assert_eq!(command_binaries("env -i A=1 rm x"), vec!["env", "rm"]);
+        assert_eq!(
+            command_binaries("nice -n 10 time rm x"),
+            vec!["nice", "time", "rm"]
+        );
+        assert_eq!(
+            command_binaries("timeout -s KILL 5 rm x"),
+            vec!["timeout", "rm"]
+        );
+        assert_eq!(command_binaries("exec rm x"), vec!["exec", "rm"]);
+    }
+
+    #[test]
+    fn quotes_and_paths_are_stripped() {
+        assert_eq!(command_binaries("\"rm\" -rf x"), vec!["rm"]);
+        assert_eq!(command_binaries("'rm' -rf x"), vec!["rm"]);
+        assert_eq!(command_binaries("\\rm -rf x"), vec!["rm"]);
+        assert_eq!(command_binaries("/bin/rm -rf x"), vec!["rm"]);
+        assert_eq!(command_binaries("r\"m\" -rf x"), vec!["rm"]);
+    }
+
+    #[test]
+    fn keywords_and_groups_are_skipped() {
+        assert_eq!(
+            command_binaries("if true; then rm x; fi"),
+            vec!["true", "rm"]
+        );
+        assert_eq!(command_binaries("{ rm x; }"), vec!["rm"]);
+        assert_eq!(command_binaries("! rm x"), vec!["rm"]);
+        assert_eq!(
+            command_binaries("while true; do rm x; done"),
+            vec!["true", "rm"]
+        );
+        assert_eq!(
+            command_binaries("until false\ndo rm x\ndone"),
+            vec!["false", "rm"]
+        );
+        assert_eq!(
+            command_binaries("for f in *.rs; do rm $f; done"),
+            vec!["rm"]
+        );
+        assert_eq!(command_binaries("(cd x && rm y)"), vec!["cd", "rm"]);
+    }
+
+    #[test]
+    fn shells_eval_xargs_and_find_run_their_arguments() {
+        assert_eq!(command_binaries("sh -c 'rm x'"), vec!["sh", "rm"]);
+        assert_eq!(
+            command_binaries("bash -c \"ls; rm x\""),
+            vec!["bash", "ls", "rm"]
+        );
+        assert_eq!(command_binaries("bash -lc 'rm x'"), vec!["bash", "rm"]);
+        assert_eq!(
+            command_binaries("sudo sh -c 'rm x'"),
+            vec!["sudo", "sh", "rm"]
+        );
+        assert_eq!(command_binaries("eval rm x"), vec!["eval", "rm"]);
+        assert_eq!(command_binaries("eval 'rm x'"), vec!["eval", "rm"]);
+        assert_eq!(command_binaries("ls | xargs rm"), vec!["ls", "xargs", "rm"]);
+        assert_eq!(
+            command_binaries("ls | xargs -n 1 -I {} rm {}"),
+            vec!["ls", "xargs", "rm"]
+        );
+        assert_eq!(
+            command_binaries("find . -name '*.o' -exec rm {} \\;"),
+            vec!["find", "rm"]
+        );
+        assert_eq!(
+            command_binaries("find . -execdir sudo rm {} +"),
+            vec!["find", "sudo", "rm"]
+        );
+    }
+
+    #[test]
+    fn substitutions_inside_quotes_are_commands() {
+        assert_eq!(command_binaries("echo \"$(rm x)\""), vec!["echo", "rm"]);
+        assert_eq!(command_binaries("echo \"`rm x`\""), vec!["echo", "rm"]);
+        assert_eq!(command_binaries("echo '$(rm x)'"), vec!["echo"]);
+        assert_eq!(command_binaries("echo \"a; rm x\""), vec!["echo"]);
+    }
+
+    #[test]
+    fn redirections_and_comments_are_not_commands() {
+        assert_eq!(
+            command_binaries("cargo test 2>&1 | tail"),
+            vec!["cargo", "tail"]
+        );
+        assert_eq!(command_binaries("cargo test &> log"), vec!["cargo"]);
+        assert_eq!(command_binaries("cargo test # rm x"), vec!["cargo"]);
+        assert_eq!(command_binaries("PATH=/usr/bin cargo test"), vec!["cargo"]);
+    }
+
+    #[test]
+    fn deny_list_catches_every_bypass() {
+        let config = config(&[], &["rm"]);
+        for command in [
+            "if true; then rm x; fi",
+            "{ rm x; }",
+            "! rm x",
+            "while true; do rm x; done",
+            "sh -c 'rm x'",
+            "bash -c 'rm x'",
+            "eval rm x",
+            "xargs rm",
+            "find . -exec rm {} \\;",
+            "echo \"$(rm x)\"",
+        ] {
+            assert!(check_command(&config, command).is_err(), "{}", command);
+        }
+    }
+
+    #[test]
+    fn allow_list_blocks_wrappers_it_does_not_name() {
+        let config = config(&["rm"], &[]);
+        assert!(check_command(&config, "rm x").is_ok());
+        assert!(check_command(&config, "sudo rm -rf /").is_err());
+        assert!(check_command(&config, "env rm x").is_err());
+        assert!(check_command(&config, "sh -c 'rm x'").is_err());
+        assert!(check_command(&config, "xargs rm").is_err());
+        assert!(check_command(&config, "find . -exec rm {} +").is_err());
+    }
+
+    #[test]
+    fn deny_list_matches_wrappers_quotes_and_paths() {
+        let config = config(&[], &["rm", "sudo"]);
+        assert!(check_command(&config, "sudo ls").is_err());
+        assert!(check_command(&config, "nice rm x").is_err());
+        assert!(check_command(&config, "\"rm\" -rf x").is_err());
+        assert!(check_command(&config, "'rm' -rf x").is_err());
+        assert!(check_command(&config, "/bin/rm -rf x").is_err());
+        assert!(check_command(&config, "ls && /usr/bin/rm x").is_err());
+        assert!(check_command(&config, "ls -la").is_ok());
+    }
+
+    #[test]
+    fn relative_paths_stay_inside() {
+        let directory = TempDir::new().unwrap();
+        assert!(!escapes_directory(directory.path(), "src/main.rs"));
+        assert!(!escapes_directory(directory.path(), "./src/../Cargo.toml"));
+        assert!(!escapes_directory(directory.path(), "new/dir/file.rs"));
+    }
+
+    #[test]
+    fn parent_and_absolute_paths_escape() {
+        let directory = TempDir::new().unwrap();
+        assert!(escapes_directory(directory.path(), "../x"));
+        assert!(escapes_directory(directory.path(), "src/../../x"));
+        assert!(escapes_directory(directory.path(), "../../etc/shadow"));
+        assert!(escapes_directory(directory.path(), "/etc/passwd"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn symlinks_pointing_outside_escape() {
+        use std::os::unix::fs::symlink;
+        let outside = TempDir::new().unwrap();
+        let directory = TempDir::new().unwrap();
+        fs::write(outside.path().join("secret"), "x").unwrap();
+        symlink(outside.path(), directory.path().join("link")).unwrap();
+        symlink(
+            outside.path().join("secret"),
+            directory.path().join("secret"),
+        )
+        .unwrap();
+        symlink(
+            outside.path().join("missing"),
+            directory.path().join("dangling"),
+        )
+        .unwrap();
+
+        assert!(escapes_directory(directory.path(), "link/secret"));
+        assert!(escapes_directory(directory.path(), "link/new/file"));
+        assert!(escapes_directory(directory.path(), "secret"));
+        assert!(escapes_directory(directory.path(), "dangling"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn symlinks_inside_the_directory_stay_inside() {
+        use std::os::unix::fs::symlink;
+        let directory = TempDir::new().unwrap();
+        fs::create_dir(directory.path().join("src")).unwrap();
+        symlink(directory.path().join("src"), directory.path().join("link")).unwrap();
+        assert!(!escapes_directory(directory.path(), "link/main.rs"));
+    }
+}
@@ -0,0 +1,57 @@
+use super::AuditRepository;
+use crate::repository::db::SqliteRepository;
+use crate::sandbox::entity::audit_entity::AuditEntity;
+use chrono::NaiveDateTime;
+use rusqlite::{params, Result};
+
+const DATE_TIME_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+
+impl AuditRepository for SqliteRepository {
+    type Error = rusqlite::Error;
+
+    fn fetch_audit(&self, limit: u32) -> Result<Vec<AuditEntity>, Self::Error> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, command, directory, source, exit_code, blocked, created_at
+             FROM command_audit ORDER BY created_at DESC, rowid DESC LIMIT ?1",
+        )?;
+        let rows = stmt.query_map(params![limit], |row| {
+            let created_at_str: String = row.get(6)?;
+            let created_at = NaiveDateTime::parse_from_str(&created_at_str, DATE_TIME_FORMAT)
+                .expect("Invalid DateTime format");
+
+            Ok(AuditEntity::new(
+                row.get(0)?,
+                row.get(1)?,
+                row.get(2)?,
+                row.get(3)?,
+                row.get(4)?,
+                row.get(5)?,
+                created_at,
+            ))
+        })?;
+
+        let mut audit = Vec::new();
+        for entry in rows {
+            audit.push(entry?);
+        }
+        Ok(audit)
+    }
+
+    fn add_audit(&self, entry: &AuditEntity) -> Result<(), Self::Error> {
+        let created_at_str = entry.created_at.format(DATE_TIME_FORMAT).to_string();
+        self.conn.execute(
+            "INSERT INTO command_audit (id, command, directory, source, exit_code, blocked, created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            params![
+                entry.id,
+                entry.command,
+                entry.directory,
+                entry.source,
+                entry.exit_code,
+                entry.blocked,
+                created_at_str
+            ],
+        )?;
+        Ok(())
+    }
+}
@@ -0,0 +1,14 @@
+use super::entity::audit_entity::AuditEntity;
+use std::fmt::Debug;
+
+pub(crate) mod audit_repository;
+
+pub trait AuditRepository
+where
+    Self::Error: Debug,
+{
+    type Error;
+
+    fn fetch_audit(&self, limit: u32) -> Result<Vec<AuditEntity>, Self::Error>;
+    fn add_audit(&self, entry: &AuditEntity) -> Result<(), Self::Error>;
+}
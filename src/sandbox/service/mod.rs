@@ -0,0 +1 @@
+pub(crate) mod sandbox_service;
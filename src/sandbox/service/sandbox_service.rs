@@ -0,0 +1,100 @@
+use crate::common::unique_id::generate_uuid_v4;
+use crate::output::markdown::truncate;
+use crate::output::progress::status;
+use crate::project::config::SandboxConfig;
+use crate::sandbox::entity::audit_entity::AuditEntity;
+use crate::sandbox::model::policy::check_command;
+use crate::sandbox::repository::AuditRepository;
+use anyhow::{anyhow, Result};
+use chrono::Utc;
+use colored::*;
+use std::path::Path;
+use std::process::{Command, Output};
+
+const COMMAND_WIDTH: usize = 50;
+
+pub fn run_sandboxed<AR: AuditRepository>(
+    audit_repo: &AR,
+    config: &SandboxConfig,
+    command: &str,
+    directory: &Path,
+    source: &str,
+) -> Result<Output> {
+    let record = |exit_code: Option<i32>, blocked: Option<String>| {
+        let entry = AuditEntity::new(
+            generate_uuid_v4().to_string(),
+            command.to_string(),
+            directory.to_string_lossy().to_string(),
+            source.to_string(),
+            exit_code,
+            blocked,
+            Utc::now().naive_utc(),
+        );
+        if let Err(err) = audit_repo.add_audit(&entry) {
+            status(&format!(
+                "could not add the command to the audit log: {:?}",
+                err
+            ));
+        }
+    };
+    if let Err(reason) = check_command(config, command) {
+        record(None, Some(reason.clone()));
+        return Err(anyhow!("'{}' was blocked: {}", command, reason));
+    }
+
+    let mut process = if config.network {
+        Command::new("sh")
+    } else {
+        let mut unshare = Command::new("unshare");
+        unshare.args(["--net", "--map-root-user", "sh"]);
+        unshare
+    };
+    let output = process
+        .arg("-c")
+        .arg(command)
+        .current_dir(directory)
+        .output()
+        .map_err(|err| match config.network {
+            true => anyhow!("could not run '{}': {:?}", command, err),
+            false => anyhow!(
+                "could not run '{}' without network, this needs `unshare`: {:?}",
+                command,
+                err
+            ),
+        })?;
+    record(output.status.code(), None);
+    Ok(output)
+}
+
+pub fn list_audit<AR: AuditRepository>(audit_repo: &AR, limit: u32) -> Result<()> {
+    let audit = audit_repo
+        .fetch_audit(limit)
+        .map_err(|err| anyhow!("could not read the audit log: {:?}", err))?;
+    if audit.is_empty() {
+        println!("no commands were run yet");
+        return Ok(());
+    }
+    for entry in &audit {
+        let outcome = match (&entry.blocked, entry.exit_code) {
+            (Some(reason), _) => format!("blocked, {}", reason).red(),
+            (None, Some(0)) => "exit 0".green(),
+            (None, Some(code)) => format!("exit {}", code).yellow(),
+            (None, None) => "killed".yellow(),
+        };
+        println!(
+            "{}  {}  {}",
+            entry
+                .created_at
+                .format("%Y-%m-%d %H:%M")
+                .to_string()
+                .dimmed(),
+            truncate(&entry.command, COMMAND_WIDTH),
+            outcome
+        );
+        println!(
+            "    {}",
+            format!("{} in {}", entry.source, entry.directory).dimmed()
+        );
+    }
+    Ok(())
+}
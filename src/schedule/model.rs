@@ -0,0 +1,119 @@
+use anyhow::{anyhow, Result};
+
+const MARKER: &str = "# termai schedule:";
+
+#[derive(Debug, Clone)]
+pub struct ScheduledRun {
+    pub name: String,
+    pub cron: String,
+    pub command: String,
+}
+
+impl ScheduledRun {
+    pub fn to_crontab_lines(&self) -> String {
+        format!("{} {}\n{} {}\n", MARKER, self.name, self.cron, self.command)
+    }
+}
+
+pub fn validate_cron(cron: &str) -> Result<()> {
+    let fields = cron.split_whitespace().count();
+    let is_shortcut = cron.starts_with('@') && fields == 1;
+    if fields != 5 && !is_shortcut {
+        return Err(anyhow!(
+            "'{}' is not a cron expression, expected five fields like \"0 9 * * MON\"",
+            cron
+        ));
+    }
+    Ok(())
+}
+
+pub fn validate_name(name: &str) -> Result<()> {
+    if name.is_empty()
+        || !name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        return Err(anyhow!(
+            "schedule names may only contain letters, digits, '-' and '_'"
+        ));
+    }
+    Ok(())
+}
+
+// A crontab entry is one line, a line break in a value would start a new entry.
+pub fn validate_single_line(what: &str, value: &str) -> Result<()> {
+    if value.contains(['\n', '\r']) {
+        return Err(anyhow!("the {} of a schedule must be a single line", what));
+    }
+    Ok(())
+}
+
+pub fn shell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "'\\''"))
+}
+
+pub fn parse_crontab(crontab: &str) -> Vec<ScheduledRun> {
+    let mut runs = Vec::new();
+    let mut lines = crontab.lines();
+    while let Some(line) = lines.next() {
+        let Some(name) = line.strip_prefix(MARKER) else {
+            continue;
+        };
+        let Some(entry) = lines.next() else {
+            break;
+        };
+        let (cron, command) = split_cron_line(entry);
+        runs.push(ScheduledRun {
+            name: name.trim().to_string(),
+            cron,
+            command,
+        });
+    }
+    runs
+}
+
+pub fn remove_from_crontab(crontab: &str, name: &str) -> (String, bool) {
+    let mut kept = String::new();
+    let mut removed = false;
+    let mut lines = crontab.lines();
+    while let Some(line) = lines.next() {
+        if line
+            .strip_prefix(MARKER)
+            .is_some_and(|marked| marked.trim() == name)
+        {
+            lines.next();
+            removed = true;
+            continue;
+        }
+        kept.push_str(line);
+        kept.push('\n');
+    }
+    (kept, removed)
+}
+
+fn split_cron_line(line: &str) -> (String, String) {
+    let fields = if line.starts_with('@') { 1 } else { 5 };
+    let mut parts = line.splitn(fields + 1, ' ');
+    let cron = (0..fields)
+        .filter_map(|_| parts.next())
+        .collect::<Vec<&str>>()
+        .join(" ");
+    (cron, parts.next().unwrap_or_default().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn line_breaks_are_refused() {
+        assert!(validate_single_line("prompt", "summarize the logs").is_ok());
+        assert!(validate_single_line("prompt", "a\n* * * * * rm -rf ~").is_err());
+        assert!(validate_single_line("cron", "0 9 * * *\r* * * * * rm -rf ~").is_err());
+    }
+
+    #[test]
+    fn a_quoted_value_stays_one_shell_word() {
+        assert_eq!(shell_quote("it's"), "'it'\\''s'");
+    }
+}
@@ -0,0 +1 @@
+pub(crate) mod schedule_service;
@@ -0,0 +1,127 @@
+use crate::args::ScheduleAction;
+use crate::schedule::model::{
+    parse_crontab, remove_from_crontab, shell_quote, validate_cron, validate_name,
+    validate_single_line, ScheduledRun,
+};
+use anyhow::{anyhow, Result};
+use std::io::Write;
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+pub fn schedule(action: &ScheduleAction) -> Result<()> {
+    match action {
+        ScheduleAction::Add {
+            name,
+            cron,
+            prompt,
+            directory,
+            session,
+            dry_run,
+        } => add_schedule(name, cron, prompt, directory, session.as_deref(), *dry_run),
+        ScheduleAction::List => list_schedules(),
+        ScheduleAction::Remove { name } => remove_schedule(name),
+    }
+}
+
+fn add_schedule(
+    name: &str,
+    cron: &str,
+    prompt: &str,
+    directory: &str,
+    session: Option<&str>,
+    dry_run: bool,
+) -> Result<()> {
+    validate_name(name)?;
+    validate_single_line("cron expression", cron)?;
+    validate_cron(cron)?;
+    validate_single_line("prompt", prompt)?;
+    let directory = Path::new(directory)
+        .canonicalize()
+        .map_err(|err| anyhow!("could not resolve {}: {:?}", directory, err))?;
+    validate_single_line("directory", &directory.to_string_lossy())?;
+    let termai = std::env::current_exe()?;
+    let session = session.unwrap_or(name);
+    validate_single_line("session name", session)?;
+    let run = ScheduledRun {
+        name: name.to_string(),
+        cron: cron.to_string(),
+        command: format!(
+            "cd {} && {} --session {} {} . </dev/null >/dev/null 2>&1",
+            shell_quote(&directory.to_string_lossy()),
+            shell_quote(&termai.to_string_lossy()),
+            shell_quote(session),
+            shell_quote(prompt)
+        )
+        .replace('%', "\\%"),
+    };
+
+    if dry_run {
+        print!("{}", run.to_crontab_lines());
+        return Ok(());
+    }
+
+    let crontab = read_crontab()?;
+    if parse_crontab(&crontab)
+        .iter()
+        .any(|existing| existing.name == name)
+    {
+        return Err(anyhow!(
+            "a schedule named '{}' already exists, remove it first",
+            name
+        ));
+    }
+    write_crontab(&format!("{}{}", crontab, run.to_crontab_lines()))?;
+    println!(
+        "scheduled '{}' ({}), answers are added to session '{}'",
+        name, cron, session
+    );
+    Ok(())
+}
+
+fn list_schedules() -> Result<()> {
+    let runs = parse_crontab(&read_crontab()?);
+    if runs.is_empty() {
+        println!("no scheduled runs");
+    }
+    for run in runs {
+        println!("{:<20} {:<16} {}", run.name, run.cron, run.command);
+    }
+    Ok(())
+}
+
+fn remove_schedule(name: &str) -> Result<()> {
+    let (crontab, removed) = remove_from_crontab(&read_crontab()?, name);
+    if !removed {
+        return Err(anyhow!("no schedule named '{}'", name));
+    }
+    write_crontab(&crontab)?;
+    println!("removed schedule '{}'", name);
+    Ok(())
+}
+
+fn read_crontab() -> Result<String> {
+    let output = Command::new("crontab")
+        .arg("-l")
+        .output()
+        .map_err(|err| anyhow!("could not run crontab: {:?}", err))?;
+    if output.status.success() {
+        Ok(String::from_utf8_lossy(&output.stdout).to_string())
+    } else {
+        Ok(String::new())
+    }
+}
+
+fn write_crontab(crontab: &str) -> Result<()> {
+    let mut child = Command::new("crontab")
+        .arg("-")
+        .stdin(Stdio::piped())
+        .spawn()
+        .map_err(|err| anyhow!("could not run crontab: {:?}", err))?;
+    if let Some(stdin) = child.stdin.as_mut() {
+        stdin.write_all(crontab.as_bytes())?;
+    }
+    if !child.wait()?.success() {
+        return Err(anyhow!("crontab rejected the new entries"));
+    }
+    Ok(())
+}
@@ -0,0 +1,28 @@
+pub struct AttachmentEntity {
+    pub id: String,
+    pub message_id: String,
+    pub kind: String,
+    pub path: String,
+    pub hash: String,
+    pub content: String,
+}
+
+impl AttachmentEntity {
+    pub fn new(
+        id: String,
+        message_id: String,
+        kind: String,
+        path: String,
+        hash: String,
+        content: String,
+    ) -> Self {
+        Self {
+            id,
+            message_id,
+            kind,
+            path,
+            hash,
+            content,
+        }
+    }
+}
@@ -3,15 +3,23 @@ pub struct MessageEntity {
     pub session_id: String,
     pub role: String,
     pub content: String,
+    pub model: Option<String>,
 }
 
 impl MessageEntity {
-    pub fn new(id: String, session_id: String, role: String, content: String) -> Self {
+    pub fn new(
+        id: String,
+        session_id: String,
+        role: String,
+        content: String,
+        model: Option<String>,
+    ) -> Self {
         Self {
             id,
             session_id,
             role,
             content,
+            model,
         }
     }
 }
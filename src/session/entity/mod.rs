@@ -1,2 +1,6 @@
+pub(crate) mod attachment_entity;
+pub(crate) mod session_digest_entity;
 pub(crate) mod session_entity;
+pub(crate) mod session_pin_entity;
 pub(crate) mod message_entity;
+pub(crate) mod session_summary_entity;
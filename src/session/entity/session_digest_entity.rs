@@ -0,0 +1,27 @@
+use chrono::NaiveDateTime;
+
+pub struct SessionDigestEntity {
+    pub session_id: String,
+    pub depth: String,
+    pub content: String,
+    pub message_count: u32,
+    pub created_at: NaiveDateTime,
+}
+
+impl SessionDigestEntity {
+    pub fn new(
+        session_id: String,
+        depth: String,
+        content: String,
+        message_count: u32,
+        created_at: NaiveDateTime,
+    ) -> Self {
+        Self {
+            session_id,
+            depth,
+            content,
+            message_count,
+            created_at,
+        }
+    }
+}
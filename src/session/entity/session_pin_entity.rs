@@ -0,0 +1,17 @@
+use chrono::NaiveDateTime;
+
+pub struct SessionPinEntity {
+    pub session_id: String,
+    pub model: String,
+    pub pinned_at: NaiveDateTime,
+}
+
+impl SessionPinEntity {
+    pub fn new(session_id: String, model: String, pinned_at: NaiveDateTime) -> Self {
+        Self {
+            session_id,
+            model,
+            pinned_at,
+        }
+    }
+}
@@ -0,0 +1,9 @@
+use chrono::NaiveDateTime;
+
+pub struct SessionSummaryEntity {
+    pub name: String,
+    pub expires_at: NaiveDateTime,
+    pub current: i32,
+    pub message_count: u32,
+    pub model: Option<String>,
+}
@@ -0,0 +1,155 @@
+use crate::common::unique_id::generate_uuid_v4;
+use crate::guard::model::wrap_data;
+use crate::path::model::Files;
+use crate::session::entity::attachment_entity::AttachmentEntity;
+use sha2::{Digest, Sha256};
+use std::fmt;
+use std::path::Path;
+
+const DIFF_EXTENSIONS: [&str; 2] = ["diff", "patch"];
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum AttachmentKind {
+    File,
+    Diff,
+    Image,
+    Document,
+}
+
+impl AttachmentKind {
+    pub fn from_str(kind: &str) -> Self {
+        match kind {
+            "diff" => AttachmentKind::Diff,
+            "image" => AttachmentKind::Image,
+            "document" => AttachmentKind::Document,
+            _ => AttachmentKind::File,
+        }
+    }
+}
+
+impl fmt::Display for AttachmentKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AttachmentKind::File => write!(f, "file"),
+            AttachmentKind::Diff => write!(f, "diff"),
+            AttachmentKind::Image => write!(f, "image"),
+            AttachmentKind::Document => write!(f, "document"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Attachment {
+    pub id: String,
+    pub kind: AttachmentKind,
+    pub path: String,
+    pub content: String,
+}
+
+impl From<&AttachmentEntity> for Attachment {
+    fn from(entity: &AttachmentEntity) -> Self {
+        Self {
+            id: entity.id.clone(),
+            kind: AttachmentKind::from_str(&entity.kind),
+            path: entity.path.clone(),
+            content: entity.content.clone(),
+        }
+    }
+}
+
+impl From<&Files> for Attachment {
+    fn from(file: &Files) -> Self {
+        let extension = Path::new(&file.path)
+            .extension()
+            .and_then(|e| e.to_str())
+            .map(|e| e.to_lowercase())
+            .unwrap_or_default();
+        let kind = if file.is_image() {
+            AttachmentKind::Image
+        } else if file.is_document() {
+            AttachmentKind::Document
+        } else if DIFF_EXTENSIONS.contains(&extension.as_str()) {
+            AttachmentKind::Diff
+        } else {
+            AttachmentKind::File
+        };
+        Self {
+            id: "".to_string(),
+            kind,
+            path: file.path.clone(),
+            content: file.content.clone(),
+        }
+    }
+}
+
+impl Attachment {
+    pub fn to_entity(&self, message_id: &str) -> AttachmentEntity {
+        let id = if self.id.is_empty() {
+            generate_uuid_v4().to_string()
+        } else {
+            self.id.clone()
+        };
+        AttachmentEntity::new(
+            id,
+            message_id.to_string(),
+            self.kind.to_string(),
+            self.path.clone(),
+            self.hash(),
+            self.content.clone(),
+        )
+    }
+
+    pub fn hash(&self) -> String {
+        format!("{:x}", Sha256::digest(self.content.as_bytes()))
+    }
+
+    pub fn is_text(&self) -> bool {
+        !matches!(self.kind, AttachmentKind::Image | AttachmentKind::Document)
+    }
+
+    pub fn to_prompt_text(&self) -> Option<String> {
+        if !self.is_text() {
+            return None;
+        }
+        Some(wrap_data(&self.path, &self.content))
+    }
+
+    pub fn image_data_url(&self) -> Option<String> {
+        if self.kind != AttachmentKind::Image {
+            return None;
+        }
+        let extension = Path::new(&self.path)
+            .extension()
+            .and_then(|e| e.to_str())
+            .map(|e| e.to_lowercase())
+            .unwrap_or_default();
+        let mime = match extension.as_str() {
+            "jpg" | "jpeg" => "image/jpeg",
+            "gif" => "image/gif",
+            "webp" => "image/webp",
+            _ => "image/png",
+        };
+        Some(format!("data:{};base64,{}", mime, self.content))
+    }
+
+    pub fn document_data_url(&self) -> Option<String> {
+        if self.kind != AttachmentKind::Document {
+            return None;
+        }
+        Some(format!("data:application/pdf;base64,{}", self.content))
+    }
+
+    pub fn file_name(&self) -> String {
+        Path::new(&self.path)
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_else(|| self.path.clone())
+    }
+
+    pub fn copy_with_content(&self, content: String) -> Self {
+        Self {
+            content,
+            ..self.clone()
+        }
+    }
+}
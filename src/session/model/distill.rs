@@ -0,0 +1,110 @@
+use crate::openai::model::role::Role;
+use crate::output::markdown::truncate;
+use crate::session::model::message::Message;
+use crate::snippet::model::code_block::CodeBlock;
+use crate::usage::model::token_usage::estimate_tokens;
+use regex::Regex;
+
+pub struct Transcript {
+    pub entries: Vec<String>,
+    pub code: Vec<CodeBlock>,
+}
+
+// Numbers the messages and labels each code block [C1], [C2]... so the notes
+// and the final document can point at code without copying it.
+pub fn transcript(messages: &[Message]) -> Transcript {
+    let mut code = Vec::new();
+    let entries = messages
+        .iter()
+        .filter(|message| message.role != Role::System)
+        .enumerate()
+        .map(|(index, message)| {
+            format!(
+                "#{} {}:\n{}",
+                index + 1,
+                message.role,
+                label_code_blocks(&message.content, &mut code)
+            )
+        })
+        .collect();
+    Transcript { entries, code }
+}
+
+pub fn chunks(entries: &[String], max_tokens: u32) -> Vec<String> {
+    let mut chunks = Vec::new();
+    let mut current = Vec::new();
+    let mut tokens = 0;
+    for entry in entries {
+        let entry = truncate(entry, max_tokens as usize * 4);
+        let entry_tokens = estimate_tokens(&entry);
+        if !current.is_empty() && tokens + entry_tokens > max_tokens {
+            chunks.push(current.join("\n\n"));
+            current.clear();
+            tokens = 0;
+        }
+        tokens += entry_tokens;
+        current.push(entry);
+    }
+    if !current.is_empty() {
+        chunks.push(current.join("\n\n"));
+    }
+    chunks
+}
+
+// Replaces lines holding only a {{Cn}} placeholder with the labelled code block,
+// returns the document and the placeholders that did not match a block.
+pub fn fill_code(document: &str, code: &[CodeBlock]) -> (String, Vec<String>) {
+    let placeholder = Regex::new(r"^`*\{\{C(\d+)\}\}`*$").unwrap();
+    let mut unknown = Vec::new();
+    let lines = document
+        .lines()
+        .filter_map(|line| {
+            let Some(caps) = placeholder.captures(line.trim()) else {
+                return Some(line.to_string());
+            };
+            let block = caps[1]
+                .parse::<usize>()
+                .ok()
+                .and_then(|number| code.get(number.checked_sub(1)?));
+            match block {
+                Some(block) => Some(format!(
+                    "```{}\n{}```",
+                    block.language.as_deref().unwrap_or_default(),
+                    block.content
+                )),
+                None => {
+                    unknown.push(format!("C{}", &caps[1]));
+                    None
+                }
+            }
+        })
+        .collect::<Vec<String>>();
+    (lines.join("\n"), unknown)
+}
+
+fn label_code_blocks(content: &str, code: &mut Vec<CodeBlock>) -> String {
+    let mut lines = Vec::new();
+    let mut current: Option<CodeBlock> = None;
+    for line in content.lines() {
+        if let Some(info) = line.trim_start().strip_prefix("```") {
+            match current.take() {
+                Some(block) => code.push(block),
+                None => {
+                    lines.push(format!("[C{}]", code.len() + 1));
+                    current = Some(CodeBlock {
+                        language: info.split_whitespace().next().map(|l| l.to_lowercase()),
+                        content: String::new(),
+                    });
+                }
+            }
+        } else if let Some(block) = current.as_mut() {
+            block.content.push_str(line);
+            block.content.push('\n');
+        }
+        lines.push(line.to_string());
+    }
+    if let Some(block) = current {
+        code.push(block);
+    }
+    lines.join("\n")
+}
@@ -0,0 +1,68 @@
+use crate::path::budget::{PIPED_INPUT_NAME, TRUNCATED_MARKER};
+use crate::session::model::attachment::{Attachment, AttachmentKind};
+use crate::session::model::session::Session;
+use crate::summary::service::summary_service::SUMMARY_MARKER;
+use similar::TextDiff;
+use std::fs;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DriftStatus {
+    Unchanged,
+    Changed,
+    Missing,
+}
+
+pub struct FileDrift {
+    pub path: String,
+    pub status: DriftStatus,
+    pub sent: String,
+    pub current: Option<String>,
+}
+
+impl FileDrift {
+    pub fn diff(&self) -> String {
+        let current = self.current.as_deref().unwrap_or_default();
+        TextDiff::from_lines(self.sent.as_str(), current)
+            .unified_diff()
+            .header(&self.path, &self.path)
+            .to_string()
+    }
+}
+
+pub fn context_drift(session: &Session) -> Vec<FileDrift> {
+    let mut latest: Vec<&Attachment> = Vec::new();
+    for attachment in session
+        .messages
+        .iter()
+        .flat_map(|message| message.attachments.iter())
+        .filter(|attachment| is_comparable(attachment))
+    {
+        latest.retain(|known| known.path != attachment.path);
+        latest.push(attachment);
+    }
+
+    latest
+        .into_iter()
+        .map(|attachment| {
+            let current = fs::read_to_string(&attachment.path).ok();
+            let status = match &current {
+                None => DriftStatus::Missing,
+                Some(content) if *content == attachment.content => DriftStatus::Unchanged,
+                Some(_) => DriftStatus::Changed,
+            };
+            FileDrift {
+                path: attachment.path.clone(),
+                status,
+                sent: attachment.content.clone(),
+                current,
+            }
+        })
+        .collect()
+}
+
+fn is_comparable(attachment: &Attachment) -> bool {
+    attachment.kind == AttachmentKind::File
+        && attachment.path != PIPED_INPUT_NAME
+        && !attachment.content.starts_with(SUMMARY_MARKER)
+        && !attachment.content.trim_end().ends_with(TRUNCATED_MARKER)
+}
@@ -2,14 +2,19 @@ use crate::openai::model::role::Role;
 use crate::openai::service::chat::SYSTEM_PROMPT;
 use crate::output::message;
 use crate::session::entity::message_entity::MessageEntity;
+use crate::session::model::attachment::{Attachment, AttachmentKind};
 use std::collections::HashMap;
 
+const CITATION_INSTRUCTION: &str = "When a statement relies on one of the files above, cite it inline as 【path:line】, or 【path】 if you can't tell the line.";
+
 #[derive(Clone, Debug)]
 pub struct Message {
     pub id: String,
     pub role: Role,
     pub content: String,
+    pub attachments: Vec<Attachment>,
     pub redaction_mapping: Option<HashMap<String, String>>,
+    pub model: Option<String>,
 }
 
 impl From<&MessageEntity> for Message {
@@ -18,7 +23,9 @@ impl From<&MessageEntity> for Message {
             id: entity.id.to_string(),
             role: Role::from_str(&entity.role),
             content: entity.content.clone(),
+            attachments: vec![],
             redaction_mapping: None,
+            model: entity.model.clone(),
         }
     }
 }
@@ -30,57 +37,88 @@ impl Message {
             session_id: session_id.to_string(),
             role: self.role.to_string(),
             content: self.content.clone(),
+            model: self.model.clone(),
         }
     }
 
     pub fn to_output_message(&self) -> message::Message {
         message::Message {
+            id: self.id.clone(),
             role: self.role.clone(),
             message: self.content.to_string(),
+            attachments: vec![],
+            model: self.model.clone(),
         }
     }
 
-    pub fn copy_with_id(&self, id: String) -> Self {
-        Self { id, ..self.clone() }
-    }
-
-    pub fn prepend_content(&self, text: &str) -> Self {
-        let new_content = format!("{}\n\n{}", text, self.content);
-        Self {
-            id: self.id.to_string(),
+    pub fn to_output_message_with_context(&self) -> message::Message {
+        message::Message {
+            id: self.id.clone(),
             role: self.role.clone(),
-            content: new_content,
-            redaction_mapping: self.redaction_mapping.clone(),
+            message: self.content.to_string(),
+            attachments: self.attachments.clone(),
+            model: self.model.clone(),
         }
     }
 
-    pub fn remove_from_content(&self, text: &str) -> Self {
-        let new_content = self.content.replace(text, "");
-        let new_content = new_content.trim();
-        Self {
-            id: self.id.to_string(),
-            role: self.role.clone(),
-            content: new_content.to_string(),
-            redaction_mapping: self.redaction_mapping.clone(),
+    pub fn content_with_context(&self) -> String {
+        let context = self
+            .attachments
+            .iter()
+            .filter_map(Attachment::to_prompt_text)
+            .collect::<Vec<String>>();
+        if context.is_empty() {
+            return self.content.clone();
         }
+        format!(
+            "{}\n{}\n{}",
+            self.content,
+            context.join("\n"),
+            CITATION_INSTRUCTION
+        )
+    }
+
+    pub fn image_attachments(&self) -> Vec<&Attachment> {
+        self.attachments_of(AttachmentKind::Image)
+    }
+
+    pub fn document_attachments(&self) -> Vec<&Attachment> {
+        self.attachments_of(AttachmentKind::Document)
+    }
+
+    fn attachments_of(&self, kind: AttachmentKind) -> Vec<&Attachment> {
+        self.attachments
+            .iter()
+            .filter(|attachment| attachment.kind == kind)
+            .collect()
+    }
+
+    pub fn copy_with_id(&self, id: String) -> Self {
+        Self { id, ..self.clone() }
     }
 }
 
-pub fn contains_system_prompt(messages: &Vec<Message>) -> bool {
+pub fn contains_system_prompt(messages: &[Message]) -> bool {
     messages.iter().any(|m| m.role == Role::System)
 }
 
 pub fn messages_with_system_prompt(
     user_prompt: Option<String>,
-    messages: &Vec<Message>,
+    project_preamble: Option<String>,
+    messages: &[Message],
 ) -> Vec<Message> {
     let mut new_messages = Vec::with_capacity(messages.len() + 1);
-    let system_prompt = user_prompt.unwrap_or_else(|| SYSTEM_PROMPT.to_string());
+    let mut system_prompt = user_prompt.unwrap_or_else(|| SYSTEM_PROMPT.to_string());
+    if let Some(preamble) = project_preamble {
+        system_prompt = format!("{}\n\n{}", system_prompt, preamble);
+    }
     new_messages.push(Message {
         id: "".to_string(),
         role: Role::System,
         content: system_prompt,
+        attachments: vec![],
         redaction_mapping: None,
+        model: None,
     });
     for m in messages {
         new_messages.push(m.clone());
@@ -1,2 +1,8 @@
+pub(crate) mod attachment;
 pub(crate) mod session;
 pub(crate) mod message;
+pub(crate) mod session_query;
+pub(crate) mod notebook;
+pub(crate) mod drift;
+pub(crate) mod timeline;
+pub(crate) mod distill;
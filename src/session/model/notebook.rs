@@ -0,0 +1,182 @@
+use crate::openai::model::role::Role;
+use crate::session::model::session::Session;
+use serde_json::{json, Value};
+
+const DEFAULT_LANGUAGE: &str = "python";
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExportFormat {
+    Jupyter,
+    Quarto,
+}
+
+impl ExportFormat {
+    pub fn from_str(format: &str) -> Self {
+        match format {
+            "quarto" | "qmd" => ExportFormat::Quarto,
+            _ => ExportFormat::Jupyter,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Cell {
+    Markdown(String),
+    Code {
+        language: Option<String>,
+        source: String,
+    },
+}
+
+pub fn session_cells(session: &Session) -> Vec<Cell> {
+    let mut cells = Vec::new();
+    for message in session.messages.iter() {
+        match message.role {
+            Role::System => continue,
+            Role::User => cells.push(Cell::Markdown(format!(
+                "**{}:**\n\n{}",
+                message.role,
+                message.content.trim()
+            ))),
+            _ => cells.extend(split_cells(&message.content)),
+        }
+    }
+    cells
+}
+
+pub fn split_cells(content: &str) -> Vec<Cell> {
+    let mut cells = Vec::new();
+    let mut prose = String::new();
+    let mut code: Option<(Option<String>, String)> = None;
+
+    for line in content.lines() {
+        if let Some(info) = line.trim_start().strip_prefix("```") {
+            match code.take() {
+                Some((language, source)) => cells.push(Cell::Code { language, source }),
+                None => {
+                    push_prose(&mut cells, &mut prose);
+                    let language = info.split_whitespace().next().map(|l| l.to_lowercase());
+                    code = Some((language, String::new()));
+                }
+            }
+            continue;
+        }
+        match code.as_mut() {
+            Some((_, source)) => {
+                source.push_str(line);
+                source.push('\n');
+            }
+            None => {
+                prose.push_str(line);
+                prose.push('\n');
+            }
+        }
+    }
+    if let Some((language, source)) = code {
+        cells.push(Cell::Code { language, source });
+    }
+    push_prose(&mut cells, &mut prose);
+    cells
+}
+
+fn push_prose(cells: &mut Vec<Cell>, prose: &mut String) {
+    let text = prose.trim();
+    if !text.is_empty() {
+        cells.push(Cell::Markdown(text.to_string()));
+    }
+    prose.clear();
+}
+
+pub fn kernel_language(cells: &[Cell]) -> String {
+    let mut counts: Vec<(String, usize)> = Vec::new();
+    for cell in cells {
+        if let Cell::Code {
+            language: Some(language),
+            ..
+        } = cell
+        {
+            match counts.iter_mut().find(|(l, _)| l == language) {
+                Some((_, count)) => *count += 1,
+                None => counts.push((language.clone(), 1)),
+            }
+        }
+    }
+    counts
+        .into_iter()
+        .rev()
+        .max_by_key(|(_, count)| *count)
+        .map(|(language, _)| language)
+        .unwrap_or_else(|| DEFAULT_LANGUAGE.to_string())
+}
+
+fn is_executable(language: &Option<String>, kernel: &str) -> bool {
+    language
+        .as_deref()
+        .is_none_or(|language| language == kernel)
+}
+
+fn fenced(language: &Option<String>, source: &str) -> String {
+    format!("```{}\n{}```", language.as_deref().unwrap_or(""), source)
+}
+
+pub fn to_jupyter(title: &str, cells: &[Cell]) -> Value {
+    let kernel = kernel_language(cells);
+    let mut notebook_cells = vec![markdown_cell(&format!("# {}", title))];
+    for cell in cells {
+        notebook_cells.push(match cell {
+            Cell::Markdown(text) => markdown_cell(text),
+            Cell::Code { language, source } if is_executable(language, &kernel) => json!({
+                "cell_type": "code",
+                "execution_count": null,
+                "metadata": {},
+                "outputs": [],
+                "source": source_lines(source.trim_end()),
+            }),
+            Cell::Code { language, source } => markdown_cell(&fenced(language, source)),
+        });
+    }
+    json!({
+        "cells": notebook_cells,
+        "metadata": {
+            "kernelspec": {
+                "display_name": kernel,
+                "language": kernel,
+                "name": kernel,
+            },
+            "language_info": { "name": kernel },
+        },
+        "nbformat": 4,
+        "nbformat_minor": 5,
+    })
+}
+
+pub fn to_quarto(title: &str, cells: &[Cell]) -> String {
+    let kernel = kernel_language(cells);
+    let mut document = format!("---\ntitle: \"{}\"\n---\n", title.replace('"', "'"));
+    for cell in cells {
+        document.push('\n');
+        match cell {
+            Cell::Markdown(text) => document.push_str(text),
+            Cell::Code { language, source } if is_executable(language, &kernel) => {
+                document.push_str(&format!("```{{{}}}\n{}```", kernel, source))
+            }
+            Cell::Code { language, source } => document.push_str(&fenced(language, source)),
+        }
+        document.push('\n');
+    }
+    document
+}
+
+fn markdown_cell(text: &str) -> Value {
+    json!({
+        "cell_type": "markdown",
+        "metadata": {},
+        "source": source_lines(text),
+    })
+}
+
+fn source_lines(text: &str) -> Vec<String> {
+    text.split_inclusive('\n')
+        .map(|line| line.to_string())
+        .collect()
+}
@@ -1,11 +1,13 @@
 use crate::common;
 use crate::config::repository::ConfigRepository;
 use crate::openai::model::role::Role;
-use crate::redactions::redact::redact;
+use crate::redactions::redact::{redact, redact_with_mapping};
 use crate::redactions::revert::unredact;
 use crate::session::entity::session_entity::SessionEntity;
+use crate::session::model::attachment::Attachment;
 use crate::session::model::message::Message;
 use chrono::{Duration, NaiveDateTime, Utc};
+use std::collections::HashMap;
 
 #[derive(Debug)]
 pub struct Session {
@@ -48,54 +50,136 @@ impl Session {
         Self {
             id: self.id.clone(),
             name: self.name.clone(),
-            expires_at: self.expires_at.clone(),
-            current: self.current.clone(),
+            expires_at: self.expires_at,
+            current: self.current,
             messages: messages.to_vec(),
             temporary: self.temporary,
         }
     }
 
     pub fn add_raw_message(&mut self, message: String, role: Role) {
+        self.add_message_with_attachments(message, role, vec![]);
+    }
+
+    pub fn add_message_with_attachments(
+        &mut self,
+        message: String,
+        role: Role,
+        attachments: Vec<Attachment>,
+    ) {
         self.messages.push(Message {
             id: "".to_string(),
             role,
             content: message,
+            attachments,
             redaction_mapping: None,
+            model: None,
         });
     }
 
+    pub fn context_paths(&self) -> Vec<String> {
+        let mut paths: Vec<String> = Vec::new();
+        for attachment in self.messages.iter().flat_map(|m| m.attachments.iter()) {
+            if !paths.contains(&attachment.path) {
+                paths.push(attachment.path.clone());
+            }
+        }
+        paths
+    }
+
     pub fn redact<R: ConfigRepository>(&mut self, repo: &R) {
         let mut redacted_messages = Vec::with_capacity(self.messages.len());
         for message in self.messages.iter() {
             let (redacted_input, mapped_redactions) = redact(repo, &message.content);
+            let attachments = message
+                .attachments
+                .iter()
+                .map(|attachment| match attachment.is_text() {
+                    true => attachment.copy_with_content(redact_with_mapping(
+                        &mapped_redactions,
+                        &attachment.content,
+                    )),
+                    false => attachment.clone(),
+                })
+                .collect();
             redacted_messages.push(Message {
                 id: message.id.to_string(),
                 role: message.role.clone(),
                 content: redacted_input,
+                attachments,
                 redaction_mapping: Some(mapped_redactions),
+                model: message.model.clone(),
             });
         }
 
         self.messages = redacted_messages;
     }
 
+    pub fn redaction_mappings(&self) -> Vec<HashMap<String, String>> {
+        self.messages
+            .iter()
+            .filter_map(|message| message.redaction_mapping.clone())
+            .collect()
+    }
+
     pub fn unredact(&mut self) {
+        let redaction_mappings = self.redaction_mappings();
+
         let mut unredacted = Vec::with_capacity(self.messages.len());
         for message in self.messages.iter() {
-            match &message.redaction_mapping {
-                Some(redaction_mapping) => {
-                    let content = unredact(redaction_mapping, &message.content);
-                    unredacted.push(Message {
-                        id: message.id.to_string(),
-                        role: message.role.clone(),
-                        content,
-                        redaction_mapping: message.redaction_mapping.clone(),
-                    });
-                }
-                None => unredacted.push(message.clone()),
-            }
+            let content = match &message.redaction_mapping {
+                Some(redaction_mapping) => unredact(redaction_mapping, &message.content),
+                None => redaction_mappings
+                    .iter()
+                    .fold(message.content.clone(), |content, redaction_mapping| {
+                        unredact(redaction_mapping, &content)
+                    }),
+            };
+            let attachments = match &message.redaction_mapping {
+                Some(redaction_mapping) => message
+                    .attachments
+                    .iter()
+                    .map(|attachment| match attachment.is_text() {
+                        true => attachment
+                            .copy_with_content(unredact(redaction_mapping, &attachment.content)),
+                        false => attachment.clone(),
+                    })
+                    .collect(),
+                None => message.attachments.clone(),
+            };
+            unredacted.push(Message {
+                id: message.id.to_string(),
+                role: message.role.clone(),
+                content,
+                attachments,
+                redaction_mapping: message.redaction_mapping.clone(),
+                model: message.model.clone(),
+            });
         }
 
         self.messages = unredacted;
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn answers_repeating_a_redacted_value_are_unredacted() {
+        let mut session = Session::new_temporary();
+        session.add_raw_message("log in with [redacted-1]".to_string(), Role::User);
+        session.messages[0].redaction_mapping = Some(HashMap::from([(
+            "hunter2".to_string(),
+            "[redacted-1]".to_string(),
+        )]));
+        session.add_raw_message(
+            "use [redacted-1] as the password".to_string(),
+            Role::Assistant,
+        );
+
+        session.unredact();
+        assert_eq!(session.messages[0].content, "log in with hunter2");
+        assert_eq!(session.messages[1].content, "use hunter2 as the password");
+    }
+}
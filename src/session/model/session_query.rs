@@ -0,0 +1,46 @@
+#[derive(Debug, Clone, PartialEq)]
+pub enum SessionSort {
+    Recent,
+    Name,
+    Messages,
+}
+
+impl SessionSort {
+    pub fn from_str(sort: &str) -> Self {
+        match sort {
+            "name" => SessionSort::Name,
+            "messages" => SessionSort::Messages,
+            _ => SessionSort::Recent,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SessionFilter {
+    All,
+    Temporary,
+    Named,
+}
+
+impl SessionFilter {
+    pub fn from_str(filter: &str) -> Self {
+        match filter {
+            "temporary" => SessionFilter::Temporary,
+            "named" => SessionFilter::Named,
+            _ => SessionFilter::All,
+        }
+    }
+}
+
+pub struct SessionQuery {
+    pub sort: SessionSort,
+    pub filter: SessionFilter,
+    pub limit: u32,
+    pub page: u32,
+}
+
+impl SessionQuery {
+    pub fn offset(&self) -> u32 {
+        self.limit * self.page.saturating_sub(1)
+    }
+}
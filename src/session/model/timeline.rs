@@ -0,0 +1,73 @@
+use crate::usage::model::token_usage::TokenUsage;
+use chrono::{Local, NaiveDate, NaiveDateTime, TimeZone, Timelike, Utc};
+use std::collections::BTreeMap;
+
+const SPARKS: [char; 9] = [' ', '▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+pub struct Day {
+    pub date: NaiveDate,
+    pub hours: [u32; 24],
+    pub messages: u32,
+    pub tokens: u32,
+    pub cost: f64,
+}
+
+impl Day {
+    fn new(date: NaiveDate) -> Self {
+        Self {
+            date,
+            hours: [0; 24],
+            messages: 0,
+            tokens: 0,
+            cost: 0.0,
+        }
+    }
+}
+
+pub fn to_local(time: NaiveDateTime) -> NaiveDateTime {
+    Utc.from_utc_datetime(&time)
+        .with_timezone(&Local)
+        .naive_local()
+}
+
+pub fn to_utc(time: NaiveDateTime) -> NaiveDateTime {
+    Local
+        .from_local_datetime(&time)
+        .earliest()
+        .map(|time| time.naive_utc())
+        .unwrap_or(time)
+}
+
+pub fn active_days(messages: &[NaiveDateTime], usage: &[(NaiveDateTime, TokenUsage)]) -> Vec<Day> {
+    let mut days = BTreeMap::new();
+    for time in messages.iter().map(|time| to_local(*time)) {
+        let day = days
+            .entry(time.date())
+            .or_insert_with(|| Day::new(time.date()));
+        day.hours[time.hour() as usize] += 1;
+        day.messages += 1;
+    }
+    for (time, usage) in usage {
+        let time = to_local(*time);
+        let day = days
+            .entry(time.date())
+            .or_insert_with(|| Day::new(time.date()));
+        day.tokens += usage.prompt_tokens + usage.completion_tokens;
+        day.cost += usage.cost();
+    }
+    days.into_values().collect()
+}
+
+pub fn sparkline(values: &[u32], max: u32) -> String {
+    values
+        .iter()
+        .map(|value| match (*value, max) {
+            (0, _) | (_, 0) => SPARKS[0],
+            (value, max) => SPARKS[(value * 8).div_ceil(max).clamp(1, 8) as usize],
+        })
+        .collect()
+}
+
+pub fn quiet_days(previous: NaiveDate, next: NaiveDate) -> i64 {
+    (next - previous).num_days() - 1
+}
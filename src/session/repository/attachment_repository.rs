@@ -0,0 +1,62 @@
+use super::AttachmentRepository;
+use crate::repository::db::SqliteRepository;
+use crate::session::entity::attachment_entity::AttachmentEntity;
+use rusqlite::{params, Result, Row};
+
+impl AttachmentRepository for SqliteRepository {
+    type Error = rusqlite::Error;
+
+    fn fetch_attachments_for_session(
+        &self,
+        session_id: &str,
+    ) -> Result<Vec<AttachmentEntity>, Self::Error> {
+        let mut stmt = self.conn.prepare(
+            "SELECT a.id, a.message_id, a.kind, a.path, a.hash, c.content
+             FROM attachments a
+             JOIN attachment_contents c ON c.hash = a.hash
+             JOIN messages m ON m.id = a.message_id
+             WHERE m.session_id = ?1
+             ORDER BY a.rowid",
+        )?;
+        let rows = stmt.query_map(params![session_id], row_to_attachment_entity())?;
+
+        let mut attachments = Vec::new();
+        for attachment in rows {
+            attachments.push(attachment?);
+        }
+        Ok(attachments)
+    }
+
+    fn add_attachment(&self, attachment: &AttachmentEntity) -> Result<(), Self::Error> {
+        self.conn.execute(
+            "INSERT OR IGNORE INTO attachment_contents (hash, content) VALUES (?1, ?2)",
+            params![attachment.hash, attachment.content],
+        )?;
+        self.conn.execute(
+            "INSERT INTO attachments (id, message_id, kind, path, hash) VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![
+                attachment.id,
+                attachment.message_id,
+                attachment.kind,
+                attachment.path,
+                attachment.hash
+            ],
+        )?;
+        Ok(())
+    }
+}
+
+fn row_to_attachment_entity() -> fn(&Row) -> Result<AttachmentEntity> {
+    |row| {
+        let id: String = row.get(0)?;
+        let message_id: String = row.get(1)?;
+        let kind: String = row.get(2)?;
+        let path: String = row.get(3)?;
+        let hash: String = row.get(4)?;
+        let content: String = row.get(5)?;
+
+        Ok(AttachmentEntity::new(
+            id, message_id, kind, path, hash, content,
+        ))
+    }
+}
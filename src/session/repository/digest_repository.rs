@@ -0,0 +1,56 @@
+use super::DigestRepository;
+use crate::repository::db::SqliteRepository;
+use crate::session::entity::session_digest_entity::SessionDigestEntity;
+use chrono::NaiveDateTime;
+use rusqlite::{params, OptionalExtension, Result};
+
+const DATE_TIME_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+
+impl DigestRepository for SqliteRepository {
+    type Error = rusqlite::Error;
+
+    fn fetch_digest(&self, session_id: &str) -> Result<Option<SessionDigestEntity>, Self::Error> {
+        self.conn
+            .query_row(
+                "SELECT session_id, depth, content, message_count, created_at
+                 FROM session_digests WHERE session_id = ?1",
+                params![session_id],
+                |row| {
+                    let session_id: String = row.get(0)?;
+                    let depth: String = row.get(1)?;
+                    let content: String = row.get(2)?;
+                    let message_count: u32 = row.get(3)?;
+                    let created_at_str: String = row.get(4)?;
+                    let created_at =
+                        NaiveDateTime::parse_from_str(&created_at_str, DATE_TIME_FORMAT)
+                            .expect("Invalid DateTime format");
+
+                    Ok(SessionDigestEntity::new(
+                        session_id,
+                        depth,
+                        content,
+                        message_count,
+                        created_at,
+                    ))
+                },
+            )
+            .optional()
+    }
+
+    fn save_digest(&self, digest: &SessionDigestEntity) -> Result<(), Self::Error> {
+        let created_at_str = digest.created_at.format(DATE_TIME_FORMAT).to_string();
+        self.conn.execute(
+            "INSERT OR REPLACE INTO session_digests
+                (session_id, depth, content, message_count, created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![
+                digest.session_id,
+                digest.depth,
+                digest.content,
+                digest.message_count,
+                created_at_str
+            ],
+        )?;
+        Ok(())
+    }
+}
@@ -1,31 +1,21 @@
 use super::MessageRepository;
 use crate::repository::db::SqliteRepository;
 use crate::session::entity::message_entity::MessageEntity;
+use chrono::{NaiveDateTime, Utc};
 use rusqlite::{params, Result, Row};
 
+const DATE_TIME_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+
 impl MessageRepository for SqliteRepository {
     type Error = rusqlite::Error;
 
-    fn fetch_all_messages(&self) -> Result<Vec<MessageEntity>, Self::Error> {
-        let mut stmt = self
-            .conn
-            .prepare("SELECT id, session_id, role, content FROM messages")?;
-        let rows = stmt.query_map([], row_to_message_entity())?;
-
-        let mut messages = Vec::new();
-        for message in rows {
-            messages.push(message?);
-        }
-        Ok(messages)
-    }
-
     fn fetch_messages_for_session(
         &self,
         session_id: &str,
     ) -> Result<Vec<MessageEntity>, Self::Error> {
-        let mut stmt = self
-            .conn
-            .prepare("SELECT id, session_id, role, content FROM messages WHERE session_id = ?1")?;
+        let mut stmt = self.conn.prepare(
+            "SELECT id, session_id, role, content, model FROM messages WHERE session_id = ?1",
+        )?;
         let rows = stmt.query_map([session_id], row_to_message_entity())?;
 
         let mut messages = Vec::new();
@@ -37,16 +27,59 @@ impl MessageRepository for SqliteRepository {
 
     fn add_message_to_session(&self, message: &MessageEntity) -> Result<(), Self::Error> {
         self.conn.execute(
-            "INSERT INTO messages (id, session_id, role, content) VALUES (?1, ?2, ?3, ?4)",
+            "INSERT INTO messages (id, session_id, role, content, model, created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
             params![
                 message.id,
                 message.session_id,
                 message.role,
-                message.content
+                message.content,
+                message.model,
+                Utc::now().naive_utc().format(DATE_TIME_FORMAT).to_string()
             ],
         )?;
         Ok(())
     }
+
+    fn fetch_message_times(
+        &self,
+        session_id: &str,
+        since: Option<NaiveDateTime>,
+        until: Option<NaiveDateTime>,
+    ) -> Result<Vec<NaiveDateTime>, Self::Error> {
+        let since_str = since
+            .map(|since| since.format(DATE_TIME_FORMAT).to_string())
+            .unwrap_or_default();
+        let until_str = until
+            .map(|until| until.format(DATE_TIME_FORMAT).to_string())
+            .unwrap_or_else(|| "9999".to_string());
+        let mut stmt = self.conn.prepare(
+            "SELECT created_at FROM messages
+             WHERE session_id = ?1 AND created_at >= ?2 AND created_at < ?3
+             ORDER BY created_at",
+        )?;
+        let rows = stmt.query_map(params![session_id, since_str, until_str], |row| {
+            let created_at_str: String = row.get(0)?;
+            Ok(
+                NaiveDateTime::parse_from_str(&created_at_str, DATE_TIME_FORMAT)
+                    .expect("Invalid DateTime format"),
+            )
+        })?;
+
+        let mut times = Vec::new();
+        for time in rows {
+            times.push(time?);
+        }
+        Ok(times)
+    }
+
+    fn count_untimed_messages(&self, session_id: &str) -> Result<usize, Self::Error> {
+        self.conn.query_row(
+            "SELECT COUNT(*) FROM messages WHERE session_id = ?1 AND created_at IS NULL",
+            params![session_id],
+            |row| row.get(0),
+        )
+    }
 }
 
 fn row_to_message_entity() -> fn(&Row) -> Result<MessageEntity> {
@@ -55,7 +88,8 @@ fn row_to_message_entity() -> fn(&Row) -> Result<MessageEntity> {
         let session_id: String = row.get(1)?;
         let role: String = row.get(2)?;
         let content: String = row.get(3)?;
+        let model: Option<String> = row.get(4)?;
 
-        Ok(MessageEntity::new(id, session_id, role, content))
+        Ok(MessageEntity::new(id, session_id, role, content, model))
     }
 }
@@ -1,10 +1,18 @@
+use super::entity::attachment_entity::AttachmentEntity;
+use super::entity::session_digest_entity::SessionDigestEntity;
 use super::entity::session_entity::SessionEntity;
+use super::entity::session_pin_entity::SessionPinEntity;
+use super::entity::session_summary_entity::SessionSummaryEntity;
+use super::model::session_query::SessionQuery;
 use crate::session::entity::message_entity::MessageEntity;
 use chrono::NaiveDateTime;
 use std::fmt::Debug;
 
-pub(crate) mod session_repository;
+pub(crate) mod attachment_repository;
+pub(crate) mod digest_repository;
 pub(crate) mod message_repository;
+pub(crate) mod pin_repository;
+pub(crate) mod session_repository;
 
 pub trait SessionRepository
 where
@@ -13,6 +21,10 @@ where
     type Error;
 
     fn fetch_all_sessions(&self) -> Result<Vec<SessionEntity>, Self::Error>;
+    fn fetch_session_summaries(
+        &self,
+        query: &SessionQuery,
+    ) -> Result<Vec<SessionSummaryEntity>, Self::Error>;
     fn fetch_current_session(&self) -> Result<SessionEntity, Self::Error>;
     fn fetch_session_by_name(&self, name: &str) -> Result<SessionEntity, Self::Error>;
     fn add_session(
@@ -38,7 +50,50 @@ where
 {
     type Error;
 
-    fn fetch_all_messages(&self) -> Result<Vec<MessageEntity>, Self::Error>;
-    fn fetch_messages_for_session(&self, session_id: &str) -> Result<Vec<MessageEntity>, Self::Error>;
+    fn fetch_messages_for_session(
+        &self,
+        session_id: &str,
+    ) -> Result<Vec<MessageEntity>, Self::Error>;
     fn add_message_to_session(&self, message: &MessageEntity) -> Result<(), Self::Error>;
+    fn fetch_message_times(
+        &self,
+        session_id: &str,
+        since: Option<NaiveDateTime>,
+        until: Option<NaiveDateTime>,
+    ) -> Result<Vec<NaiveDateTime>, Self::Error>;
+    fn count_untimed_messages(&self, session_id: &str) -> Result<usize, Self::Error>;
+}
+
+pub trait AttachmentRepository
+where
+    Self::Error: Debug,
+{
+    type Error;
+
+    fn fetch_attachments_for_session(
+        &self,
+        session_id: &str,
+    ) -> Result<Vec<AttachmentEntity>, Self::Error>;
+    fn add_attachment(&self, attachment: &AttachmentEntity) -> Result<(), Self::Error>;
+}
+
+pub trait DigestRepository
+where
+    Self::Error: Debug,
+{
+    type Error;
+
+    fn fetch_digest(&self, session_id: &str) -> Result<Option<SessionDigestEntity>, Self::Error>;
+    fn save_digest(&self, digest: &SessionDigestEntity) -> Result<(), Self::Error>;
+}
+
+pub trait PinRepository
+where
+    Self::Error: Debug,
+{
+    type Error;
+
+    fn fetch_pin(&self, session_id: &str) -> Result<Option<SessionPinEntity>, Self::Error>;
+    fn save_pin(&self, pin: &SessionPinEntity) -> Result<(), Self::Error>;
+    fn remove_pin(&self, session_id: &str) -> Result<(), Self::Error>;
 }
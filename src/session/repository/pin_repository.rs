@@ -0,0 +1,47 @@
+use super::PinRepository;
+use crate::repository::db::SqliteRepository;
+use crate::session::entity::session_pin_entity::SessionPinEntity;
+use chrono::NaiveDateTime;
+use rusqlite::{params, OptionalExtension, Result};
+
+const DATE_TIME_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+
+impl PinRepository for SqliteRepository {
+    type Error = rusqlite::Error;
+
+    fn fetch_pin(&self, session_id: &str) -> Result<Option<SessionPinEntity>, Self::Error> {
+        self.conn
+            .query_row(
+                "SELECT session_id, model, pinned_at FROM session_pins WHERE session_id = ?1",
+                params![session_id],
+                |row| {
+                    let session_id: String = row.get(0)?;
+                    let model: String = row.get(1)?;
+                    let pinned_at_str: String = row.get(2)?;
+                    let pinned_at = NaiveDateTime::parse_from_str(&pinned_at_str, DATE_TIME_FORMAT)
+                        .expect("Invalid DateTime format");
+
+                    Ok(SessionPinEntity::new(session_id, model, pinned_at))
+                },
+            )
+            .optional()
+    }
+
+    fn save_pin(&self, pin: &SessionPinEntity) -> Result<(), Self::Error> {
+        let pinned_at_str = pin.pinned_at.format(DATE_TIME_FORMAT).to_string();
+        self.conn.execute(
+            "INSERT OR REPLACE INTO session_pins (session_id, model, pinned_at)
+             VALUES (?1, ?2, ?3)",
+            params![pin.session_id, pin.model, pinned_at_str],
+        )?;
+        Ok(())
+    }
+
+    fn remove_pin(&self, session_id: &str) -> Result<(), Self::Error> {
+        self.conn.execute(
+            "DELETE FROM session_pins WHERE session_id = ?1",
+            params![session_id],
+        )?;
+        Ok(())
+    }
+}
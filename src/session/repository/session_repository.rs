@@ -1,4 +1,6 @@
 use super::SessionRepository;
+use crate::session::entity::session_summary_entity::SessionSummaryEntity;
+use crate::session::model::session_query::{SessionFilter, SessionQuery, SessionSort};
 use crate::{repository::db::SqliteRepository, session::entity::session_entity::SessionEntity};
 use chrono::NaiveDateTime;
 use rusqlite::{params, Result, Row};
@@ -21,6 +23,54 @@ impl SessionRepository for SqliteRepository {
         Ok(sessions)
     }
 
+    fn fetch_session_summaries(
+        &self,
+        query: &SessionQuery,
+    ) -> Result<Vec<SessionSummaryEntity>, Self::Error> {
+        let filter = match query.filter {
+            SessionFilter::All => "",
+            SessionFilter::Temporary => "WHERE s.name = 'temporary'",
+            SessionFilter::Named => "WHERE s.name != 'temporary'",
+        };
+        let order = match query.sort {
+            SessionSort::Recent => "s.expires_at DESC",
+            SessionSort::Name => "s.name COLLATE NOCASE ASC",
+            SessionSort::Messages => "message_count DESC, s.expires_at DESC",
+        };
+        let sql = format!(
+            "SELECT s.name, s.expires_at, s.current, COUNT(m.id) AS message_count,
+                (SELECT model FROM messages
+                 WHERE session_id = s.id AND model IS NOT NULL
+                 ORDER BY rowid DESC LIMIT 1)
+             FROM sessions s
+             LEFT JOIN messages m ON m.session_id = s.id AND m.role != 'system'
+             {}
+             GROUP BY s.id
+             ORDER BY {}
+             LIMIT ?1 OFFSET ?2",
+            filter, order
+        );
+        let mut stmt = self.conn.prepare(&sql)?;
+        let rows = stmt.query_map(params![query.limit, query.offset()], |row| {
+            let expires_at_str: String = row.get(1)?;
+            let expires_at = NaiveDateTime::parse_from_str(&expires_at_str, DATE_TIME_FORMAT)
+                .expect("Invalid DateTime format");
+            Ok(SessionSummaryEntity {
+                name: row.get(0)?,
+                expires_at,
+                current: row.get(2)?,
+                message_count: row.get(3)?,
+                model: row.get(4)?,
+            })
+        })?;
+
+        let mut sessions = Vec::new();
+        for session in rows {
+            sessions.push(session?);
+        }
+        Ok(sessions)
+    }
+
     fn fetch_current_session(&self) -> Result<SessionEntity, Self::Error> {
         let session = self.conn.query_row(
             "SELECT id, name, expires_at, current FROM sessions WHERE current = 1",
@@ -0,0 +1,89 @@
+use crate::session::entity::session_pin_entity::SessionPinEntity;
+use crate::session::model::message::Message;
+use crate::session::repository::{
+    AttachmentRepository, MessageRepository, PinRepository, SessionRepository,
+};
+use crate::session::service::sessions_service::{
+    assistant_message, existing_session, session, session_add_messages,
+};
+use anyhow::{anyhow, Result};
+use chrono::Utc;
+
+pub fn create_branch<
+    SR: SessionRepository,
+    MR: MessageRepository,
+    AR: AttachmentRepository,
+    PR: PinRepository,
+>(
+    session_repo: &SR,
+    message_repository: &MR,
+    attachment_repository: &AR,
+    pin_repo: &PR,
+    name: &str,
+    branch: &str,
+    from_message: Option<&str>,
+) -> Result<()> {
+    let source = existing_session(
+        session_repo,
+        message_repository,
+        attachment_repository,
+        name,
+    )?;
+    if session_repo.fetch_session_by_name(branch).is_ok() {
+        return Err(anyhow!("session '{}' already exists", branch));
+    }
+    let keep = match from_message {
+        Some(selector) => {
+            let answer = assistant_message(&source, selector)?;
+            source
+                .messages
+                .iter()
+                .position(|message| message.id == answer.id)
+                .map(|index| index + 1)
+                .unwrap_or(source.messages.len())
+        }
+        None => source.messages.len(),
+    };
+
+    let mut target = session(
+        session_repo,
+        message_repository,
+        attachment_repository,
+        branch,
+    )?;
+    target.messages = source.messages[..keep]
+        .iter()
+        .map(|message| {
+            let mut copy = message.copy_with_id(String::new());
+            for attachment in copy.attachments.iter_mut() {
+                attachment.id = String::new();
+            }
+            copy
+        })
+        .collect::<Vec<Message>>();
+    session_add_messages(
+        session_repo,
+        message_repository,
+        attachment_repository,
+        &target,
+    )?;
+    if let Ok(Some(pin)) = pin_repo.fetch_pin(&source.id) {
+        let entity = SessionPinEntity::new(target.id.clone(), pin.model, Utc::now().naive_utc());
+        pin_repo
+            .save_pin(&entity)
+            .map_err(|err| anyhow!("could not pin the branch: {:?}", err))?;
+    }
+
+    println!(
+        "branched '{}' into '{}' with {} of {} messages",
+        source.name,
+        target.name,
+        keep,
+        source.messages.len()
+    );
+    println!(
+        "continue it with `termai --session {} \"...\"`",
+        target.name
+    );
+    Ok(())
+}
@@ -0,0 +1,132 @@
+use crate::config::repository::ConfigRepository;
+use crate::openai::model::role::Role;
+use crate::openai::service::prompt::prompt;
+use crate::output::markdown::truncate;
+use crate::session::entity::session_digest_entity::SessionDigestEntity;
+use crate::session::model::session::Session;
+use crate::session::repository::DigestRepository;
+use crate::usage::repository::UsageRepository;
+use anyhow::{anyhow, Result};
+use chrono::Utc;
+use colored::*;
+use std::path::Path;
+
+const MAX_MESSAGE_CHARS: usize = 4000;
+
+const DIGEST_PROMPT: &str = "
+You summarize a conversation between a developer and a terminal assistant.
+Answer with exactly these sections, each a list of short bullet points, and write \"none\" under a section with nothing to report:
+Decisions
+Code changed
+Open questions";
+
+const BRIEF_INSTRUCTION: &str = "Keep it to at most three bullets per section.";
+const DETAILED_INSTRUCTION: &str =
+    "Be thorough: name files, functions and commands, and say why each decision was made.";
+
+pub async fn summarize<R: ConfigRepository, UR: UsageRepository, DR: DigestRepository>(
+    repo: &R,
+    usage_repo: &UR,
+    digest_repo: &DR,
+    session: &Session,
+    depth: &str,
+) -> Result<()> {
+    let conversation = session
+        .messages
+        .iter()
+        .filter(|message| message.role != Role::System)
+        .collect::<Vec<_>>();
+    if conversation.is_empty() {
+        return Err(anyhow!("session '{}' has no messages yet", session.name));
+    }
+    let transcript = conversation
+        .iter()
+        .map(|message| {
+            format!(
+                "{}: {}",
+                message.role,
+                truncate(&message.content, MAX_MESSAGE_CHARS)
+            )
+        })
+        .collect::<Vec<String>>()
+        .join("\n\n");
+    let instruction = match depth {
+        "detailed" => DETAILED_INSTRUCTION,
+        _ => BRIEF_INSTRUCTION,
+    };
+    let system_prompt = format!("{}\n{}", DIGEST_PROMPT, instruction);
+    let digest = prompt(
+        repo,
+        usage_repo,
+        "session",
+        Path::new("."),
+        &system_prompt,
+        &transcript,
+    )
+    .await?;
+
+    let entity = SessionDigestEntity::new(
+        session.id.clone(),
+        depth.to_string(),
+        digest.trim().to_string(),
+        conversation.len() as u32,
+        Utc::now().naive_utc(),
+    );
+    digest_repo
+        .save_digest(&entity)
+        .map_err(|err| anyhow!("could not save the summary: {:?}", err))?;
+    print_digest(digest_repo, session);
+    Ok(())
+}
+
+pub fn print_digest<DR: DigestRepository>(digest_repo: &DR, session: &Session) {
+    let Ok(Some(digest)) = digest_repo.fetch_digest(&session.id) else {
+        return;
+    };
+    let message_count = session
+        .messages
+        .iter()
+        .filter(|message| message.role != Role::System)
+        .count() as u32;
+    let mut title = format!(
+        "summary ({}, {} messages, {})",
+        digest.depth,
+        digest.message_count,
+        digest.created_at.format("%Y-%m-%d %H:%M")
+    );
+    if message_count > digest.message_count {
+        title = format!(
+            "{}, {} newer messages not included",
+            title,
+            message_count - digest.message_count
+        );
+    }
+    println!("{}", title.bold());
+    println!("{}", digest.content);
+    println!();
+}
+
+pub fn resume_with_digest<DR: DigestRepository>(digest_repo: &DR, session: &mut Session) {
+    let Ok(Some(digest)) = digest_repo.fetch_digest(&session.id) else {
+        return;
+    };
+    let Some(system) = session
+        .messages
+        .iter()
+        .position(|message| message.role == Role::System)
+    else {
+        return;
+    };
+    session.messages[system].content = format!(
+        "{}\n\nSummary of the earlier conversation:\n{}",
+        session.messages[system].content, digest.content
+    );
+    let mut covered = 0;
+    session.messages.retain(|message| {
+        if message.role == Role::System || covered >= digest.message_count {
+            return true;
+        }
+        covered += 1;
+        false
+    });
+}
@@ -0,0 +1,95 @@
+use crate::config::repository::ConfigRepository;
+use crate::openai::service::prompt::prompt;
+use crate::output::progress::{status, Progress};
+use crate::session::model::distill::{chunks, fill_code, transcript};
+use crate::session::model::session::Session;
+use crate::usage::repository::UsageRepository;
+use anyhow::{anyhow, Result};
+use std::fs;
+use std::path::Path;
+
+const CHUNK_TOKENS: u32 = 6000;
+
+const NOTES_PROMPT: &str = "
+You take notes on one part of a conversation between a developer and a terminal assistant, for a reference document written later.
+Messages are numbered #1, #2 and so on, code blocks are labelled [C1], [C2] and so on.
+Answer with exactly these sections as short bullet points, and write \"none\" under a section with nothing to report:
+Decisions: what was settled and why, with the message number
+Code: each code block worth keeping by its label, what it does, and the label of any earlier block it replaces
+Discarded: approaches that were tried or rejected, and why
+Open questions";
+
+const DOCUMENT_PROMPT: &str = "
+You turn notes on a conversation, taken part by part in order, into a clean reference document in markdown.
+Later notes override earlier ones: keep only final decisions and the last version of each piece of code.
+Write these sections:
+# A title naming the topic
+## Summary: the goal and the outcome in a few sentences
+## Decisions: each final decision with its reason
+## Final code: for each piece of code, a short heading and description, then a line holding only its placeholder such as {{C7}}. Never write the code itself.
+## Open questions: leave the section out when there are none
+## Appendix: discarded alternatives, each with why it was dropped
+Do not mention the notes, message numbers or code labels outside the placeholders.";
+
+pub async fn distill<R: ConfigRepository, UR: UsageRepository>(
+    repo: &R,
+    usage_repo: &UR,
+    session: &Session,
+    output: Option<&str>,
+) -> Result<()> {
+    let transcript = transcript(&session.messages);
+    if transcript.entries.is_empty() {
+        return Err(anyhow!("session '{}' has no messages yet", session.name));
+    }
+    let chunks = chunks(&transcript.entries, CHUNK_TOKENS);
+
+    let progress = Progress::steps(chunks.len() + 1, "distilling");
+    let mut notes = Vec::with_capacity(chunks.len());
+    for (index, chunk) in chunks.iter().enumerate() {
+        progress.message(&format!("taking notes on part {}", index + 1));
+        let part = prompt(
+            repo,
+            usage_repo,
+            "session",
+            Path::new("."),
+            NOTES_PROMPT,
+            chunk,
+        )
+        .await?;
+        notes.push(format!("Part {}:\n{}", index + 1, part.trim()));
+        progress.advance();
+    }
+    progress.message("writing the document");
+    let document = prompt(
+        repo,
+        usage_repo,
+        "session",
+        Path::new("."),
+        DOCUMENT_PROMPT,
+        &notes.join("\n\n"),
+    )
+    .await?;
+    progress.finish();
+
+    let (document, unknown) = fill_code(document.trim(), &transcript.code);
+    if !unknown.is_empty() {
+        status(&format!(
+            "left out code the session does not have: {}",
+            unknown.join(", ")
+        ));
+    }
+    match output {
+        Some(path) => {
+            fs::write(path, format!("{}\n", document))
+                .map_err(|err| anyhow!("could not write {}: {:?}", path, err))?;
+            println!(
+                "distilled {} messages of session '{}' into {}",
+                transcript.entries.len(),
+                session.name,
+                path
+            );
+        }
+        None => println!("{}", document),
+    }
+    Ok(())
+}
@@ -0,0 +1,69 @@
+use crate::common::terminal::{answer, is_interactive};
+use crate::output::progress::status;
+use crate::path::model::Files;
+use crate::session::model::drift::{context_drift, DriftStatus, FileDrift};
+use crate::session::model::session::Session;
+use anyhow::Result;
+use colored::*;
+
+pub fn print_drift(session: &Session) {
+    let drift = context_drift(session);
+    if drift.is_empty() {
+        println!("session '{}' has no context files", session.name);
+        return;
+    }
+    for file in &drift {
+        let label = match file.status {
+            DriftStatus::Unchanged => "unchanged".dimmed(),
+            DriftStatus::Changed => "changed".yellow(),
+            DriftStatus::Missing => "missing".red(),
+        };
+        println!("{:>9}  {}", label, file.path);
+    }
+}
+
+pub fn refresh_context(session: &Session, sending: &[Files]) -> Result<Vec<Files>> {
+    let changed = context_drift(session)
+        .into_iter()
+        .filter(|file| file.status == DriftStatus::Changed)
+        .filter(|file| !sending.iter().any(|sent| sent.path == file.path))
+        .collect::<Vec<FileDrift>>();
+    if changed.is_empty() {
+        return Ok(vec![]);
+    }
+
+    let paths = changed
+        .iter()
+        .map(|file| file.path.as_str())
+        .collect::<Vec<&str>>()
+        .join(", ");
+    if !is_interactive() {
+        status(&format!(
+            "context files changed since the last turn: {}, see `termai session stale {}`",
+            paths, session.name
+        ));
+        return Ok(vec![]);
+    }
+    let answer = answer(&format!(
+        "context files changed since the last turn: {}\nresend [d]iff, [c]ontents or [n]othing? [D/c/n]",
+        paths
+    ))?;
+    let refreshed = match answer.to_lowercase().as_str() {
+        "n" | "nothing" => vec![],
+        "c" | "contents" => changed
+            .into_iter()
+            .map(|file| Files {
+                path: file.path,
+                content: file.current.unwrap_or_default(),
+            })
+            .collect(),
+        _ => changed
+            .iter()
+            .map(|file| Files {
+                path: format!("{}.diff", file.path),
+                content: file.diff(),
+            })
+            .collect(),
+    };
+    Ok(refreshed)
+}
@@ -0,0 +1,36 @@
+use crate::session::model::notebook::{session_cells, to_jupyter, to_quarto, ExportFormat};
+use crate::session::repository::{AttachmentRepository, MessageRepository, SessionRepository};
+use crate::session::service::sessions_service::existing_session;
+use anyhow::{anyhow, Result};
+use std::fs;
+
+pub fn export_session<SR: SessionRepository, MR: MessageRepository, AR: AttachmentRepository>(
+    session_repo: &SR,
+    message_repository: &MR,
+    attachment_repository: &AR,
+    name: &str,
+    format: &str,
+    output: Option<&str>,
+) -> Result<()> {
+    let session = existing_session(
+        session_repo,
+        message_repository,
+        attachment_repository,
+        name,
+    )?;
+    let cells = session_cells(&session);
+    let exported = match ExportFormat::from_str(format) {
+        ExportFormat::Jupyter => serde_json::to_string_pretty(&to_jupyter(&session.name, &cells))?,
+        ExportFormat::Quarto => to_quarto(&session.name, &cells),
+    };
+
+    match output {
+        Some(path) => {
+            fs::write(path, exported)
+                .map_err(|err| anyhow!("could not write {}: {:?}", path, err))?;
+            println!("exported session '{}' to {}", session.name, path);
+        }
+        None => println!("{}", exported),
+    }
+    Ok(())
+}
@@ -1 +1,8 @@
 pub(crate) mod sessions_service;
+pub(crate) mod export_service;
+pub(crate) mod digest_service;
+pub(crate) mod drift_service;
+pub(crate) mod pin_service;
+pub(crate) mod timeline_service;
+pub(crate) mod branch_service;
+pub(crate) mod distill_service;
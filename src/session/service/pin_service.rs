@@ -0,0 +1,147 @@
+use crate::common::unique_id::generate_uuid_v4;
+use crate::config::repository::ConfigRepository;
+use crate::models::repository::ModelRepository;
+use crate::models::service::models_service::{is_model_offered, resolve_model};
+use crate::openai::model::role::Role;
+use crate::session::entity::message_entity::MessageEntity;
+use crate::session::entity::session_pin_entity::SessionPinEntity;
+use crate::session::model::session::Session;
+use crate::session::repository::{MessageRepository, PinRepository};
+use anyhow::{anyhow, Result};
+use chrono::Utc;
+use colored::*;
+
+pub fn pin<R: ConfigRepository, MR: ModelRepository, PR: PinRepository>(
+    repo: &R,
+    model_repo: &MR,
+    pin_repo: &PR,
+    session: &Session,
+    model: Option<&str>,
+) -> Result<()> {
+    let last_answered_by = session
+        .messages
+        .iter()
+        .rev()
+        .filter(|message| message.role == Role::Assistant)
+        .find_map(|message| message.model.clone());
+    let model = match (model, last_answered_by) {
+        (Some(model), _) => resolve_model(repo, model_repo, Some(model))?,
+        (None, Some(model)) => model,
+        (None, None) => resolve_model(repo, model_repo, None)?,
+    };
+    let entity = SessionPinEntity::new(session.id.clone(), model, Utc::now().naive_utc());
+    pin_repo
+        .save_pin(&entity)
+        .map_err(|err| anyhow!("could not pin the session: {:?}", err))?;
+    println!("session '{}' is pinned to {}", session.name, entity.model);
+    Ok(())
+}
+
+pub fn unpin<PR: PinRepository>(pin_repo: &PR, session: &Session) -> Result<()> {
+    let pin = pin_repo
+        .fetch_pin(&session.id)
+        .map_err(|err| anyhow!("could not read the pinned model: {:?}", err))?;
+    if pin.is_none() {
+        println!("session '{}' is not pinned", session.name);
+        return Ok(());
+    }
+    pin_repo
+        .remove_pin(&session.id)
+        .map_err(|err| anyhow!("could not unpin the session: {:?}", err))?;
+    println!(
+        "session '{}' is no longer pinned, it follows the configured model",
+        session.name
+    );
+    Ok(())
+}
+
+pub fn pinned_model<R: ConfigRepository, MR: ModelRepository, PR: PinRepository>(
+    repo: &R,
+    model_repo: &MR,
+    pin_repo: &PR,
+    session: &Session,
+    requested: Option<&str>,
+) -> Result<Option<String>> {
+    let Ok(Some(pin)) = pin_repo.fetch_pin(&session.id) else {
+        return Ok(None);
+    };
+    if let Some(requested) = requested.filter(|requested| *requested != pin.model) {
+        return Err(anyhow!(
+            "session '{}' is pinned to {}, switch it with `termai session migrate {} {}`",
+            session.name,
+            pin.model,
+            session.name,
+            requested
+        ));
+    }
+    if is_model_offered(repo, model_repo, &pin.model) == Some(false) {
+        eprintln!(
+            "{} {} is no longer offered, move the session to a current model with `termai session migrate {} <model>`",
+            "warning:".yellow().bold(),
+            pin.model,
+            session.name
+        );
+    }
+    Ok(Some(pin.model))
+}
+
+pub fn migrate<
+    R: ConfigRepository,
+    MR: ModelRepository,
+    PR: PinRepository,
+    MsR: MessageRepository,
+>(
+    repo: &R,
+    model_repo: &MR,
+    pin_repo: &PR,
+    message_repo: &MsR,
+    session: &Session,
+    model: &str,
+) -> Result<()> {
+    let model = resolve_model(repo, model_repo, Some(model))?;
+    let previous = pin_repo
+        .fetch_pin(&session.id)
+        .map_err(|err| anyhow!("could not read the pinned model: {:?}", err))?
+        .ok_or_else(|| {
+            anyhow!(
+                "session '{}' is not pinned, pin it with `termai session pin {}`",
+                session.name,
+                session.name
+            )
+        })?;
+    if previous.model == model {
+        println!("session '{}' is already pinned to {}", session.name, model);
+        return Ok(());
+    }
+
+    let now = Utc::now().naive_utc();
+    let note = format!(
+        "The conversation was moved from {} to {} on {}. Answers before this point were written by {}.",
+        previous.model,
+        model,
+        now.format("%Y-%m-%d"),
+        previous.model
+    );
+    let message = MessageEntity::new(
+        generate_uuid_v4().to_string(),
+        session.id.clone(),
+        Role::System.to_string(),
+        note,
+        None,
+    );
+    message_repo
+        .add_message_to_session(&message)
+        .map_err(|err| anyhow!("could not annotate the transcript: {:?}", err))?;
+    pin_repo
+        .save_pin(&SessionPinEntity::new(
+            session.id.clone(),
+            model.clone(),
+            now,
+        ))
+        .map_err(|err| anyhow!("could not pin the session: {:?}", err))?;
+    println!(
+        "session '{}' moved from {} to {}",
+        session.name, previous.model, model
+    );
+    Ok(())
+}
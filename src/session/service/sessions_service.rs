@@ -1,23 +1,202 @@
+use crate::args::SessionAction;
 use crate::common::unique_id::generate_uuid_v4;
+use crate::config::repository::ConfigRepository;
+use crate::config::service::output_config;
+use crate::models::repository::ModelRepository;
+use crate::openai::model::role::Role;
+use crate::output::badge::model_badge;
+use crate::output::{diff, message, outputter};
+use crate::rating::model::rating::Rating;
+use crate::rating::repository::RatingRepository;
+use crate::rating::service::rating_service;
+use crate::session::model::attachment::Attachment;
 use crate::session::model::message::Message;
-use crate::session::repository::MessageRepository;
+use crate::session::model::session_query::{SessionFilter, SessionQuery, SessionSort};
+use crate::session::repository::{
+    AttachmentRepository, DigestRepository, MessageRepository, PinRepository,
+};
+use crate::session::service::{
+    branch_service, digest_service, distill_service, drift_service, export_service, pin_service,
+    timeline_service,
+};
 use crate::session::{model::session::Session, repository::SessionRepository};
-use anyhow::Result;
+use crate::usage::repository::UsageRepository;
+use crate::usage::service::usage_service;
+use anyhow::{anyhow, Result};
 use chrono::{Duration, NaiveDateTime, Utc};
 
-pub fn fetch_all_sessions<SR: SessionRepository, MR: MessageRepository>(
+const SESSION_LIFETIME_HOURS: i64 = 24;
+
+pub async fn sessions<R>(repo: &R, action: &SessionAction) -> Result<()>
+where
+    R: ConfigRepository
+        + SessionRepository
+        + MessageRepository
+        + AttachmentRepository
+        + DigestRepository
+        + PinRepository
+        + RatingRepository
+        + ModelRepository
+        + UsageRepository,
+{
+    match action {
+        SessionAction::List {
+            sort,
+            limit,
+            page,
+            filter,
+        } => list_sessions(repo, sort, *limit, *page, filter.as_deref()),
+        SessionAction::Show {
+            name,
+            stats,
+            show_context,
+        } => show_session(repo, name, *stats, *show_context),
+        SessionAction::Branch {
+            name,
+            branch,
+            from_message,
+        } => branch_service::create_branch(
+            repo,
+            repo,
+            repo,
+            repo,
+            name,
+            branch,
+            from_message.as_deref(),
+        ),
+        SessionAction::Timeline { name, since, until } => timeline_service::print_timeline(
+            repo,
+            repo,
+            repo,
+            name,
+            since.as_deref(),
+            until.as_deref(),
+        ),
+        SessionAction::Export {
+            name,
+            format,
+            output,
+        } => export_service::export_session(repo, repo, repo, name, format, output.as_deref()),
+        SessionAction::Note {
+            name,
+            answer,
+            text,
+            up,
+            down,
+        } => {
+            let session = existing_session(repo, repo, repo, name)?;
+            let rating = match (up, down) {
+                (true, _) => Some(Rating::Up),
+                (_, true) => Some(Rating::Down),
+                _ => None,
+            };
+            rating_service::note(repo, &session, answer, rating, text.as_deref())
+        }
+        SessionAction::Summarize { name, depth } => {
+            let session = existing_session(repo, repo, repo, name)?;
+            digest_service::summarize(repo, repo, repo, &session, depth).await
+        }
+        SessionAction::Distill { name, output } => {
+            let session = existing_session(repo, repo, repo, name)?;
+            distill_service::distill(repo, repo, &session, output.as_deref()).await
+        }
+        SessionAction::Stale { name } => {
+            let session = existing_session(repo, repo, repo, name)?;
+            drift_service::print_drift(&session);
+            Ok(())
+        }
+        SessionAction::Ratings => rating_service::print_model_ratings(repo),
+        SessionAction::Pin { name, model } => {
+            let session = existing_session(repo, repo, repo, name)?;
+            pin_service::pin(repo, repo, repo, &session, model.as_deref())
+        }
+        SessionAction::Unpin { name } => {
+            let session = existing_session(repo, repo, repo, name)?;
+            pin_service::unpin(repo, &session)
+        }
+        SessionAction::Migrate { name, model } => {
+            let session = existing_session(repo, repo, repo, name)?;
+            pin_service::migrate(repo, repo, repo, repo, &session, model)
+        }
+    }
+}
+
+fn show_session<R>(repo: &R, name: &str, stats: bool, show_context: bool) -> Result<()>
+where
+    R: ConfigRepository
+        + SessionRepository
+        + MessageRepository
+        + AttachmentRepository
+        + DigestRepository
+        + RatingRepository
+        + UsageRepository,
+{
+    let session = existing_session(repo, repo, repo, name)?;
+    digest_service::print_digest(repo, &session);
+    let output_messages = session
+        .messages
+        .iter()
+        .filter(|message| message.role != Role::System)
+        .map(|message| {
+            if show_context {
+                message.to_output_message_with_context()
+            } else {
+                message.to_output_message()
+            }
+        })
+        .collect::<Vec<message::Message>>();
+    outputter::print(
+        output_messages,
+        &session.context_paths(),
+        &output_config::output_options(repo, &session.name),
+    );
+
+    rating_service::print_session_notes(repo, &session);
+
+    if stats {
+        usage_service::print_session_stats(repo, repo, &session.id);
+    }
+    Ok(())
+}
+
+pub fn diff_answers<SR: SessionRepository, MR: MessageRepository, AR: AttachmentRepository>(
+    session_repo: &SR,
+    message_repository: &MR,
+    attachment_repository: &AR,
+    name: &str,
+    first: &str,
+    second: &str,
+) -> Result<()> {
+    let session = existing_session(
+        session_repo,
+        message_repository,
+        attachment_repository,
+        name,
+    )?;
+    let first = assistant_message(&session, first)?;
+    let second = assistant_message(&session, second)?;
+    diff::print_word_diff(&first.content, &second.content);
+    Ok(())
+}
+
+pub fn fetch_all_sessions<
+    SR: SessionRepository,
+    MR: MessageRepository,
+    AR: AttachmentRepository,
+>(
     session_repo: &SR,
     message_repository: &MR,
+    attachment_repository: &AR,
 ) -> Result<()> {
     let session_entities = session_repo.fetch_all_sessions().unwrap_or_else(|_| vec![]);
     let sessions = session_entities
         .iter()
-        .map(|s| Session::from(s))
+        .map(Session::from)
         .collect::<Vec<Session>>();
 
     println!("\n");
     for session in sessions {
-        let session = session_with_messages(message_repository, &session);
+        let session = session_with_messages(message_repository, attachment_repository, &session);
         println!(
             "session: {}\nis current: {}\nexpires at: {}\nmessage: {}\n{}\n\n",
             session.name,
@@ -31,16 +210,74 @@ pub fn fetch_all_sessions<SR: SessionRepository, MR: MessageRepository>(
     Ok(())
 }
 
-pub fn session<SR: SessionRepository, MR: MessageRepository>(
+pub fn list_sessions<SR: SessionRepository>(
+    session_repo: &SR,
+    sort: &str,
+    limit: u32,
+    page: u32,
+    filter: Option<&str>,
+) -> Result<()> {
+    let query = SessionQuery {
+        sort: SessionSort::from_str(sort),
+        filter: filter
+            .map(SessionFilter::from_str)
+            .unwrap_or(SessionFilter::All),
+        limit,
+        page: page.max(1),
+    };
+    let sessions = session_repo
+        .fetch_session_summaries(&query)
+        .map_err(|err| anyhow!("could not fetch sessions: {:?}", err))?;
+    if sessions.is_empty() {
+        println!("no sessions found");
+        return Ok(());
+    }
+
+    let now = Utc::now().naive_utc();
+    for session in sessions {
+        let marker = if session.current == 1 { "*" } else { " " };
+        let last_used = session.expires_at - Duration::hours(SESSION_LIFETIME_HOURS);
+        let model = session
+            .model
+            .as_deref()
+            .map(|model| format!("  {}", model_badge(model)))
+            .unwrap_or_default();
+        println!(
+            "{} {:<24} {:>4} messages  used {}{}",
+            marker,
+            session.name,
+            session.message_count,
+            relative_time(last_used, now),
+            model
+        );
+    }
+    Ok(())
+}
+
+fn relative_time(then: NaiveDateTime, now: NaiveDateTime) -> String {
+    let elapsed = now - then;
+    if elapsed.num_minutes() < 1 {
+        "just now".to_string()
+    } else if elapsed.num_hours() < 1 {
+        format!("{} minutes ago", elapsed.num_minutes())
+    } else if elapsed.num_days() < 1 {
+        format!("{} hours ago", elapsed.num_hours())
+    } else {
+        format!("{} days ago", elapsed.num_days())
+    }
+}
+
+pub fn session<SR: SessionRepository, MR: MessageRepository, AR: AttachmentRepository>(
     session_repo: &SR,
     message_repository: &MR,
+    attachment_repository: &AR,
     name: &str,
 ) -> Result<Session> {
     let session = match session_repo.fetch_session_by_name(name) {
         Err(_) => {
             let id = generate_uuid_v4().to_string();
             let now = Utc::now().naive_utc();
-            let expires_at: NaiveDateTime = now + Duration::hours(24);
+            let expires_at: NaiveDateTime = now + Duration::hours(SESSION_LIFETIME_HOURS);
 
             match session_repo.remove_current_from_all() {
                 Ok(_) => {}
@@ -62,29 +299,74 @@ pub fn session<SR: SessionRepository, MR: MessageRepository>(
         Ok(session) => Session::from(&session),
     };
 
-    let session = session_with_messages(message_repository, &session);
+    let session = session_with_messages(message_repository, attachment_repository, &session);
     Ok(session)
 }
 
-pub fn session_add_messages<SR: SessionRepository, MR: MessageRepository>(
+pub fn existing_session<SR: SessionRepository, MR: MessageRepository, AR: AttachmentRepository>(
     session_repo: &SR,
     message_repository: &MR,
+    attachment_repository: &AR,
+    name: &str,
+) -> Result<Session> {
+    let session = session_repo
+        .fetch_session_by_name(name)
+        .map_err(|_| anyhow!("session '{}' does not exist", name))?;
+    Ok(session_with_messages(
+        message_repository,
+        attachment_repository,
+        &Session::from(&session),
+    ))
+}
+
+pub fn assistant_message<'a>(session: &'a Session, selector: &str) -> Result<&'a Message> {
+    let mut answers = session
+        .messages
+        .iter()
+        .filter(|message| message.role == Role::Assistant);
+
+    let answer = match selector.parse::<usize>() {
+        Ok(index) => answers.nth(index.saturating_sub(1)),
+        Err(_) => answers.find(|message| message.id.starts_with(selector)),
+    };
+    answer.ok_or_else(|| {
+        anyhow!(
+            "session '{}' has no answer matching '{}'",
+            session.name,
+            selector
+        )
+    })
+}
+
+pub fn session_add_messages<
+    SR: SessionRepository,
+    MR: MessageRepository,
+    AR: AttachmentRepository,
+>(
+    session_repo: &SR,
+    message_repository: &MR,
+    attachment_repository: &AR,
     session: &Session,
 ) -> Result<()> {
     if !session.temporary {
         let new_messages = session
             .messages
             .iter()
-            .filter(|message| message.id == "")
+            .filter(|message| message.id.is_empty())
             .collect::<Vec<&Message>>();
         for message in new_messages {
             let message_with_id = message.copy_with_id(generate_uuid_v4().to_string());
             message_repository
                 .add_message_to_session(&message_with_id.to_entity(&session.id))
                 .expect("could not add new message to session");
+            for attachment in &message_with_id.attachments {
+                attachment_repository
+                    .add_attachment(&attachment.to_entity(&message_with_id.id))
+                    .map_err(|err| anyhow!("could not add attachment to message: {:?}", err))?;
+            }
         }
         let now = Utc::now().naive_utc();
-        let expires_at: NaiveDateTime = now + Duration::hours(24);
+        let expires_at: NaiveDateTime = now + Duration::hours(SESSION_LIFETIME_HOURS);
         session_repo
             .update_session(&session.id, &session.name, expires_at, session.current)
             .expect("could not update session");
@@ -92,16 +374,27 @@ pub fn session_add_messages<SR: SessionRepository, MR: MessageRepository>(
     Ok(())
 }
 
-fn session_with_messages<MR: MessageRepository>(
+fn session_with_messages<MR: MessageRepository, AR: AttachmentRepository>(
     message_repository: &MR,
+    attachment_repository: &AR,
     session: &Session,
 ) -> Session {
+    let attachments = attachment_repository
+        .fetch_attachments_for_session(&session.id)
+        .unwrap_or_default();
     let messages = message_repository
         .fetch_messages_for_session(&session.id)
-        .unwrap_or(Vec::new())
+        .unwrap_or_default()
         .iter()
-        .map(|m| Message::from(m))
+        .map(|entity| {
+            let mut message = Message::from(entity);
+            message.attachments = attachments
+                .iter()
+                .filter(|attachment| attachment.message_id == entity.id)
+                .map(Attachment::from)
+                .collect();
+            message
+        })
         .collect::<Vec<Message>>();
-    let session = session.copy_with_messages(messages);
-    session
+    session.copy_with_messages(messages)
 }
@@ -0,0 +1,126 @@
+use crate::session::model::timeline::{active_days, quiet_days, sparkline, to_utc};
+use crate::session::repository::{MessageRepository, SessionRepository};
+use crate::usage::model::token_usage::TokenUsage;
+use crate::usage::repository::UsageRepository;
+use anyhow::{anyhow, Result};
+use chrono::{Duration, NaiveDate, NaiveDateTime};
+use colored::*;
+
+const DATE_FORMAT: &str = "%Y-%m-%d";
+
+pub fn print_timeline<SR: SessionRepository, MR: MessageRepository, UR: UsageRepository>(
+    session_repo: &SR,
+    message_repo: &MR,
+    usage_repo: &UR,
+    name: &str,
+    since: Option<&str>,
+    until: Option<&str>,
+) -> Result<()> {
+    let session = session_repo
+        .fetch_session_by_name(name)
+        .map_err(|_| anyhow!("session '{}' does not exist", name))?;
+    let since = since.map(parse_date).transpose()?;
+    let until = until
+        .map(parse_date)
+        .transpose()?
+        .map(|until| until + Duration::days(1));
+    let messages = message_repo
+        .fetch_message_times(&session.id, since, until)
+        .map_err(|err| anyhow!("could not read the message times: {:?}", err))?;
+    let usage = usage_repo
+        .fetch_usage_for_session(&session.id)
+        .map_err(|err| anyhow!("could not read the usage: {:?}", err))?
+        .iter()
+        .filter(|usage| since.is_none_or(|since| usage.created_at >= since))
+        .filter(|usage| until.is_none_or(|until| usage.created_at < until))
+        .map(|usage| (usage.created_at, TokenUsage::from(usage)))
+        .collect::<Vec<(NaiveDateTime, TokenUsage)>>();
+    let untimed = message_repo
+        .count_untimed_messages(&session.id)
+        .map_err(|err| anyhow!("could not read the messages: {:?}", err))?;
+
+    let days = active_days(&messages, &usage);
+    let (Some(first), Some(last)) = (days.first(), days.last()) else {
+        println!("no activity in session '{}' for this range", session.name);
+        if untimed > 0 {
+            println!(
+                "{}",
+                format!(
+                    "{} older message(s) were stored before termai recorded times",
+                    untimed
+                )
+                .dimmed()
+            );
+        }
+        return Ok(());
+    };
+    println!(
+        "session '{}', {} to {} (local time)",
+        session.name, first.date, last.date
+    );
+    println!("{}", format!("{:15}0     6     12    18   23", "").dimmed());
+
+    let busiest_hour = days
+        .iter()
+        .flat_map(|day| day.hours.iter())
+        .copied()
+        .max()
+        .unwrap_or(0);
+    let mut longest_gap: Option<(i64, NaiveDate, NaiveDate)> = None;
+    let mut previous: Option<NaiveDate> = None;
+    for day in &days {
+        if let Some(previous) = previous {
+            let quiet = quiet_days(previous, day.date);
+            if quiet > 0 {
+                println!("{}", format!("  … {} quiet day(s)", quiet).dimmed());
+            }
+            if longest_gap.is_none_or(|(longest, _, _)| quiet > longest) && quiet > 0 {
+                longest_gap = Some((quiet, previous, day.date));
+            }
+        }
+        previous = Some(day.date);
+        println!(
+            "{} {} {}  {:>4} msgs  {:>8} tokens  ${:.4}",
+            day.date.format("%a"),
+            day.date,
+            sparkline(&day.hours, busiest_hour).cyan(),
+            day.messages,
+            day.tokens,
+            day.cost
+        );
+    }
+
+    println!();
+    let total_messages: u32 = days.iter().map(|day| day.messages).sum();
+    let total_tokens: u32 = days.iter().map(|day| day.tokens).sum();
+    let total_cost = days.iter().fold(0.0, |total, day| total + day.cost);
+    println!(
+        "{} message(s) on {} day(s), {} tokens, ${:.4}",
+        total_messages,
+        days.len(),
+        total_tokens,
+        total_cost
+    );
+    if let Some((quiet, from, to)) = longest_gap {
+        println!("longest gap: {} day(s) between {} and {}", quiet, from, to);
+    }
+    if untimed > 0 {
+        println!(
+            "{}",
+            format!(
+                "{} older message(s) were stored before termai recorded times",
+                untimed
+            )
+            .dimmed()
+        );
+    }
+    Ok(())
+}
+
+fn parse_date(value: &str) -> Result<NaiveDateTime> {
+    let date = NaiveDate::parse_from_str(value, DATE_FORMAT)
+        .map_err(|_| anyhow!("'{}' is not a date in the form YYYY-MM-DD", value))?;
+    Ok(to_utc(
+        date.and_hms_opt(0, 0, 0).expect("midnight is always valid"),
+    ))
+}
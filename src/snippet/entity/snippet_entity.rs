@@ -0,0 +1,33 @@
+use chrono::NaiveDateTime;
+
+pub struct SnippetEntity {
+    pub id: String,
+    pub name: String,
+    pub language: Option<String>,
+    pub content: String,
+    pub session_id: String,
+    pub message_id: String,
+    pub created_at: NaiveDateTime,
+}
+
+impl SnippetEntity {
+    pub fn new(
+        id: String,
+        name: String,
+        language: Option<String>,
+        content: String,
+        session_id: String,
+        message_id: String,
+        created_at: NaiveDateTime,
+    ) -> Self {
+        Self {
+            id,
+            name,
+            language,
+            content,
+            session_id,
+            message_id,
+            created_at,
+        }
+    }
+}
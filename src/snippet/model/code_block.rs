@@ -0,0 +1,63 @@
+#[derive(Debug, Clone)]
+pub struct CodeBlock {
+    pub language: Option<String>,
+    pub content: String,
+}
+
+pub fn extract_code_blocks(content: &str) -> Vec<CodeBlock> {
+    let mut blocks = Vec::new();
+    let mut current: Option<CodeBlock> = None;
+
+    for line in content.lines() {
+        let trimmed = line.trim_start();
+        if let Some(info) = trimmed.strip_prefix("```") {
+            match current.take() {
+                Some(block) => blocks.push(block),
+                None => {
+                    let language = info.split_whitespace().next().map(|l| l.to_lowercase());
+                    current = Some(CodeBlock {
+                        language,
+                        content: String::new(),
+                    });
+                }
+            }
+            continue;
+        }
+
+        if let Some(block) = current.as_mut() {
+            block.content.push_str(line);
+            block.content.push('\n');
+        }
+    }
+
+    blocks
+}
+
+pub fn prose_outside_code_blocks(content: &str) -> String {
+    let mut inside = false;
+    let mut prose = Vec::new();
+    for line in content.lines() {
+        if line.trim_start().starts_with("```") {
+            inside = !inside;
+            continue;
+        }
+        if !inside && !line.trim().is_empty() {
+            prose.push(line.trim());
+        }
+    }
+    prose.join("\n")
+}
+
+pub fn fence_code_blocks(blocks: &[CodeBlock]) -> String {
+    blocks
+        .iter()
+        .map(|block| {
+            format!(
+                "```{}\n{}```",
+                block.language.as_deref().unwrap_or_default(),
+                block.content
+            )
+        })
+        .collect::<Vec<String>>()
+        .join("\n\n")
+}
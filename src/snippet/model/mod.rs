@@ -0,0 +1 @@
+pub(crate) mod code_block;
@@ -0,0 +1,16 @@
+use super::entity::snippet_entity::SnippetEntity;
+use std::fmt::Debug;
+
+pub(crate) mod snippet_repository;
+
+pub trait SnippetRepository
+where
+    Self::Error: Debug,
+{
+    type Error;
+
+    fn fetch_all_snippets(&self) -> Result<Vec<SnippetEntity>, Self::Error>;
+    fn fetch_snippet_by_name(&self, name: &str) -> Result<SnippetEntity, Self::Error>;
+    fn search_snippets(&self, query: &str) -> Result<Vec<SnippetEntity>, Self::Error>;
+    fn add_snippet(&self, snippet: &SnippetEntity) -> Result<(), Self::Error>;
+}
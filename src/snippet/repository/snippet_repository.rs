@@ -0,0 +1,89 @@
+use super::SnippetRepository;
+use crate::repository::db::SqliteRepository;
+use crate::snippet::entity::snippet_entity::SnippetEntity;
+use chrono::NaiveDateTime;
+use rusqlite::{params, Result, Row};
+
+const DATE_TIME_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+
+impl SnippetRepository for SqliteRepository {
+    type Error = rusqlite::Error;
+
+    fn fetch_all_snippets(&self) -> Result<Vec<SnippetEntity>, Self::Error> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, name, language, content, session_id, message_id, created_at
+             FROM snippets ORDER BY name",
+        )?;
+        let rows = stmt.query_map([], row_to_snippet_entity())?;
+
+        let mut snippets = Vec::new();
+        for snippet in rows {
+            snippets.push(snippet?);
+        }
+        Ok(snippets)
+    }
+
+    fn fetch_snippet_by_name(&self, name: &str) -> Result<SnippetEntity, Self::Error> {
+        let snippet = self.conn.query_row(
+            "SELECT id, name, language, content, session_id, message_id, created_at
+             FROM snippets WHERE name = ?1",
+            params![name],
+            row_to_snippet_entity(),
+        )?;
+
+        Ok(snippet)
+    }
+
+    fn search_snippets(&self, query: &str) -> Result<Vec<SnippetEntity>, Self::Error> {
+        let pattern = format!("%{}%", query);
+        let mut stmt = self.conn.prepare(
+            "SELECT id, name, language, content, session_id, message_id, created_at
+             FROM snippets
+             WHERE name LIKE ?1 OR language LIKE ?1 OR content LIKE ?1
+             ORDER BY name",
+        )?;
+        let rows = stmt.query_map(params![pattern], row_to_snippet_entity())?;
+
+        let mut snippets = Vec::new();
+        for snippet in rows {
+            snippets.push(snippet?);
+        }
+        Ok(snippets)
+    }
+
+    fn add_snippet(&self, snippet: &SnippetEntity) -> Result<(), Self::Error> {
+        let created_at_str = snippet.created_at.format(DATE_TIME_FORMAT).to_string();
+        self.conn.execute(
+            "INSERT INTO snippets (id, name, language, content, session_id, message_id, created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            params![
+                snippet.id,
+                snippet.name,
+                snippet.language,
+                snippet.content,
+                snippet.session_id,
+                snippet.message_id,
+                created_at_str
+            ],
+        )?;
+        Ok(())
+    }
+}
+
+fn row_to_snippet_entity() -> fn(&Row) -> Result<SnippetEntity> {
+    |row| {
+        let id: String = row.get(0)?;
+        let name: String = row.get(1)?;
+        let language: Option<String> = row.get(2)?;
+        let content: String = row.get(3)?;
+        let session_id: String = row.get(4)?;
+        let message_id: String = row.get(5)?;
+        let created_at_str: String = row.get(6)?;
+        let created_at = NaiveDateTime::parse_from_str(&created_at_str, DATE_TIME_FORMAT)
+            .expect("Invalid DateTime format");
+
+        Ok(SnippetEntity::new(
+            id, name, language, content, session_id, message_id, created_at,
+        ))
+    }
+}
@@ -0,0 +1 @@
+pub(crate) mod snippet_service;
@@ -0,0 +1,135 @@
+use crate::args::SnippetAction;
+use crate::common::clipboard::copy_to_clipboard;
+use crate::common::unique_id::generate_uuid_v4;
+use crate::openai::model::role::Role;
+use crate::session::model::message::Message;
+use crate::session::repository::{MessageRepository, SessionRepository};
+use crate::snippet::entity::snippet_entity::SnippetEntity;
+use crate::snippet::model::code_block::extract_code_blocks;
+use crate::snippet::repository::SnippetRepository;
+use anyhow::{anyhow, Result};
+use chrono::Utc;
+use colored::*;
+
+pub fn snippet<SR: SessionRepository, MR: MessageRepository, SNR: SnippetRepository>(
+    session_repo: &SR,
+    message_repo: &MR,
+    snippet_repo: &SNR,
+    action: &SnippetAction,
+) -> Result<()> {
+    match action {
+        SnippetAction::Save {
+            name,
+            session,
+            block,
+        } => save_snippet(
+            session_repo,
+            message_repo,
+            snippet_repo,
+            name,
+            session,
+            *block,
+        ),
+        SnippetAction::List => list_snippets(snippet_repo),
+        SnippetAction::Search { query } => search_snippets(snippet_repo, query),
+        SnippetAction::Copy { name } => copy_snippet(snippet_repo, name),
+    }
+}
+
+fn save_snippet<SR: SessionRepository, MR: MessageRepository, SNR: SnippetRepository>(
+    session_repo: &SR,
+    message_repo: &MR,
+    snippet_repo: &SNR,
+    name: &str,
+    session_name: &Option<String>,
+    block: usize,
+) -> Result<()> {
+    if snippet_repo.fetch_snippet_by_name(name).is_ok() {
+        return Err(anyhow!("snippet '{}' already exists", name));
+    }
+
+    let session = match session_name {
+        Some(session_name) => session_repo.fetch_session_by_name(session_name),
+        None => session_repo.fetch_current_session(),
+    }
+    .map_err(|_| anyhow!("no session found to save a snippet from"))?;
+
+    let last_answer = message_repo
+        .fetch_messages_for_session(&session.id)
+        .unwrap_or_default()
+        .iter()
+        .rev()
+        .map(Message::from)
+        .find(|message| message.role == Role::Assistant)
+        .ok_or_else(|| anyhow!("session '{}' has no answers yet", session.name))?;
+
+    let code_blocks = extract_code_blocks(&last_answer.content);
+    let code_block = code_blocks.get(block.saturating_sub(1)).ok_or_else(|| {
+        anyhow!(
+            "the last answer has {} code block(s), block {} does not exist",
+            code_blocks.len(),
+            block
+        )
+    })?;
+
+    let snippet = SnippetEntity::new(
+        generate_uuid_v4().to_string(),
+        name.to_string(),
+        code_block.language.clone(),
+        code_block.content.clone(),
+        session.id.clone(),
+        last_answer.id.clone(),
+        Utc::now().naive_utc(),
+    );
+    snippet_repo
+        .add_snippet(&snippet)
+        .map_err(|err| anyhow!("could not save snippet: {:?}", err))?;
+
+    println!("Saved snippet '{}' from session '{}'", name, session.name);
+    Ok(())
+}
+
+fn list_snippets<SNR: SnippetRepository>(snippet_repo: &SNR) -> Result<()> {
+    let snippets = snippet_repo.fetch_all_snippets().unwrap_or_default();
+    print_snippets(&snippets);
+    Ok(())
+}
+
+fn search_snippets<SNR: SnippetRepository>(snippet_repo: &SNR, query: &str) -> Result<()> {
+    let snippets = snippet_repo.search_snippets(query).unwrap_or_default();
+    print_snippets(&snippets);
+    Ok(())
+}
+
+fn copy_snippet<SNR: SnippetRepository>(snippet_repo: &SNR, name: &str) -> Result<()> {
+    let snippet = snippet_repo
+        .fetch_snippet_by_name(name)
+        .map_err(|_| anyhow!("snippet '{}' does not exist", name))?;
+
+    match copy_to_clipboard(&snippet.content) {
+        Ok(_) => println!("Copied snippet '{}' to the clipboard", name),
+        Err(_) => print!("{}", snippet.content),
+    }
+    Ok(())
+}
+
+fn print_snippets(snippets: &[SnippetEntity]) {
+    if snippets.is_empty() {
+        println!("No snippets found");
+        return;
+    }
+
+    for snippet in snippets {
+        let language = snippet.language.as_deref().unwrap_or("text");
+        println!(
+            "{} {} {}",
+            snippet.name.green().bold(),
+            format!("[{}]", language).cyan(),
+            format!(
+                "saved {} from message {}",
+                snippet.created_at, snippet.message_id
+            )
+            .dimmed()
+        );
+    }
+}
@@ -0,0 +1,183 @@
+use anyhow::{anyhow, Result};
+use rusqlite::{Connection, OpenFlags};
+use std::process::Command;
+
+const READ_ONLY_PREFIXES: [&str; 6] = ["select", "with", "explain", "show", "pragma", "values"];
+
+pub enum Database {
+    Sqlite(Connection),
+    Postgres(String),
+}
+
+pub struct QueryResult {
+    pub columns: Vec<String>,
+    pub rows: Vec<Vec<String>>,
+}
+
+impl Database {
+    pub fn open(target: &str) -> Result<Self> {
+        if target.starts_with("postgres://") || target.starts_with("postgresql://") {
+            return Ok(Database::Postgres(target.to_string()));
+        }
+        let conn = Connection::open_with_flags(target, OpenFlags::SQLITE_OPEN_READ_ONLY)
+            .map_err(|err| anyhow!("could not open {} read-only: {:?}", target, err))?;
+        Ok(Database::Sqlite(conn))
+    }
+
+    pub fn dialect(&self) -> &str {
+        match self {
+            Database::Sqlite(_) => "SQLite",
+            Database::Postgres(_) => "PostgreSQL",
+        }
+    }
+
+    pub fn schema(&self) -> Result<String> {
+        match self {
+            Database::Sqlite(conn) => sqlite_schema(conn),
+            Database::Postgres(url) => postgres_schema(url),
+        }
+    }
+
+    pub fn query(&self, sql: &str, limit: usize) -> Result<QueryResult> {
+        if !is_read_only(sql) {
+            return Err(anyhow!("only read-only queries are executed"));
+        }
+        match self {
+            Database::Sqlite(conn) => sqlite_query(conn, sql, limit),
+            Database::Postgres(url) => postgres_query(url, sql, limit),
+        }
+    }
+}
+
+pub fn is_read_only(sql: &str) -> bool {
+    let statement = sql.trim().trim_end_matches(';');
+    let first_word = statement
+        .split_whitespace()
+        .next()
+        .unwrap_or_default()
+        .to_lowercase();
+    READ_ONLY_PREFIXES.contains(&first_word.as_str()) && !statement.contains(';')
+}
+
+fn sqlite_schema(conn: &Connection) -> Result<String> {
+    let mut stmt = conn.prepare(
+        "SELECT sql FROM sqlite_master WHERE sql IS NOT NULL AND name NOT LIKE 'sqlite_%' ORDER BY type DESC, name",
+    )?;
+    let rows = stmt.query_map([], |row| row.get::<_, String>(0))?;
+    let mut schema = Vec::new();
+    for row in rows {
+        schema.push(format!("{};", row?));
+    }
+    Ok(schema.join("\n"))
+}
+
+fn sqlite_query(conn: &Connection, sql: &str, limit: usize) -> Result<QueryResult> {
+    let mut stmt = conn.prepare(sql)?;
+    let columns = stmt
+        .column_names()
+        .iter()
+        .map(|name| name.to_string())
+        .collect::<Vec<String>>();
+    let mut rows = stmt.query([])?;
+    let mut result = Vec::new();
+    while let Some(row) = rows.next()? {
+        if result.len() >= limit {
+            break;
+        }
+        let mut values = Vec::with_capacity(columns.len());
+        for index in 0..columns.len() {
+            let value = match row.get_ref(index)? {
+                rusqlite::types::ValueRef::Null => "NULL".to_string(),
+                rusqlite::types::ValueRef::Integer(i) => i.to_string(),
+                rusqlite::types::ValueRef::Real(f) => f.to_string(),
+                rusqlite::types::ValueRef::Text(t) => String::from_utf8_lossy(t).to_string(),
+                rusqlite::types::ValueRef::Blob(b) => format!("<{} bytes>", b.len()),
+            };
+            values.push(value);
+        }
+        result.push(values);
+    }
+    Ok(QueryResult {
+        columns,
+        rows: result,
+    })
+}
+
+fn psql(url: &str, sql: &str, tuples_only: bool) -> Result<String> {
+    let mut command = Command::new("psql");
+    command
+        .arg(url)
+        .args(["-X", "-A", "-F", "\t", "-v", "ON_ERROR_STOP=1", "-c", sql]);
+    if tuples_only {
+        command.arg("-t");
+    }
+    let output = command
+        .env("PGOPTIONS", "-c default_transaction_read_only=on")
+        .output()
+        .map_err(|err| anyhow!("could not run psql: {:?}", err))?;
+    if !output.status.success() {
+        return Err(anyhow!(
+            "psql failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+fn postgres_schema(url: &str) -> Result<String> {
+    let columns = psql(
+        url,
+        "SELECT table_schema || '.' || table_name, column_name, data_type, is_nullable
+         FROM information_schema.columns
+         WHERE table_schema NOT IN ('pg_catalog', 'information_schema')
+         ORDER BY table_schema, table_name, ordinal_position",
+        true,
+    )?;
+    let indexes = psql(
+        url,
+        "SELECT indexdef FROM pg_indexes WHERE schemaname NOT IN ('pg_catalog', 'information_schema')",
+        true,
+    )?;
+
+    let mut schema = String::new();
+    let mut current_table = String::new();
+    for line in data_lines(&columns) {
+        let fields = line.split('\t').collect::<Vec<&str>>();
+        if fields.len() < 4 {
+            continue;
+        }
+        if fields[0] != current_table {
+            current_table = fields[0].to_string();
+            schema.push_str(&format!("\ntable {}\n", current_table));
+        }
+        let nullable = if fields[3] == "YES" { "" } else { " not null" };
+        schema.push_str(&format!("  {} {}{}\n", fields[1], fields[2], nullable));
+    }
+    schema.push_str("\nindexes:\n");
+    for line in data_lines(&indexes) {
+        schema.push_str(&format!("  {}\n", line));
+    }
+    Ok(schema)
+}
+
+fn postgres_query(url: &str, sql: &str, limit: usize) -> Result<QueryResult> {
+    let output = psql(url, sql.trim().trim_end_matches(';'), false)?;
+    let mut lines = output.lines();
+    let columns = lines
+        .next()
+        .map(|header| header.split('\t').map(|c| c.to_string()).collect())
+        .unwrap_or_default();
+    let rows = data_lines(&lines.collect::<Vec<&str>>().join("\n"))
+        .into_iter()
+        .take(limit)
+        .map(|line| line.split('\t').map(|v| v.to_string()).collect())
+        .collect();
+    Ok(QueryResult { columns, rows })
+}
+
+fn data_lines(output: &str) -> Vec<&str> {
+    output
+        .lines()
+        .filter(|line| !(line.starts_with('(') && line.ends_with(')')))
+        .collect()
+}
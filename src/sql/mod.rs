@@ -0,0 +1,2 @@
+pub(crate) mod database;
+pub(crate) mod service;
@@ -0,0 +1,101 @@
+use crate::config::repository::ConfigRepository;
+use crate::openai::service::prompt::prompt;
+use crate::snippet::model::code_block::extract_code_blocks;
+use crate::sql::database::{Database, QueryResult};
+use crate::usage::repository::UsageRepository;
+use anyhow::Result;
+use colored::*;
+use std::path::Path;
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+const MAX_CELL_WIDTH: usize = 40;
+
+const SQL_PROMPT: &str = "
+You write SQL for the {dialect} database described below.
+Answer with a single SQL statement in a ```sql code block followed by one or two sentences explaining it.
+Prefer read-only queries. Only use tables and columns that exist in the schema.
+
+schema:
+{schema}";
+
+pub async fn sql<R: ConfigRepository, UR: UsageRepository>(
+    repo: &R,
+    usage_repo: &UR,
+    target: &str,
+    question: &str,
+    execute: bool,
+    limit: usize,
+) -> Result<()> {
+    let database = Database::open(target)?;
+    let schema = database.schema()?;
+    let system_prompt = SQL_PROMPT
+        .replace("{dialect}", database.dialect())
+        .replace("{schema}", &schema);
+    let answer = prompt(
+        repo,
+        usage_repo,
+        "sql",
+        Path::new("."),
+        &system_prompt,
+        question,
+    )
+    .await?;
+    println!("{}", answer);
+
+    if !execute {
+        return Ok(());
+    }
+    let Some(query) = extract_code_blocks(&answer).into_iter().next() else {
+        println!("{}", "no SQL statement found in the answer".yellow());
+        return Ok(());
+    };
+    match database.query(&query.content, limit) {
+        Ok(result) => print_result(&result, limit),
+        Err(err) => println!("{} {}", "not executed:".yellow(), err),
+    }
+    Ok(())
+}
+
+fn print_result(result: &QueryResult, limit: usize) {
+    let mut widths = result
+        .columns
+        .iter()
+        .map(|column| column.width())
+        .collect::<Vec<usize>>();
+    for row in &result.rows {
+        for (index, value) in row.iter().enumerate() {
+            if let Some(width) = widths.get_mut(index) {
+                *width = (*width).max(value.width()).min(MAX_CELL_WIDTH);
+            }
+        }
+    }
+
+    let format_row = |values: &[String]| {
+        values
+            .iter()
+            .zip(widths.iter())
+            .map(|(value, width)| {
+                let mut fitted = String::new();
+                let mut used = 0;
+                for c in value.chars() {
+                    let char_width = c.width().unwrap_or(0);
+                    if used + char_width > *width {
+                        break;
+                    }
+                    used += char_width;
+                    fitted.push(c);
+                }
+                format!("{}{}", fitted, " ".repeat(width - used))
+            })
+            .collect::<Vec<String>>()
+            .join(" | ")
+    };
+    println!();
+    println!("{}", format_row(&result.columns).bold());
+    for row in &result.rows {
+        println!("{}", format_row(row));
+    }
+    if result.rows.len() >= limit {
+        println!("{}", format!("(showing the first {} rows)", limit).dimmed());
+    }
+}
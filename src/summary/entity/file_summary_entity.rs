@@ -0,0 +1,15 @@
+pub struct FileSummaryEntity {
+    pub hash: String,
+    pub path: String,
+    pub summary: String,
+}
+
+impl FileSummaryEntity {
+    pub fn new(hash: String, path: String, summary: String) -> Self {
+        Self {
+            hash,
+            path,
+            summary,
+        }
+    }
+}
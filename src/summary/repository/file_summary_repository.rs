@@ -0,0 +1,32 @@
+use super::FileSummaryRepository;
+use crate::repository::db::SqliteRepository;
+use crate::summary::entity::file_summary_entity::FileSummaryEntity;
+use rusqlite::{params, Result};
+
+impl FileSummaryRepository for SqliteRepository {
+    type Error = rusqlite::Error;
+
+    fn fetch_summary_by_hash(&self, hash: &str) -> Result<FileSummaryEntity, Self::Error> {
+        let summary = self.conn.query_row(
+            "SELECT hash, path, summary FROM file_summaries WHERE hash = ?1",
+            params![hash],
+            |row| {
+                let hash: String = row.get(0)?;
+                let path: String = row.get(1)?;
+                let summary: String = row.get(2)?;
+
+                Ok(FileSummaryEntity::new(hash, path, summary))
+            },
+        )?;
+
+        Ok(summary)
+    }
+
+    fn add_summary(&self, summary: &FileSummaryEntity) -> Result<(), Self::Error> {
+        self.conn.execute(
+            "INSERT OR REPLACE INTO file_summaries (hash, path, summary) VALUES (?1, ?2, ?3)",
+            params![summary.hash, summary.path, summary.summary],
+        )?;
+        Ok(())
+    }
+}
@@ -0,0 +1,14 @@
+use super::entity::file_summary_entity::FileSummaryEntity;
+use std::fmt::Debug;
+
+pub(crate) mod file_summary_repository;
+
+pub trait FileSummaryRepository
+where
+    Self::Error: Debug,
+{
+    type Error;
+
+    fn fetch_summary_by_hash(&self, hash: &str) -> Result<FileSummaryEntity, Self::Error>;
+    fn add_summary(&self, summary: &FileSummaryEntity) -> Result<(), Self::Error>;
+}
@@ -0,0 +1 @@
+pub(crate) mod summary_service;
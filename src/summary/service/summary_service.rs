@@ -0,0 +1,83 @@
+use crate::config::repository::ConfigRepository;
+use crate::openai::service::prompt::prompt;
+use crate::output::progress::Progress;
+use crate::path::model::Files;
+use crate::summary::entity::file_summary_entity::FileSummaryEntity;
+use crate::summary::repository::FileSummaryRepository;
+use crate::usage::model::token_usage::estimate_tokens;
+use crate::usage::repository::UsageRepository;
+use anyhow::Result;
+use sha2::{Digest, Sha256};
+use std::path::Path;
+
+pub const SUMMARY_MARKER: &str = "[structural summary";
+
+const SUMMARY_PROMPT: &str = "
+You summarize source files so they can be used as context for another question.
+List the public types, function signatures and constants, then describe the key logic in a few sentences.
+Keep names exactly as written in the file. Do not include any other commentary.";
+
+pub async fn summarize_oversized_files<
+    R: ConfigRepository,
+    UR: UsageRepository,
+    FR: FileSummaryRepository,
+>(
+    repo: &R,
+    usage_repo: &UR,
+    summary_repo: &FR,
+    directory: &Path,
+    files: Vec<Files>,
+    threshold: u32,
+) -> Result<Vec<Files>> {
+    let oversized = files
+        .iter()
+        .filter(|file| !file.is_binary() && estimate_tokens(&file.content) > threshold)
+        .count();
+    let progress = Progress::steps(oversized, "summarizing");
+    let mut summarized = Vec::with_capacity(files.len());
+    for file in files {
+        let tokens = estimate_tokens(&file.content);
+        if file.is_binary() || tokens <= threshold {
+            summarized.push(file);
+            continue;
+        }
+
+        progress.message(&file.path);
+        let summary = file_summary(repo, usage_repo, summary_repo, directory, &file).await?;
+        progress.advance();
+        summarized.push(Files {
+            content: format!(
+                "{}, the file has about {} tokens]\n{}\n",
+                SUMMARY_MARKER, tokens, summary
+            ),
+            path: file.path,
+        });
+    }
+    Ok(summarized)
+}
+
+async fn file_summary<R: ConfigRepository, UR: UsageRepository, FR: FileSummaryRepository>(
+    repo: &R,
+    usage_repo: &UR,
+    summary_repo: &FR,
+    directory: &Path,
+    file: &Files,
+) -> Result<String> {
+    let hash = content_hash(&file.content);
+    if let Ok(cached) = summary_repo.fetch_summary_by_hash(&hash) {
+        return Ok(cached.summary);
+    }
+
+    let input = format!("{}\n```\n{}```", file.path, file.content);
+    let summary = prompt(repo, usage_repo, "ask", directory, SUMMARY_PROMPT, &input).await?;
+    let _ = summary_repo.add_summary(&FileSummaryEntity::new(
+        hash,
+        file.path.clone(),
+        summary.clone(),
+    ));
+    Ok(summary)
+}
+
+fn content_hash(content: &str) -> String {
+    format!("{:x}", Sha256::digest(content.as_bytes()))
+}
@@ -0,0 +1,124 @@
+use regex::Regex;
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Bump {
+    Patch,
+    Minor,
+    Major,
+}
+
+impl Bump {
+    pub fn from_str(value: &str) -> Option<Self> {
+        match value
+            .trim_matches(|c: char| !c.is_alphanumeric())
+            .to_lowercase()
+            .as_str()
+        {
+            "major" => Some(Self::Major),
+            "minor" => Some(Self::Minor),
+            "patch" => Some(Self::Patch),
+            _ => None,
+        }
+    }
+
+    pub fn to_key(self) -> &'static str {
+        match self {
+            Self::Major => "major",
+            Self::Minor => "minor",
+            Self::Patch => "patch",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Version {
+    pub prefix: String,
+    pub major: u64,
+    pub minor: u64,
+    pub patch: u64,
+}
+
+impl Version {
+    pub fn parse(tag: &str) -> Option<Self> {
+        let (prefix, version) = match tag.strip_prefix('v') {
+            Some(version) => ("v", version),
+            None => ("", tag),
+        };
+        let version = version.split(['-', '+']).next()?;
+        let mut parts = version.split('.').map(|part| part.parse::<u64>());
+        let (Some(Ok(major)), Some(Ok(minor)), Some(Ok(patch)), None) =
+            (parts.next(), parts.next(), parts.next(), parts.next())
+        else {
+            return None;
+        };
+        Some(Self {
+            prefix: prefix.to_string(),
+            major,
+            minor,
+            patch,
+        })
+    }
+
+    pub fn bump(&self, bump: Bump) -> Self {
+        let (major, minor, patch) = match bump {
+            Bump::Major => (self.major + 1, 0, 0),
+            Bump::Minor => (self.major, self.minor + 1, 0),
+            Bump::Patch => (self.major, self.minor, self.patch + 1),
+        };
+        Self {
+            prefix: self.prefix.clone(),
+            major,
+            minor,
+            patch,
+        }
+    }
+}
+
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}{}.{}.{}",
+            self.prefix, self.major, self.minor, self.patch
+        )
+    }
+}
+
+pub struct Commit {
+    pub sha: String,
+    pub subject: String,
+    pub body: String,
+}
+
+impl Commit {
+    pub fn conventional_bump(&self) -> Bump {
+        let re = Regex::new(r"^(\w+)(\([^)]*\))?(!)?:").unwrap();
+        let breaking_footer = self.body.lines().any(|line| {
+            line.starts_with("BREAKING CHANGE:") || line.starts_with("BREAKING-CHANGE:")
+        });
+        match re.captures(&self.subject) {
+            Some(captures) if captures.get(3).is_some() => Bump::Major,
+            _ if breaking_footer => Bump::Major,
+            Some(captures) if &captures[1] == "feat" => Bump::Minor,
+            _ => Bump::Patch,
+        }
+    }
+}
+
+pub fn parse_log(log: &str) -> Vec<Commit> {
+    log.split('\x1e')
+        .filter_map(|record| {
+            let mut fields = record.trim_start_matches('\n').splitn(3, '\x1f');
+            let sha = fields.next()?.trim();
+            if sha.is_empty() {
+                return None;
+            }
+            Some(Commit {
+                sha: sha.to_string(),
+                subject: fields.next().unwrap_or_default().trim().to_string(),
+                body: fields.next().unwrap_or_default().trim().to_string(),
+            })
+        })
+        .collect()
+}
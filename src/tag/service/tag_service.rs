@@ -0,0 +1,134 @@
+use crate::config::repository::ConfigRepository;
+use crate::git::repository::{repo_root, run_git};
+use crate::openai::service::prompt::prompt;
+use crate::tag::model::{parse_log, Bump, Commit, Version};
+use crate::usage::model::token_usage::estimate_tokens;
+use crate::usage::repository::UsageRepository;
+use anyhow::{anyhow, Result};
+use colored::*;
+use std::path::Path;
+
+const MAX_DIFF_TOKENS: u32 = 12000;
+
+const TAG_PROMPT: &str = "
+You decide the next semantic version of a project.
+You get the commits since the last release and the diff between the release and now.
+Look for breaking changes in public APIs, command-line flags, config formats and data formats,
+new features, and fixes.
+Answer with MAJOR, MINOR or PATCH alone on the first line, then justify it in at most five short bullet points.";
+
+pub async fn suggest<R: ConfigRepository, UR: UsageRepository>(
+    repo: &R,
+    usage_repo: &UR,
+    create: bool,
+) -> Result<()> {
+    let dir = repo_root(Path::new("."))?;
+    let last_tag = run_git(&dir, &["describe", "--tags", "--abbrev=0"])
+        .ok()
+        .map(|tag| tag.trim().to_string());
+    let current = match &last_tag {
+        Some(tag) => Version::parse(tag)
+            .ok_or_else(|| anyhow!("the last tag '{}' is not a semantic version", tag))?,
+        None => Version::parse("v0.0.0").unwrap(),
+    };
+    let range = match &last_tag {
+        Some(tag) => format!("{}..HEAD", tag),
+        None => "HEAD".to_string(),
+    };
+    let commits = parse_log(&run_git(
+        &dir,
+        &["log", "--format=%h%x1f%s%x1f%b%x1e", &range],
+    )?);
+    if commits.is_empty() {
+        return Err(anyhow!(
+            "no commits since {}, nothing to release",
+            last_tag.as_deref().unwrap_or("the start")
+        ));
+    }
+
+    let conventional = commits
+        .iter()
+        .map(Commit::conventional_bump)
+        .max()
+        .unwrap_or(Bump::Patch);
+    let base = match &last_tag {
+        Some(tag) => tag.clone(),
+        None => run_git(&dir, &["hash-object", "-t", "tree", "/dev/null"])?
+            .trim()
+            .to_string(),
+    };
+    let diff = run_git(&dir, &["diff", "-M", &base, "HEAD"])?;
+    let diff = if estimate_tokens(&diff) > MAX_DIFF_TOKENS {
+        let truncated = diff
+            .chars()
+            .take(MAX_DIFF_TOKENS as usize * 4)
+            .collect::<String>();
+        format!("{}\n... [diff truncated]\n", truncated)
+    } else {
+        diff
+    };
+    let log = commits
+        .iter()
+        .map(|commit| format!("{} {}\n{}", commit.sha, commit.subject, commit.body))
+        .collect::<Vec<String>>()
+        .join("\n");
+    let question = format!(
+        "current version: {}\n\ncommits:\n{}\n\ndiff:\n{}",
+        current, log, diff
+    );
+    let answer = prompt(repo, usage_repo, "tag", &dir, TAG_PROMPT, &question).await?;
+    let mut lines = answer.trim().lines();
+    let suggested = lines.next().and_then(Bump::from_str);
+    let justification = match suggested {
+        Some(_) => lines.collect::<Vec<&str>>().join("\n").trim().to_string(),
+        None => answer.trim().to_string(),
+    };
+
+    let bump = suggested.unwrap_or(Bump::Patch).max(conventional);
+    let next = current.bump(bump);
+    match &last_tag {
+        Some(tag) => println!("last tag {}, {} commits since", tag, commits.len()),
+        None => println!("no tags yet, {} commits", commits.len()),
+    }
+    for level in [Bump::Major, Bump::Minor] {
+        let marked = commits
+            .iter()
+            .filter(|commit| commit.conventional_bump() == level)
+            .collect::<Vec<&Commit>>();
+        for commit in marked {
+            println!(
+                "  {} {} {}",
+                level.to_key().yellow(),
+                commit.sha.dimmed(),
+                commit.subject
+            );
+        }
+    }
+    if suggested.is_some_and(|suggested| suggested < conventional) {
+        println!(
+            "{}",
+            format!(
+                "the commit messages call for a {} release, overriding the model's suggestion",
+                conventional.to_key()
+            )
+            .dimmed()
+        );
+    }
+    println!();
+    println!(
+        "{}",
+        format!("suggested: {} ({})", next, bump.to_key()).bold()
+    );
+    println!("{}", justification);
+
+    if create {
+        let message = format!("{}\n\n{}", next, justification);
+        run_git(&dir, &["tag", "-a", &next.to_string(), "-m", &message])?;
+        println!();
+        println!(
+            "created tag {}, push it with `git push origin {}`",
+            next, next
+        );
+    }
+    Ok(())
+}
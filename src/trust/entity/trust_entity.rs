@@ -0,0 +1,17 @@
+use chrono::NaiveDateTime;
+
+pub struct TrustEntity {
+    pub path: String,
+    pub trusted: bool,
+    pub decided_at: NaiveDateTime,
+}
+
+impl TrustEntity {
+    pub fn new(path: String, trusted: bool, decided_at: NaiveDateTime) -> Self {
+        Self {
+            path,
+            trusted,
+            decided_at,
+        }
+    }
+}
@@ -0,0 +1,15 @@
+use super::entity::trust_entity::TrustEntity;
+use std::fmt::Debug;
+
+pub(crate) mod trust_repository;
+
+pub trait TrustRepository
+where
+    Self::Error: Debug,
+{
+    type Error;
+
+    fn fetch_trust(&self) -> Result<Vec<TrustEntity>, Self::Error>;
+    fn save_trust(&self, trust: &TrustEntity) -> Result<(), Self::Error>;
+    fn remove_trust(&self, path: &str) -> Result<usize, Self::Error>;
+}
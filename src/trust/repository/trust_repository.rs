@@ -0,0 +1,47 @@
+use super::TrustRepository;
+use crate::repository::db::SqliteRepository;
+use crate::trust::entity::trust_entity::TrustEntity;
+use chrono::NaiveDateTime;
+use rusqlite::{params, Result};
+
+const DATE_TIME_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+
+impl TrustRepository for SqliteRepository {
+    type Error = rusqlite::Error;
+
+    fn fetch_trust(&self) -> Result<Vec<TrustEntity>, Self::Error> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT path, trusted, decided_at FROM trusted_directories ORDER BY path")?;
+        let rows = stmt.query_map([], |row| {
+            let decided_at_str: String = row.get(2)?;
+            let decided_at = NaiveDateTime::parse_from_str(&decided_at_str, DATE_TIME_FORMAT)
+                .expect("Invalid DateTime format");
+
+            Ok(TrustEntity::new(row.get(0)?, row.get(1)?, decided_at))
+        })?;
+
+        let mut trust = Vec::new();
+        for entry in rows {
+            trust.push(entry?);
+        }
+        Ok(trust)
+    }
+
+    fn save_trust(&self, trust: &TrustEntity) -> Result<(), Self::Error> {
+        let decided_at_str = trust.decided_at.format(DATE_TIME_FORMAT).to_string();
+        self.conn.execute(
+            "INSERT OR REPLACE INTO trusted_directories (path, trusted, decided_at)
+             VALUES (?1, ?2, ?3)",
+            params![trust.path, trust.trusted, decided_at_str],
+        )?;
+        Ok(())
+    }
+
+    fn remove_trust(&self, path: &str) -> Result<usize, Self::Error> {
+        self.conn.execute(
+            "DELETE FROM trusted_directories WHERE path = ?1",
+            params![path],
+        )
+    }
+}
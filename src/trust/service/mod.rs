@@ -0,0 +1 @@
+pub(crate) mod trust_service;
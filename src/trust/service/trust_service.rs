@@ -0,0 +1,148 @@
+use crate::args::TrustAction;
+use crate::common::terminal::{ask, is_interactive};
+use crate::git::repository::repo_root;
+use crate::trust::entity::trust_entity::TrustEntity;
+use crate::trust::repository::TrustRepository;
+use anyhow::{anyhow, Result};
+use chrono::Utc;
+use colored::*;
+use std::path::{Path, PathBuf};
+
+pub fn trust<TR: TrustRepository>(trust_repo: &TR, action: &TrustAction) -> Result<()> {
+    match action {
+        TrustAction::List => list(trust_repo),
+        TrustAction::Add { directory } => {
+            let root = trust_root(Path::new(directory))?;
+            save(trust_repo, &root, true)?;
+            println!("trusted {}", root.display());
+            Ok(())
+        }
+        TrustAction::Remove { directory } => {
+            let root = trust_root(Path::new(directory))?;
+            let removed = trust_repo
+                .remove_trust(&root.to_string_lossy())
+                .map_err(|err| anyhow!("could not remove the trust decision: {:?}", err))?;
+            match removed {
+                0 => println!("no decision was stored for {}", root.display()),
+                _ => println!(
+                    "forgot {}, termai asks again the next time it reads it",
+                    root.display()
+                ),
+            }
+            Ok(())
+        }
+    }
+}
+
+pub fn is_trusted<TR: TrustRepository>(
+    trust_repo: &TR,
+    directory: &Path,
+    override_trust: bool,
+) -> Result<bool> {
+    if override_trust {
+        return Ok(true);
+    }
+    let root = trust_root(directory)?;
+    if let Some(trusted) = decision(trust_repo, &root) {
+        return Ok(trusted);
+    }
+    if !is_interactive() {
+        return Ok(false);
+    }
+    let trusted = ask(
+        &format!(
+            "{} is not trusted yet. Trust it? Its .termai.toml can choose files to send and commands may run in it.",
+            root.display()
+        ),
+        false,
+    )?;
+    save(trust_repo, &root, trusted)?;
+    Ok(trusted)
+}
+
+pub fn require_trust<TR: TrustRepository>(
+    trust_repo: &TR,
+    directory: &Path,
+    override_trust: bool,
+    feature: &str,
+) -> Result<()> {
+    if is_trusted(trust_repo, directory, override_trust)? {
+        return Ok(());
+    }
+    Err(untrusted_error(directory, feature))
+}
+
+pub fn untrusted_error(directory: &Path, feature: &str) -> anyhow::Error {
+    let root = trust_root(directory).unwrap_or_else(|_| directory.to_path_buf());
+    anyhow!(
+        "{} is turned off in {}, it is not a trusted directory. Trust it with `termai trust add {}` or pass --trust for this run",
+        feature,
+        root.display(),
+        root.display()
+    )
+}
+
+fn trust_root(directory: &Path) -> Result<PathBuf> {
+    let directory = match directory.is_file() {
+        true => directory.parent().unwrap_or(Path::new(".")),
+        false => directory,
+    };
+    let directory = match directory.as_os_str().is_empty() {
+        true => Path::new("."),
+        false => directory,
+    };
+    match repo_root(directory) {
+        Ok(root) => Ok(root),
+        Err(_) => directory
+            .canonicalize()
+            .map_err(|err| anyhow!("could not resolve {}: {:?}", directory.display(), err)),
+    }
+}
+
+fn decision<TR: TrustRepository>(trust_repo: &TR, root: &Path) -> Option<bool> {
+    trust_repo
+        .fetch_trust()
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|entry| root.starts_with(&entry.path))
+        .max_by_key(|entry| entry.path.len())
+        .map(|entry| entry.trusted)
+}
+
+fn save<TR: TrustRepository>(trust_repo: &TR, root: &Path, trusted: bool) -> Result<()> {
+    let entity = TrustEntity::new(
+        root.to_string_lossy().to_string(),
+        trusted,
+        Utc::now().naive_utc(),
+    );
+    trust_repo
+        .save_trust(&entity)
+        .map_err(|err| anyhow!("could not store the trust decision: {:?}", err))
+}
+
+fn list<TR: TrustRepository>(trust_repo: &TR) -> Result<()> {
+    let entries = trust_repo
+        .fetch_trust()
+        .map_err(|err| anyhow!("could not read the trusted directories: {:?}", err))?;
+    if entries.is_empty() {
+        println!("no directories were trusted or refused yet");
+        return Ok(());
+    }
+    for entry in &entries {
+        let state = match entry.trusted {
+            true => "trusted".green(),
+            false => "refused".red(),
+        };
+        println!(
+            "{:<9} {}  {}",
+            state,
+            entry.path,
+            entry
+                .decided_at
+                .format("%Y-%m-%d %H:%M")
+                .to_string()
+                .dimmed()
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1 @@
+pub(crate) mod upload_entity;
@@ -0,0 +1,30 @@
+use chrono::NaiveDateTime;
+
+pub struct UploadEntity {
+    pub file_id: String,
+    pub base_url: String,
+    pub hash: String,
+    pub path: String,
+    pub bytes: i64,
+    pub created_at: NaiveDateTime,
+}
+
+impl UploadEntity {
+    pub fn new(
+        file_id: String,
+        base_url: String,
+        hash: String,
+        path: String,
+        bytes: i64,
+        created_at: NaiveDateTime,
+    ) -> Self {
+        Self {
+            file_id,
+            base_url,
+            hash,
+            path,
+            bytes,
+            created_at,
+        }
+    }
+}
@@ -0,0 +1,64 @@
+use crate::openai::model::api_error::ApiError;
+use reqwest::StatusCode;
+use std::collections::HashMap;
+
+// File ids of uploaded attachments by the hash of their content.
+pub type Uploads = HashMap<String, String>;
+
+// The chat file part only takes PDFs, other attachments are always sent inline.
+pub const UPLOAD_CONTENT_TYPE: &str = "application/pdf";
+
+// True when the API refused the request because of a file part, for example an
+// expired or deleted file id. Network errors, rate limits and server errors are
+// not a reason to resend the files inline.
+pub fn rejects_uploads(err: &anyhow::Error) -> bool {
+    err.downcast_ref::<ApiError>().is_some_and(|err| {
+        matches!(err.status, StatusCode::BAD_REQUEST | StatusCode::NOT_FOUND)
+            && err.body.to_lowercase().contains("file")
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::anyhow;
+
+    fn api_error(status: StatusCode, body: &str) -> anyhow::Error {
+        ApiError {
+            base_url: "https://api.openai.com/v1".to_string(),
+            status,
+            body: body.to_string(),
+        }
+        .into()
+    }
+
+    #[test]
+    fn a_rejected_file_id_falls_back() {
+        let err = api_error(
+            StatusCode::BAD_REQUEST,
+            r#"{"error":{"message":"Invalid file id","param":"messages[1].content[1].file.file_id"}}"#,
+        );
+        assert!(rejects_uploads(&err));
+        assert!(rejects_uploads(&api_error(
+            StatusCode::NOT_FOUND,
+            "No such File object: file-abc"
+        )));
+    }
+
+    #[test]
+    fn other_failures_do_not_fall_back() {
+        assert!(!rejects_uploads(&api_error(
+            StatusCode::TOO_MANY_REQUESTS,
+            "file rate limit reached"
+        )));
+        assert!(!rejects_uploads(&api_error(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "file service unavailable"
+        )));
+        assert!(!rejects_uploads(&api_error(
+            StatusCode::BAD_REQUEST,
+            "maximum context length exceeded"
+        )));
+        assert!(!rejects_uploads(&anyhow!("connection reset, file")));
+    }
+}
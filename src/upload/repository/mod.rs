@@ -0,0 +1,17 @@
+use super::entity::upload_entity::UploadEntity;
+use std::fmt::Debug;
+
+pub(crate) mod upload_repository;
+
+pub trait UploadRepository
+where
+    Self::Error: Debug,
+{
+    type Error;
+
+    fn fetch_upload(&self, base_url: &str, hash: &str)
+        -> Result<Option<UploadEntity>, Self::Error>;
+    fn fetch_uploads(&self) -> Result<Vec<UploadEntity>, Self::Error>;
+    fn save_upload(&self, upload: &UploadEntity) -> Result<(), Self::Error>;
+    fn delete_upload(&self, file_id: &str) -> Result<usize, Self::Error>;
+}
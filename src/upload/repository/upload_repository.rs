@@ -0,0 +1,71 @@
+use super::UploadRepository;
+use crate::repository::db::SqliteRepository;
+use crate::upload::entity::upload_entity::UploadEntity;
+use chrono::NaiveDateTime;
+use rusqlite::{params, OptionalExtension, Result, Row};
+
+const DATE_TIME_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+
+impl UploadRepository for SqliteRepository {
+    type Error = rusqlite::Error;
+
+    fn fetch_upload(
+        &self,
+        base_url: &str,
+        hash: &str,
+    ) -> Result<Option<UploadEntity>, Self::Error> {
+        self.conn
+            .query_row(
+                "SELECT file_id, base_url, hash, path, bytes, created_at FROM uploads
+                 WHERE base_url = ?1 AND hash = ?2",
+                params![base_url, hash],
+                to_entity,
+            )
+            .optional()
+    }
+
+    fn fetch_uploads(&self) -> Result<Vec<UploadEntity>, Self::Error> {
+        let mut stmt = self.conn.prepare(
+            "SELECT file_id, base_url, hash, path, bytes, created_at FROM uploads
+             ORDER BY created_at",
+        )?;
+        let rows = stmt.query_map([], to_entity)?;
+        rows.collect()
+    }
+
+    fn save_upload(&self, upload: &UploadEntity) -> Result<(), Self::Error> {
+        let created_at_str = upload.created_at.format(DATE_TIME_FORMAT).to_string();
+        self.conn.execute(
+            "INSERT OR REPLACE INTO uploads (file_id, base_url, hash, path, bytes, created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![
+                upload.file_id,
+                upload.base_url,
+                upload.hash,
+                upload.path,
+                upload.bytes,
+                created_at_str
+            ],
+        )?;
+        Ok(())
+    }
+
+    fn delete_upload(&self, file_id: &str) -> Result<usize, Self::Error> {
+        self.conn
+            .execute("DELETE FROM uploads WHERE file_id = ?1", params![file_id])
+    }
+}
+
+fn to_entity(row: &Row) -> Result<UploadEntity> {
+    let created_at_str: String = row.get(5)?;
+    let created_at = NaiveDateTime::parse_from_str(&created_at_str, DATE_TIME_FORMAT)
+        .expect("Invalid DateTime format");
+    Ok(UploadEntity::new(
+        row.get(0)?,
+        row.get(1)?,
+        row.get(2)?,
+        row.get(3)?,
+        row.get(4)?,
+        created_at,
+    ))
+}
@@ -0,0 +1,182 @@
+use crate::args::UploadsAction;
+use crate::config::model::keys::ConfigKeys;
+use crate::config::repository::ConfigRepository;
+use crate::config::service::config_service;
+use crate::openai::adapter::open_ai_adapter;
+use crate::output::progress::{status, Progress};
+use crate::provider::model::{Endpoint, Provider};
+use crate::session::model::attachment::Attachment;
+use crate::session::model::session::Session;
+use crate::upload::entity::upload_entity::UploadEntity;
+use crate::upload::model::{Uploads, UPLOAD_CONTENT_TYPE};
+use crate::upload::repository::UploadRepository;
+use anyhow::{anyhow, Result};
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use chrono::Utc;
+
+const DEFAULT_MIN_KB: usize = 100;
+
+pub fn is_enabled<R: ConfigRepository>(repo: &R, endpoint: &Endpoint) -> bool {
+    endpoint.provider == Provider::OpenAi
+        && config_service::fetch_by_key(repo, &ConfigKeys::FileUploads.to_key())
+            .is_ok_and(|config| config.value == "on")
+}
+
+pub async fn upload_large_attachments<R: ConfigRepository, UPR: UploadRepository>(
+    repo: &R,
+    upload_repo: &UPR,
+    endpoint: &Endpoint,
+    session: &Session,
+) -> Uploads {
+    let mut uploads = Uploads::new();
+    if !is_enabled(repo, endpoint) {
+        return uploads;
+    }
+    let min_bytes = config_service::fetch_by_key(repo, &ConfigKeys::FileUploadMinKb.to_key())
+        .ok()
+        .and_then(|config| config.value.parse::<usize>().ok())
+        .unwrap_or(DEFAULT_MIN_KB)
+        * 1024;
+    let documents = session
+        .messages
+        .iter()
+        .flat_map(|message| message.document_attachments());
+    for attachment in documents {
+        let hash = attachment.hash();
+        if uploads.contains_key(&hash) {
+            continue;
+        }
+        let Ok(content) = STANDARD.decode(&attachment.content) else {
+            continue;
+        };
+        if content.len() < min_bytes {
+            continue;
+        }
+        if let Some(upload) = upload_repo
+            .fetch_upload(&endpoint.base_url, &hash)
+            .ok()
+            .flatten()
+        {
+            uploads.insert(hash, upload.file_id);
+            continue;
+        }
+        match upload(upload_repo, endpoint, attachment, &content, &hash).await {
+            Ok(file_id) => {
+                uploads.insert(hash, file_id);
+            }
+            Err(err) => status(&format!(
+                "could not upload {}, sending it inline: {}",
+                attachment.path, err
+            )),
+        }
+    }
+    uploads
+}
+
+pub async fn uploads<R: ConfigRepository, UPR: UploadRepository>(
+    repo: &R,
+    upload_repo: &UPR,
+    action: &UploadsAction,
+) -> Result<()> {
+    match action {
+        UploadsAction::List => list_uploads(upload_repo),
+        UploadsAction::Delete { file_id, .. } => {
+            delete_uploads(repo, upload_repo, file_id.as_deref()).await
+        }
+    }
+}
+
+pub fn list_uploads<UPR: UploadRepository>(upload_repo: &UPR) -> Result<()> {
+    let uploads = upload_repo
+        .fetch_uploads()
+        .map_err(|err| anyhow!("could not read the uploads: {:?}", err))?;
+    if uploads.is_empty() {
+        println!("no uploaded files, large PDF attachments are uploaded after `termai config set file_uploads on`");
+        return Ok(());
+    }
+    for upload in uploads {
+        println!(
+            "{}  {:>7} KB  {}  {}",
+            upload.file_id,
+            (upload.bytes as u64).div_ceil(1024),
+            upload.created_at.format("%Y-%m-%d %H:%M"),
+            upload.path
+        );
+    }
+    Ok(())
+}
+
+pub async fn delete_uploads<R: ConfigRepository, UPR: UploadRepository>(
+    repo: &R,
+    upload_repo: &UPR,
+    file_id: Option<&str>,
+) -> Result<()> {
+    let uploads = upload_repo
+        .fetch_uploads()
+        .map_err(|err| anyhow!("could not read the uploads: {:?}", err))?
+        .into_iter()
+        .filter(|upload| file_id.is_none_or(|file_id| upload.file_id == file_id))
+        .collect::<Vec<UploadEntity>>();
+    if let (Some(file_id), true) = (file_id, uploads.is_empty()) {
+        return Err(anyhow!(
+            "no upload with the id '{}', see `termai uploads list`",
+            file_id
+        ));
+    }
+    let api_key = config_service::fetch_by_key(repo, &ConfigKeys::ChatGptApiKey.to_key())
+        .ok()
+        .map(|config| config.value);
+    for upload in &uploads {
+        let endpoint = Endpoint {
+            provider: Provider::OpenAi,
+            base_url: upload.base_url.clone(),
+            api_key: api_key.clone(),
+        };
+        open_ai_adapter::delete_file(&endpoint, &upload.file_id)
+            .await
+            .map_err(|err| anyhow!("could not delete {}: {:?}", upload.file_id, err))?;
+        upload_repo
+            .delete_upload(&upload.file_id)
+            .map_err(|err| anyhow!("could not forget {}: {:?}", upload.file_id, err))?;
+    }
+    println!("deleted {} upload(s)", uploads.len());
+    Ok(())
+}
+
+async fn upload<UPR: UploadRepository>(
+    upload_repo: &UPR,
+    endpoint: &Endpoint,
+    attachment: &Attachment,
+    content: &[u8],
+    hash: &str,
+) -> Result<String> {
+    let uploading = Progress::spinner(&format!("uploading {}", attachment.path));
+    let file = open_ai_adapter::upload_file(
+        endpoint,
+        &attachment.file_name(),
+        UPLOAD_CONTENT_TYPE,
+        content,
+    )
+    .await;
+    uploading.finish();
+    let file = file?;
+    let bytes = file.bytes.unwrap_or(content.len() as u64);
+    let entity = UploadEntity::new(
+        file.id.clone(),
+        endpoint.base_url.clone(),
+        hash.to_string(),
+        attachment.path.clone(),
+        bytes as i64,
+        Utc::now().naive_utc(),
+    );
+    if let Err(err) = upload_repo.save_upload(&entity) {
+        status(&format!("could not remember the upload: {:?}", err));
+    }
+    status(&format!(
+        "uploaded {} ({} KB), later questions reference it instead of resending it",
+        attachment.path,
+        bytes.div_ceil(1024)
+    ));
+    Ok(file.id)
+}
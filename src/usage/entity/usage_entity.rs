@@ -0,0 +1,30 @@
+use chrono::NaiveDateTime;
+
+pub struct UsageEntity {
+    pub id: String,
+    pub session_id: String,
+    pub model: String,
+    pub prompt_tokens: u32,
+    pub completion_tokens: u32,
+    pub created_at: NaiveDateTime,
+}
+
+impl UsageEntity {
+    pub fn new(
+        id: String,
+        session_id: String,
+        model: String,
+        prompt_tokens: u32,
+        completion_tokens: u32,
+        created_at: NaiveDateTime,
+    ) -> Self {
+        Self {
+            id,
+            session_id,
+            model,
+            prompt_tokens,
+            completion_tokens,
+            created_at,
+        }
+    }
+}
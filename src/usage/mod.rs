@@ -0,0 +1,4 @@
+pub(crate) mod entity;
+pub(crate) mod model;
+pub(crate) mod repository;
+pub(crate) mod service;
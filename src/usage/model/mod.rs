@@ -0,0 +1,3 @@
+pub(crate) mod pricing;
+pub(crate) mod token_usage;
+pub(crate) mod watchdog;
@@ -0,0 +1,41 @@
+pub struct ModelPrice {
+    pub input_per_million: f64,
+    pub output_per_million: f64,
+}
+
+impl ModelPrice {
+    const fn new(input_per_million: f64, output_per_million: f64) -> Self {
+        Self {
+            input_per_million,
+            output_per_million,
+        }
+    }
+
+    pub fn cost(&self, prompt_tokens: u32, completion_tokens: u32) -> f64 {
+        (prompt_tokens as f64 * self.input_per_million
+            + completion_tokens as f64 * self.output_per_million)
+            / 1_000_000.0
+    }
+}
+
+const UNKNOWN_MODEL_PRICE: ModelPrice = ModelPrice::new(0.0, 0.0);
+
+const PRICES: [(&str, ModelPrice); 9] = [
+    ("gpt-4o-mini", ModelPrice::new(0.15, 0.60)),
+    ("gpt-4o", ModelPrice::new(2.50, 10.00)),
+    ("gpt-4-turbo", ModelPrice::new(10.00, 30.00)),
+    ("gpt-4", ModelPrice::new(30.00, 60.00)),
+    ("gpt-3.5-turbo", ModelPrice::new(0.50, 1.50)),
+    ("o1-mini", ModelPrice::new(1.10, 4.40)),
+    ("o1-preview", ModelPrice::new(15.00, 60.00)),
+    ("o1", ModelPrice::new(15.00, 60.00)),
+    ("o3-mini", ModelPrice::new(1.10, 4.40)),
+];
+
+pub fn price_for_model(model: &str) -> &'static ModelPrice {
+    PRICES
+        .iter()
+        .find(|(prefix, _)| model.starts_with(prefix))
+        .map(|(_, price)| price)
+        .unwrap_or(&UNKNOWN_MODEL_PRICE)
+}
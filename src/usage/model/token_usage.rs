@@ -0,0 +1,44 @@
+use crate::usage::entity::usage_entity::UsageEntity;
+use crate::usage::model::pricing::price_for_model;
+
+#[derive(Debug, Clone)]
+pub struct TokenUsage {
+    pub model: String,
+    pub prompt_tokens: u32,
+    pub completion_tokens: u32,
+}
+
+impl TokenUsage {
+    pub fn cost(&self) -> f64 {
+        let price = price_for_model(&self.model);
+        price.cost(self.prompt_tokens, self.completion_tokens)
+    }
+}
+
+impl From<&UsageEntity> for TokenUsage {
+    fn from(entity: &UsageEntity) -> Self {
+        Self {
+            model: entity.model.clone(),
+            prompt_tokens: entity.prompt_tokens,
+            completion_tokens: entity.completion_tokens,
+        }
+    }
+}
+
+pub fn total_cost(usages: &[TokenUsage]) -> f64 {
+    usages
+        .iter()
+        .map(TokenUsage::cost)
+        .fold(0.0, |total, cost| total + cost)
+}
+
+pub fn total_tokens(usages: &[TokenUsage]) -> u32 {
+    usages
+        .iter()
+        .map(|usage| usage.prompt_tokens + usage.completion_tokens)
+        .sum()
+}
+
+pub fn estimate_tokens(text: &str) -> u32 {
+    (text.chars().count() as u32).div_ceil(4)
+}
@@ -0,0 +1,105 @@
+use crate::usage::model::token_usage::{total_cost, total_tokens, TokenUsage};
+use std::time::{Duration, Instant};
+
+pub struct Watchdog {
+    started: Instant,
+    max_tokens: u32,
+    max_duration: Duration,
+    confirm_every: Option<u32>,
+}
+
+impl Watchdog {
+    pub fn new(max_tokens: u32, max_minutes: u64, confirm_every: Option<u32>) -> Self {
+        Self {
+            started: Instant::now(),
+            max_tokens,
+            max_duration: Duration::from_secs(max_minutes * 60),
+            confirm_every: confirm_every.filter(|every| *every > 0),
+        }
+    }
+
+    pub fn elapsed(&self) -> Duration {
+        self.started.elapsed()
+    }
+
+    pub fn summary(&self, usage: &[TokenUsage]) -> String {
+        let elapsed = self.started.elapsed().as_secs();
+        format!(
+            "{} tokens, ${:.4}, {}m{:02}s",
+            total_tokens(usage),
+            total_cost(usage),
+            elapsed / 60,
+            elapsed % 60
+        )
+    }
+
+    pub fn exceeded(&self, usage: &[TokenUsage]) -> Option<String> {
+        let tokens = total_tokens(usage);
+        if tokens >= self.max_tokens {
+            return Some(format!(
+                "the token cap of {} was reached ({} used)",
+                self.max_tokens, tokens
+            ));
+        }
+        if self.started.elapsed() >= self.max_duration {
+            return Some(format!(
+                "the time cap of {} minutes was reached",
+                self.max_duration.as_secs() / 60
+            ));
+        }
+        None
+    }
+
+    pub fn checkpoint_due(&self, completed: u32) -> bool {
+        self.confirm_every
+            .is_some_and(|every| completed > 0 && completed.is_multiple_of(every))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn usage(prompt_tokens: u32, completion_tokens: u32) -> TokenUsage {
+        TokenUsage {
+            model: "gpt-4o-mini".to_string(),
+            prompt_tokens,
+            completion_tokens,
+        }
+    }
+
+    #[test]
+    fn stays_quiet_below_the_caps() {
+        let watchdog = Watchdog::new(1000, 10, None);
+        assert_eq!(watchdog.exceeded(&[]), None);
+        assert_eq!(watchdog.exceeded(&[usage(400, 100), usage(300, 199)]), None);
+    }
+
+    #[test]
+    fn stops_at_the_token_cap_over_all_usage() {
+        let watchdog = Watchdog::new(1000, 10, None);
+        let reason = watchdog
+            .exceeded(&[usage(400, 100), usage(300, 200)])
+            .unwrap();
+        assert!(reason.contains("token cap of 1000"), "{}", reason);
+        assert!(reason.contains("1000 used"), "{}", reason);
+    }
+
+    #[test]
+    fn stops_at_the_time_cap() {
+        let watchdog = Watchdog::new(1000, 0, None);
+        let reason = watchdog.exceeded(&[]).unwrap();
+        assert!(reason.contains("time cap of 0 minutes"), "{}", reason);
+    }
+
+    #[test]
+    fn checkpoints_every_n_completed_iterations() {
+        let watchdog = Watchdog::new(1000, 10, Some(3));
+        let due = (0..=7)
+            .filter(|completed| watchdog.checkpoint_due(*completed))
+            .collect::<Vec<u32>>();
+        assert_eq!(due, vec![3, 6]);
+        assert!(!Watchdog::new(1000, 10, Some(0)).checkpoint_due(3));
+        assert!(!Watchdog::new(1000, 10, None).checkpoint_due(3));
+    }
+}
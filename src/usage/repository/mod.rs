@@ -0,0 +1,16 @@
+use super::entity::usage_entity::UsageEntity;
+use chrono::NaiveDateTime;
+use std::fmt::Debug;
+
+pub(crate) mod usage_repository;
+
+pub trait UsageRepository
+where
+    Self::Error: Debug,
+{
+    type Error;
+
+    fn fetch_usage_for_session(&self, session_id: &str) -> Result<Vec<UsageEntity>, Self::Error>;
+    fn fetch_usage_since(&self, since: NaiveDateTime) -> Result<Vec<UsageEntity>, Self::Error>;
+    fn add_usage(&self, usage: &UsageEntity) -> Result<(), Self::Error>;
+}
@@ -0,0 +1,79 @@
+use super::UsageRepository;
+use crate::repository::db::SqliteRepository;
+use crate::usage::entity::usage_entity::UsageEntity;
+use chrono::NaiveDateTime;
+use rusqlite::{params, Result, Row};
+
+const DATE_TIME_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+
+impl UsageRepository for SqliteRepository {
+    type Error = rusqlite::Error;
+
+    fn fetch_usage_for_session(&self, session_id: &str) -> Result<Vec<UsageEntity>, Self::Error> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, session_id, model, prompt_tokens, completion_tokens, created_at
+             FROM usage WHERE session_id = ?1",
+        )?;
+        let rows = stmt.query_map(params![session_id], row_to_usage_entity())?;
+
+        let mut usages = Vec::new();
+        for usage in rows {
+            usages.push(usage?);
+        }
+        Ok(usages)
+    }
+
+    fn fetch_usage_since(&self, since: NaiveDateTime) -> Result<Vec<UsageEntity>, Self::Error> {
+        let since_str = since.format(DATE_TIME_FORMAT).to_string();
+        let mut stmt = self.conn.prepare(
+            "SELECT id, session_id, model, prompt_tokens, completion_tokens, created_at
+             FROM usage WHERE created_at >= ?1",
+        )?;
+        let rows = stmt.query_map(params![since_str], row_to_usage_entity())?;
+
+        let mut usages = Vec::new();
+        for usage in rows {
+            usages.push(usage?);
+        }
+        Ok(usages)
+    }
+
+    fn add_usage(&self, usage: &UsageEntity) -> Result<(), Self::Error> {
+        let created_at_str = usage.created_at.format(DATE_TIME_FORMAT).to_string();
+        self.conn.execute(
+            "INSERT INTO usage (id, session_id, model, prompt_tokens, completion_tokens, created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![
+                usage.id,
+                usage.session_id,
+                usage.model,
+                usage.prompt_tokens,
+                usage.completion_tokens,
+                created_at_str
+            ],
+        )?;
+        Ok(())
+    }
+}
+
+fn row_to_usage_entity() -> fn(&Row) -> Result<UsageEntity> {
+    |row| {
+        let id: String = row.get(0)?;
+        let session_id: String = row.get(1)?;
+        let model: String = row.get(2)?;
+        let prompt_tokens: u32 = row.get(3)?;
+        let completion_tokens: u32 = row.get(4)?;
+        let created_at_str: String = row.get(5)?;
+        let created_at = NaiveDateTime::parse_from_str(&created_at_str, DATE_TIME_FORMAT)
+            .expect("Invalid DateTime format");
+
+        Ok(UsageEntity::new(
+            id,
+            session_id,
+            model,
+            prompt_tokens,
+            completion_tokens,
+            created_at,
+        ))
+    }
+}
@@ -0,0 +1,84 @@
+use crate::config::model::keys::ConfigKeys;
+use crate::config::repository::ConfigRepository;
+use crate::config::service::config_service;
+use crate::usage::model::token_usage::total_cost;
+use crate::usage::repository::UsageRepository;
+use crate::usage::service::usage_service;
+use anyhow::{anyhow, Result};
+use colored::*;
+
+#[derive(Debug, PartialEq)]
+pub enum BudgetMode {
+    Warn,
+    Block,
+}
+
+pub fn monthly_budget<R: ConfigRepository>(repo: &R) -> Option<f64> {
+    fetch_amount(repo, &ConfigKeys::BudgetMonthly)
+}
+
+pub fn session_budget<R: ConfigRepository>(repo: &R) -> Option<f64> {
+    fetch_amount(repo, &ConfigKeys::BudgetSession)
+}
+
+pub fn budget_mode<R: ConfigRepository>(repo: &R) -> BudgetMode {
+    match config_service::fetch_by_key(repo, &ConfigKeys::BudgetMode.to_key()) {
+        Ok(config) if config.value == "block" => BudgetMode::Block,
+        _ => BudgetMode::Warn,
+    }
+}
+
+pub fn check_budget<R: ConfigRepository, UR: UsageRepository>(
+    repo: &R,
+    usage_repo: &UR,
+    session_id: &str,
+    estimated_cost: f64,
+    ignore_budget: bool,
+) -> Result<()> {
+    let mut exceeded = Vec::new();
+
+    if let Some(budget) = session_budget(repo) {
+        let spent = total_cost(&usage_service::session_usage(usage_repo, session_id));
+        if spent + estimated_cost > budget {
+            exceeded.push(format!(
+                "session budget of ${:.2} (spent ${:.4})",
+                budget, spent
+            ));
+        }
+    }
+
+    if let Some(budget) = monthly_budget(repo) {
+        let spent = total_cost(&usage_service::monthly_usage(usage_repo));
+        if spent + estimated_cost > budget {
+            exceeded.push(format!(
+                "monthly budget of ${:.2} (spent ${:.4})",
+                budget, spent
+            ));
+        }
+    }
+
+    if exceeded.is_empty() || ignore_budget {
+        return Ok(());
+    }
+
+    let message = format!(
+        "this request would exceed the {}",
+        exceeded.join(" and the ")
+    );
+    match budget_mode(repo) {
+        BudgetMode::Warn => {
+            eprintln!("{} {}", "warning:".yellow().bold(), message);
+            Ok(())
+        }
+        BudgetMode::Block => Err(anyhow!(
+            "{}, use --ignore-budget to send it anyway",
+            message
+        )),
+    }
+}
+
+fn fetch_amount<R: ConfigRepository>(repo: &R, key: &ConfigKeys) -> Option<f64> {
+    config_service::fetch_by_key(repo, &key.to_key())
+        .ok()
+        .and_then(|config| config.value.parse::<f64>().ok())
+}
@@ -0,0 +1,2 @@
+pub(crate) mod budget_service;
+pub(crate) mod usage_service;
@@ -0,0 +1,84 @@
+use crate::common::unique_id::generate_uuid_v4;
+use crate::config::repository::ConfigRepository;
+use crate::usage::entity::usage_entity::UsageEntity;
+use crate::usage::model::token_usage::{total_cost, TokenUsage};
+use crate::usage::repository::UsageRepository;
+use crate::usage::service::budget_service;
+use anyhow::{anyhow, Result};
+use chrono::{Datelike, NaiveDate, NaiveDateTime, Utc};
+
+pub fn record_usage<UR: UsageRepository>(
+    usage_repo: &UR,
+    session_id: &str,
+    usage: &TokenUsage,
+) -> Result<()> {
+    let entity = UsageEntity::new(
+        generate_uuid_v4().to_string(),
+        session_id.to_string(),
+        usage.model.clone(),
+        usage.prompt_tokens,
+        usage.completion_tokens,
+        Utc::now().naive_utc(),
+    );
+    usage_repo
+        .add_usage(&entity)
+        .map_err(|err| anyhow!("could not record usage: {:?}", err))
+}
+
+pub fn session_usage<UR: UsageRepository>(usage_repo: &UR, session_id: &str) -> Vec<TokenUsage> {
+    usage_repo
+        .fetch_usage_for_session(session_id)
+        .unwrap_or_default()
+        .iter()
+        .map(TokenUsage::from)
+        .collect()
+}
+
+pub fn monthly_usage<UR: UsageRepository>(usage_repo: &UR) -> Vec<TokenUsage> {
+    usage_repo
+        .fetch_usage_since(start_of_month())
+        .unwrap_or_default()
+        .iter()
+        .map(TokenUsage::from)
+        .collect()
+}
+
+pub fn print_session_stats<R: ConfigRepository, UR: UsageRepository>(
+    repo: &R,
+    usage_repo: &UR,
+    session_id: &str,
+) {
+    let session_usage = session_usage(usage_repo, session_id);
+    let prompt_tokens: u32 = session_usage.iter().map(|u| u.prompt_tokens).sum();
+    let completion_tokens: u32 = session_usage.iter().map(|u| u.completion_tokens).sum();
+    let session_cost = total_cost(&session_usage);
+    let monthly_cost = total_cost(&monthly_usage(usage_repo));
+
+    println!("requests: {}", session_usage.len());
+    println!("prompt tokens: {}", prompt_tokens);
+    println!("completion tokens: {}", completion_tokens);
+    println!(
+        "session spend: ${:.4}{}",
+        session_cost,
+        budget_suffix(budget_service::session_budget(repo))
+    );
+    println!(
+        "monthly spend: ${:.4}{}",
+        monthly_cost,
+        budget_suffix(budget_service::monthly_budget(repo))
+    );
+}
+
+fn budget_suffix(budget: Option<f64>) -> String {
+    match budget {
+        Some(budget) => format!(" of ${:.2}", budget),
+        None => "".to_string(),
+    }
+}
+
+fn start_of_month() -> NaiveDateTime {
+    let today = Utc::now().date_naive();
+    NaiveDate::from_ymd_opt(today.year(), today.month(), 1)
+        .and_then(|date| date.and_hms_opt(0, 0, 0))
+        .expect("first day of the month is always valid")
+}